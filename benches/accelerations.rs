@@ -0,0 +1,97 @@
+use std::{fs::File, time::Duration};
+
+use concave_hull::f32::ConcaveHullBuilder;
+use criterion::{BenchmarkId, Criterion, criterion_group, criterion_main};
+use csv::ReaderBuilder;
+use parry2d::math::Point;
+
+fn load_data(path: &str) -> Vec<Point<f32>> {
+    let f = File::open(path).unwrap();
+
+    let mut reader = ReaderBuilder::new().has_headers(false).from_reader(f);
+
+    reader
+        .records()
+        .map(|r| {
+            let r = r.unwrap();
+            let x = r[0].parse().unwrap();
+            let y = r[1].parse().unwrap();
+
+            Point::<f32>::new(x, y)
+        })
+        .collect()
+}
+
+/// Generates `n` pseudo-random points in a 1000x1000 square
+///
+/// The points come from a fixed-seed SplitMix64, so the cloud is the same on every run, without needing a fixture.
+fn random_cloud(n: usize) -> Vec<Point<f32>> {
+    let mut state: u64 = 0x5EED;
+    let mut next = || {
+        state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^= z >> 31;
+        (z >> 11) as f32 / (1u64 << 53) as f32 * 1000.
+    };
+
+    (0..n).map(|_| Point::new(next(), next())).collect()
+}
+
+/// Turns off every acceleration, leaving the brute-force paths
+fn brute_force(builder: &ConcaveHullBuilder) -> ConcaveHullBuilder {
+    builder
+        .clone()
+        .cheap_convex_checks(false)
+        .angle_surrogate(false)
+        .parallel_checks(false)
+        .eager_finalize(false)
+        .small_cloud_queue(false)
+}
+
+/// Compares every acceleration together against the brute-force paths, on the same datasets
+///
+/// The individual accelerations have their own benchmarks; this one is for spotting regressions in the overall speedup.
+/// New accelerations should be turned off in [`brute_force`] as they land.
+pub fn criterion_benchmark(c: &mut Criterion) {
+    let mut group = c.benchmark_group("accelerations");
+    group
+        .measurement_time(Duration::from_secs_f32(30.))
+        .sample_size(20);
+
+    let datasets = [
+        ("polygon", load_data("./test_data/polygon.csv"), 40.),
+        (
+            "question mark",
+            load_data("./test_data/question_mark.csv"),
+            40.,
+        ),
+        (
+            "concaveman_1k",
+            load_data("./test_data/concaveman_1k.csv"),
+            1000.,
+        ),
+        ("random 10k", random_cloud(10_000), 50.),
+    ];
+
+    for (name, points, concavity) in &datasets {
+        // Some accelerations need the total order from stable ordering, so both sides use it
+        let accelerated = ConcaveHullBuilder::new(*concavity).stable_ordering(true);
+        let brute = brute_force(&accelerated);
+
+        group.bench_with_input(
+            BenchmarkId::new("accelerated", name),
+            points,
+            |b, points| b.iter(|| accelerated.compute(points)),
+        );
+        group.bench_with_input(
+            BenchmarkId::new("brute force", name),
+            points,
+            |b, points| b.iter(|| brute.compute(points)),
+        );
+    }
+}
+
+criterion_group!(accelerations, criterion_benchmark);
+criterion_main!(accelerations);
@@ -0,0 +1,47 @@
+use std::{fs::File, time::Duration};
+
+use concave_hull::f32::ConcaveHullBuilder;
+use criterion::{Criterion, criterion_group, criterion_main};
+use csv::ReaderBuilder;
+use parry2d::math::Point;
+
+fn load_data(path: &str) -> Vec<Point<f32>> {
+    let f = File::open(path).unwrap();
+
+    let mut reader = ReaderBuilder::new().has_headers(false).from_reader(f);
+
+    reader
+        .records()
+        .map(|r| {
+            let r = r.unwrap();
+            let x = r[0].parse().unwrap();
+            let y = r[1].parse().unwrap();
+
+            Point::<f32>::new(x, y)
+        })
+        .collect()
+}
+
+pub fn criterion_benchmark(c: &mut Criterion) {
+    let mut group = c.benchmark_group("capacity_hint");
+    group
+        .measurement_time(Duration::from_secs_f32(30.))
+        .sample_size(100);
+
+    // A low concavity makes for a large hull, which is where the hint matters
+    let concaveman_1k = load_data("./test_data/concaveman_1k.csv");
+    let builder = ConcaveHullBuilder::new(100.);
+    let hull = builder.compute(&concaveman_1k);
+    let hinted = builder.clone().capacity_hint(hull.len());
+    assert_eq!(hinted.compute(&concaveman_1k), hull);
+
+    group.bench_function("concaveman_1k no hint", |b| {
+        b.iter(|| builder.compute(&concaveman_1k))
+    });
+    group.bench_function("concaveman_1k exact hint", |b| {
+        b.iter(|| hinted.compute(&concaveman_1k))
+    });
+}
+
+criterion_group!(capacity_hint, criterion_benchmark);
+criterion_main!(capacity_hint);
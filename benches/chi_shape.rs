@@ -0,0 +1,68 @@
+use std::{fs::File, time::Duration};
+
+use concave_hull::f32::{DelaunayHuller, chi_shape, concave_hull};
+use criterion::{Criterion, criterion_group, criterion_main};
+use csv::ReaderBuilder;
+use parry2d::math::Point;
+
+fn load_data(path: &str) -> Vec<Point<f32>> {
+    let f = File::open(path).unwrap();
+
+    let mut reader = ReaderBuilder::new().has_headers(false).from_reader(f);
+
+    reader
+        .records()
+        .map(|r| {
+            let r = r.unwrap();
+            let x = r[0].parse().unwrap();
+            let y = r[1].parse().unwrap();
+
+            Point::<f32>::new(x, y)
+        })
+        .collect()
+}
+
+pub fn criterion_benchmark(c: &mut Criterion) {
+    let mut group = c.benchmark_group("chi_shape");
+    group
+        .measurement_time(Duration::from_secs_f32(30.))
+        .sample_size(100);
+
+    let concaveman_1k = load_data("./test_data/concaveman_1k.csv");
+    group.bench_function("concaveman_1k gift opening", |b| {
+        b.iter(|| concave_hull(&concaveman_1k, 1000.))
+    });
+    group.bench_function("concaveman_1k chi-shape", |b| {
+        b.iter(|| chi_shape(&concaveman_1k, 1000.))
+    });
+}
+
+pub fn sweep_benchmark(c: &mut Criterion) {
+    let mut group = c.benchmark_group("concavity_sweep");
+    group
+        .measurement_time(Duration::from_secs_f32(30.))
+        .sample_size(100);
+
+    let concaveman_1k = load_data("./test_data/concaveman_1k.csv");
+    let thresholds: Vec<f32> = (1..=10).map(|k| k as f32 * 200.).collect();
+    group.bench_function("concaveman_1k ten gift openings", |b| {
+        b.iter(|| {
+            thresholds
+                .iter()
+                .map(|&concavity| concave_hull(&concaveman_1k, concavity))
+                .collect::<Vec<_>>()
+        })
+    });
+    group.bench_function("concaveman_1k shared triangulation", |b| {
+        b.iter(|| {
+            let huller = DelaunayHuller::new(&concaveman_1k);
+            thresholds
+                .iter()
+                .map(|&threshold| huller.hull(threshold))
+                .collect::<Vec<_>>()
+        })
+    });
+}
+
+criterion_group!(chi_shape_group, criterion_benchmark, sweep_benchmark);
+criterion_main!(chi_shape_group);
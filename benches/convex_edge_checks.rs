@@ -0,0 +1,53 @@
+use std::{fs::File, time::Duration};
+
+use concave_hull::f32::ConcaveHullBuilder;
+use criterion::{Criterion, criterion_group, criterion_main};
+use csv::ReaderBuilder;
+use parry2d::math::Point;
+
+fn load_data(path: &str) -> Vec<Point<f32>> {
+    let f = File::open(path).unwrap();
+
+    let mut reader = ReaderBuilder::new().has_headers(false).from_reader(f);
+
+    reader
+        .records()
+        .map(|r| {
+            let r = r.unwrap();
+            let x = r[0].parse().unwrap();
+            let y = r[1].parse().unwrap();
+
+            Point::<f32>::new(x, y)
+        })
+        .collect()
+}
+
+pub fn criterion_benchmark(c: &mut Criterion) {
+    let mut group = c.benchmark_group("convex_edge_checks");
+    group
+        .measurement_time(Duration::from_secs_f32(30.))
+        .sample_size(100);
+
+    let question_mark = load_data("./test_data/question_mark.csv");
+    let builder = ConcaveHullBuilder::new(40.);
+    group.bench_function("question mark cheap", |b| {
+        b.iter(|| builder.compute(&question_mark))
+    });
+    let full = builder.clone().cheap_convex_checks(false);
+    group.bench_function("question mark full", |b| {
+        b.iter(|| full.compute(&question_mark))
+    });
+
+    let concaveman_1k = load_data("./test_data/concaveman_1k.csv");
+    let builder = ConcaveHullBuilder::new(1000.);
+    group.bench_function("concaveman_1k cheap", |b| {
+        b.iter(|| builder.compute(&concaveman_1k))
+    });
+    let full = builder.clone().cheap_convex_checks(false);
+    group.bench_function("concaveman_1k full", |b| {
+        b.iter(|| full.compute(&concaveman_1k))
+    });
+}
+
+criterion_group!(convex_edge_checks, criterion_benchmark);
+criterion_main!(convex_edge_checks);
@@ -0,0 +1,80 @@
+use std::{fs::File, time::Duration};
+
+use concave_hull::f32::ConcaveHullBuilder;
+use criterion::{Criterion, criterion_group, criterion_main};
+use csv::ReaderBuilder;
+use parry2d::math::Point;
+
+fn load_data(path: &str) -> Vec<Point<f32>> {
+    let f = File::open(path).unwrap();
+
+    let mut reader = ReaderBuilder::new().has_headers(false).from_reader(f);
+
+    reader
+        .records()
+        .map(|r| {
+            let r = r.unwrap();
+            let x = r[0].parse().unwrap();
+            let y = r[1].parse().unwrap();
+
+            Point::<f32>::new(x, y)
+        })
+        .collect()
+}
+
+/// Prints how many pushes and pops each mode makes on the edge queue, and checks that finalizing eagerly makes fewer
+fn report_queue_operations(
+    name: &str,
+    eager: &ConcaveHullBuilder,
+    heap: &ConcaveHullBuilder,
+    points: &[Point<f32>],
+) {
+    let eager_operations = eager.count_queue_operations(points);
+    let heap_operations = heap.count_queue_operations(points);
+    println!(
+        "{name}: {eager_operations} queue operations when finalizing eagerly, {heap_operations} through the heap"
+    );
+    assert!(eager_operations < heap_operations);
+}
+
+pub fn criterion_benchmark(c: &mut Criterion) {
+    let mut group = c.benchmark_group("eager_finalize");
+    group
+        .measurement_time(Duration::from_secs_f32(30.))
+        .sample_size(100);
+
+    // Large concavities finalize most edges as soon as they're made, which is where skipping the heap helps most
+    // Eager finalization only applies with stable ordering
+    let question_mark = load_data("./test_data/question_mark.csv");
+    let builder = ConcaveHullBuilder::new(40.).stable_ordering(true);
+    let heap = builder.clone().eager_finalize(false);
+    assert_eq!(
+        builder.compute(&question_mark),
+        heap.compute(&question_mark)
+    );
+    report_queue_operations("question mark", &builder, &heap, &question_mark);
+    group.bench_function("question mark eager", |b| {
+        b.iter(|| builder.compute(&question_mark))
+    });
+    group.bench_function("question mark heap", |b| {
+        b.iter(|| heap.compute(&question_mark))
+    });
+
+    let concaveman_1k = load_data("./test_data/concaveman_1k.csv");
+    let builder = ConcaveHullBuilder::new(1000.).stable_ordering(true);
+    let heap = builder.clone().eager_finalize(false);
+    assert_eq!(
+        builder.compute(&concaveman_1k),
+        heap.compute(&concaveman_1k)
+    );
+    report_queue_operations("concaveman_1k", &builder, &heap, &concaveman_1k);
+    group.bench_function("concaveman_1k eager", |b| {
+        b.iter(|| builder.compute(&concaveman_1k))
+    });
+    group.bench_function("concaveman_1k heap", |b| {
+        b.iter(|| heap.compute(&concaveman_1k))
+    });
+}
+
+criterion_group!(eager_finalize, criterion_benchmark);
+criterion_main!(eager_finalize);
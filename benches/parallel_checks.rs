@@ -0,0 +1,38 @@
+use std::time::Duration;
+
+use concave_hull::f32::ConcaveHullBuilder;
+use criterion::{Criterion, criterion_group, criterion_main};
+use parry2d::math::Point;
+
+/// Generates a dense, slightly jittered grid of `n` by `n` points
+///
+/// The jitter comes from a simple hash of the grid position, so the cloud is the same on every run.
+fn dense_grid(n: u32) -> Vec<Point<f32>> {
+    (0..n)
+        .flat_map(|x| (0..n).map(move |y| (x, y)))
+        .map(|(x, y)| {
+            let hash = (x.wrapping_mul(73_856_093) ^ y.wrapping_mul(19_349_663)) % 1000;
+            let jitter = hash as f32 / 1000. - 0.5;
+            Point::new(x as f32 + jitter * 0.4, y as f32 - jitter * 0.4)
+        })
+        .collect()
+}
+
+pub fn criterion_benchmark(c: &mut Criterion) {
+    let mut group = c.benchmark_group("parallel_checks");
+    group
+        .measurement_time(Duration::from_secs_f32(30.))
+        .sample_size(10);
+
+    // A concavity of zero digs as deep as possible, which makes for a hull with thousands of edges
+    let grid = dense_grid(100);
+    let builder = ConcaveHullBuilder::new(0.);
+    let serial = builder.clone().parallel_checks(false);
+    assert_eq!(builder.compute(&grid), serial.compute(&grid));
+
+    group.bench_function("dense grid parallel", |b| b.iter(|| builder.compute(&grid)));
+    group.bench_function("dense grid serial", |b| b.iter(|| serial.compute(&grid)));
+}
+
+criterion_group!(parallel_checks, criterion_benchmark);
+criterion_main!(parallel_checks);
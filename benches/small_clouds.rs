@@ -0,0 +1,49 @@
+use std::time::Duration;
+
+use concave_hull::f32::ConcaveHullBuilder;
+use criterion::{Criterion, criterion_group, criterion_main};
+use parry2d::math::Point;
+
+pub fn criterion_benchmark(c: &mut Criterion) {
+    let mut group = c.benchmark_group("small_clouds");
+    group
+        .measurement_time(Duration::from_secs_f32(10.))
+        .sample_size(100);
+
+    // A square with a point in the middle, which a concavity of zero digs in to
+    let square = [
+        Point::new(0., 0.),
+        Point::new(2., 0.),
+        Point::new(2., 2.),
+        Point::new(0., 2.),
+        Point::new(1., 1.),
+    ];
+    // A ring of seven points, just under the size where the heap takes over
+    let ring: Vec<Point<f32>> = [
+        (0., 0.),
+        (4., 0.),
+        (5., 2.),
+        (4., 4.),
+        (0., 4.),
+        (1., 2.),
+        (2., 1.),
+    ]
+    .into_iter()
+    .map(|(x, y)| Point::new(x, y))
+    .collect();
+
+    for (name, points) in [("square", &square[..]), ("seven points", &ring[..])] {
+        // The small cloud queue only applies with stable ordering
+        let builder = ConcaveHullBuilder::new(0.).stable_ordering(true);
+        let heap = builder.clone().small_cloud_queue(false);
+        assert_eq!(builder.compute(points), heap.compute(points));
+
+        group.bench_function(format!("{name} linear"), |b| {
+            b.iter(|| builder.compute(points))
+        });
+        group.bench_function(format!("{name} heap"), |b| b.iter(|| heap.compute(points)));
+    }
+}
+
+criterion_group!(small_clouds, criterion_benchmark);
+criterion_main!(small_clouds);
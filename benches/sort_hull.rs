@@ -0,0 +1,68 @@
+use std::{fs::File, time::Duration};
+
+use concave_hull::{
+    Edge,
+    f32::{Point, concave_hull_unsorted},
+    sort_hull,
+};
+use criterion::{BatchSize, Criterion, criterion_group, criterion_main};
+use csv::ReaderBuilder;
+
+fn load_data(path: &str) -> Vec<Point> {
+    let f = File::open(path).unwrap();
+
+    let mut reader = ReaderBuilder::new().has_headers(false).from_reader(f);
+
+    reader
+        .records()
+        .map(|r| {
+            let r = r.unwrap();
+            let x = r[0].parse().unwrap();
+            let y = r[1].parse().unwrap();
+
+            Point::new(x, y)
+        })
+        .collect()
+}
+
+/// The original ordering pass, which does a linear scan for each next edge, for comparison
+fn sort_hull_linear(mut concave_hull: Vec<Edge<f32>>) -> Vec<(usize, Point)> {
+    let mut sorted_hull = Vec::with_capacity(concave_hull.len());
+    let mut curr = concave_hull.pop().unwrap();
+
+    while !concave_hull.is_empty() {
+        let next = concave_hull
+            .iter()
+            .position(|edge| edge.i == curr.j)
+            .unwrap();
+        let next = concave_hull.swap_remove(next);
+
+        sorted_hull.push((curr.i, curr.point_i));
+        curr = next;
+    }
+    sorted_hull.push((curr.i, curr.point_i));
+
+    sorted_hull
+}
+
+pub fn criterion_benchmark(c: &mut Criterion) {
+    let mut group = c.benchmark_group("sort_hull");
+    group
+        .measurement_time(Duration::from_secs_f32(15.))
+        .sample_size(1000);
+
+    // A concavity of zero gives the largest possible hull, which is the worst case for ordering
+    let concaveman_1k = load_data("./test_data/concaveman_1k.csv");
+    let edges = concave_hull_unsorted(&concaveman_1k, 0.);
+    assert_eq!(sort_hull(edges.clone()), sort_hull_linear(edges.clone()));
+
+    group.bench_function("concaveman_1k adjacency map", |b| {
+        b.iter_batched(|| edges.clone(), sort_hull, BatchSize::SmallInput)
+    });
+    group.bench_function("concaveman_1k linear scan", |b| {
+        b.iter_batched(|| edges.clone(), sort_hull_linear, BatchSize::SmallInput)
+    });
+}
+
+criterion_group!(sort_hull_bench, criterion_benchmark);
+criterion_main!(sort_hull_bench);
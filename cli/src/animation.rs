@@ -0,0 +1,72 @@
+use std::io::Write;
+
+use concave_hull::f32::{Point, concave_hull_anytime};
+use image::{
+    Delay, DynamicImage, Frame,
+    codecs::gif::{GifEncoder, Repeat},
+};
+
+use crate::drawing::draw_points_and_hull;
+
+/// Renders the construction of the concave hull as an animated GIF, looping forever
+///
+/// The first frame is the convex hull, and the last frame is the final concave hull.
+/// In between, frames are spaced out evenly through the edge splits, so that there are at most `frames` frames.
+pub fn write_hull_gif(
+    points: &[Point],
+    concavity: f32,
+    frames: usize,
+    fps: u32,
+    writer: impl Write,
+) -> anyhow::Result<()> {
+    let stages: Vec<Vec<(usize, Point)>> = concave_hull_anytime(points, concavity).collect();
+
+    // Render one frame per `splits_per_frame` splits, then make sure the finished hull is always the last frame
+    let splits = stages.len().saturating_sub(1);
+    let splits_per_frame = splits.div_ceil(frames.max(2) - 1).max(1);
+    let mut chosen: Vec<&Vec<(usize, Point)>> = stages.iter().step_by(splits_per_frame).collect();
+    if !splits.is_multiple_of(splits_per_frame) {
+        chosen.extend(stages.last());
+    }
+
+    let delay = Delay::from_numer_denom_ms(1000, fps.max(1));
+    let frames = chosen.into_iter().map(|hull| {
+        let image = draw_points_and_hull(points.to_vec(), hull.clone(), false);
+
+        Frame::from_parts(DynamicImage::ImageRgb8(image).into_rgba8(), 0, 0, delay)
+    });
+
+    let mut encoder = GifEncoder::new(writer);
+    encoder.set_repeat(Repeat::Infinite)?;
+    encoder.encode_frames(frames)?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs::File, io::Cursor};
+
+    use concave_hull::f32::{CsvOptions, read_points_csv};
+    use image::{AnimationDecoder, codecs::gif::GifDecoder};
+
+    use super::*;
+
+    #[test]
+    fn polygon_gif() {
+        let f = File::open("../test_data/polygon.csv").unwrap();
+        let points: Vec<Point> = read_points_csv(f, &CsvOptions::new())
+            .collect::<Result<_, _>>()
+            .unwrap();
+
+        let mut gif = Vec::new();
+        write_hull_gif(&points, 0., 5, 10, &mut gif).unwrap();
+        assert!(gif.starts_with(b"GIF89a"));
+
+        let frames = GifDecoder::new(Cursor::new(gif))
+            .unwrap()
+            .into_frames()
+            .count();
+        assert!((2..=5).contains(&frames));
+    }
+}
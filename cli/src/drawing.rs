@@ -1,9 +1,6 @@
 use concave_hull::f32::{
-    Point,
-    parry2d::{
-        bounding_volume::{BoundingVolume, details::local_point_cloud_aabb},
-        math::Vector,
-    },
+    Point, hull_aabb,
+    parry2d::{bounding_volume::BoundingVolume, math::Vector},
 };
 use imageproc::{
     drawing::{draw_filled_circle_mut, draw_line_segment_mut},
@@ -20,7 +17,11 @@ const POINT_COLOR: Rgb<u8> = Rgb([255u8, 255u8, 255u8]);
 const FULL_SEGMENT_COLOR: Rgb<u8> = Rgb([255u8, 0u8, 0u8]);
 const FADED_SEGMENT_COLOR: Rgb<u8> = Rgb([255u8, 200u8, 200u8]);
 
-pub fn draw_points_and_hull(mut points: Vec<Point>, mut hull: Vec<Point>, debug: bool) -> RgbImage {
+pub fn draw_points_and_hull(
+    mut points: Vec<Point>,
+    mut hull: Vec<(usize, Point)>,
+    debug: bool,
+) -> RgbImage {
     // Note: coordinates are mirrored about the y axis before being drawn,
     // since imageproc uses the standard image coordinate space (y-down),
     // but parry (and, by extension, this crate) use the standard mathematical coordinate space (y-up).
@@ -31,10 +32,11 @@ pub fn draw_points_and_hull(mut points: Vec<Point>, mut hull: Vec<Point>, debug:
             .iter_mut()
             .for_each(|p| *p = p.coords.component_mul(&Vector::new(1.0, -1.0)).into());
         hull.iter_mut()
-            .for_each(|p| *p = p.coords.component_mul(&Vector::new(1.0, -1.0)).into());
+            .for_each(|(_, p)| *p = p.coords.component_mul(&Vector::new(1.0, -1.0)).into());
     }
 
-    let mut aabb = local_point_cloud_aabb(&points).loosened(IMG_PADDING);
+    // The hull encloses every point, so its bounding box covers the whole point cloud
+    let mut aabb = hull_aabb(&hull).loosened(IMG_PADDING);
     if debug {
         aabb.mins = Point::origin();
     }
@@ -54,8 +56,8 @@ pub fn draw_points_and_hull(mut points: Vec<Point>, mut hull: Vec<Point>, debug:
 
     for i in 0..hull.len() {
         let j = (i + 1) % hull.len();
-        let a = hull[i] - aabb.mins;
-        let b = hull[j] - aabb.mins;
+        let a = hull[i].1 - aabb.mins;
+        let b = hull[j].1 - aabb.mins;
 
         // Interpolate from full to faded as we go around
         let color = interpolate(
@@ -1,12 +1,19 @@
-use std::{fs::File, path::PathBuf};
+use std::{
+    fs::{self, File},
+    path::{Path, PathBuf},
+};
 
 use anyhow::Ok;
 use clap::Parser;
-use concave_hull::f32::{Point, concave_hull};
-use csv::{ReaderBuilder, Writer};
+use concave_hull::f32::{
+    CsvOptions, Point, area, concave_hull, hull_pockets, perimeter, read_points_csv,
+};
+use csv::Writer;
+use ndarray::Array2;
 
-use crate::drawing::draw_points_and_hull;
+use crate::{animation::write_hull_gif, drawing::draw_points_and_hull};
 
+mod animation;
 mod drawing;
 
 /// Basic CLI to interface with the concave hull library
@@ -16,17 +23,43 @@ struct Cli {
     /// Concavity parameter to use
     concavity: f32,
 
-    /// Path to input CSV file, with an x column and y column (in order)
-    input: String,
+    /// Paths to input CSV files, each with an x column and y column (in order)
+    ///
+    /// The files are concatenated into one point cloud, in the order given,
+    /// so output indices count through the first file, then the second, and so on.
+    #[arg(required = true)]
+    inputs: Vec<String>,
 
     /// Path to output a CSV of hull points to
     #[arg(short, long)]
     point_output: Option<String>,
 
+    /// Path to output the hull points to as a NumPy `.npy` file, holding an Nx2 array of floats
+    #[arg(long)]
+    npy_output: Option<String>,
+
     /// Path to output a PNG image of the points and hull to
     #[arg(short, long)]
     img_output: Option<String>,
 
+    /// Path to output an animated GIF of the hull being constructed to
+    #[arg(short, long)]
+    gif_output: Option<String>,
+
+    /// Maximum number of frames in the GIF
+    #[arg(long, default_value_t = 60)]
+    gif_frames: usize,
+
+    /// Frames per second of the GIF
+    #[arg(long, default_value_t = 10)]
+    gif_fps: u32,
+
+    /// Path to a directory to write each pocket carved out of the convex hull to, as its own CSV of boundary points
+    ///
+    /// Files are named `pocket_000.csv`, `pocket_001.csv`, and so on, in order around the hull.
+    #[arg(long)]
+    pockets_dir: Option<String>,
+
     /// Whether the input CSV has headers
     #[arg(short = 'd', long, default_value_t = false)]
     headers: bool,
@@ -34,38 +67,41 @@ struct Cli {
 
 fn main() -> anyhow::Result<()> {
     let args = Cli::parse();
-    let input = PathBuf::from(args.input);
+    let inputs: Vec<PathBuf> = args.inputs.iter().map(PathBuf::from).collect();
     let point_output = args.point_output.map(PathBuf::from);
+    let npy_output = args.npy_output.map(PathBuf::from);
     let img_output = args.img_output.map(PathBuf::from);
+    let gif_output = args.gif_output.map(PathBuf::from);
+    let pockets_dir = args.pockets_dir.map(PathBuf::from);
 
+    let names: Vec<String> = inputs.iter().map(|p| p.display().to_string()).collect();
     println!(
         "Generating concave hull for {} [concavity: {}]",
-        input.display(),
+        names.join(", "),
         args.concavity
     );
 
     // Read input points
-    let f = File::open(input)?;
-    let mut reader = ReaderBuilder::new()
-        .has_headers(args.headers)
-        .from_reader(f);
-
-    let in_points = reader
-        .records()
-        .map(|r| {
-            let r = r?;
-            let x = r[0].parse()?;
-            let y = r[1].parse()?;
-
-            Ok(Point::new(x, y))
-        })
-        .collect::<Result<Vec<_>, _>>()?;
+    let options = CsvOptions::new().has_headers(args.headers);
+    let (in_points, counts) = read_inputs(&inputs, &options)?;
+    for (input, count) in inputs.iter().zip(&counts) {
+        println!("Read {count} points from {}", input.display());
+    }
+    if inputs.len() > 1 {
+        println!("Read {} points in total", in_points.len());
+    }
 
     // Generate hull
     let hull = concave_hull(&in_points, args.concavity);
+    eprintln!("{}", summary(&in_points, &hull));
 
     // Output
-    if point_output.is_none() && img_output.is_none() {
+    if point_output.is_none()
+        && npy_output.is_none()
+        && img_output.is_none()
+        && gif_output.is_none()
+        && pockets_dir.is_none()
+    {
         println!("No output file provided. Terminating.");
     }
 
@@ -81,15 +117,198 @@ fn main() -> anyhow::Result<()> {
         }
     }
 
+    if let Some(npy_output) = npy_output {
+        println!("Writing concave hull points to {:?}", npy_output.display());
+
+        write_npy(&npy_output, &hull)?;
+    }
+
     if let Some(img_output) = img_output {
         println!(
             "Drawing image of points and hull at {:?}",
             img_output.display()
         );
 
-        let image = draw_points_and_hull(in_points, hull.iter().map(|(_, p)| *p).collect(), false);
+        let image = draw_points_and_hull(in_points.clone(), hull.clone(), false);
         image.save(img_output)?;
     }
 
+    if let Some(gif_output) = gif_output {
+        println!(
+            "Animating construction of the hull at {:?}",
+            gif_output.display()
+        );
+
+        write_hull_gif(
+            &in_points,
+            args.concavity,
+            args.gif_frames,
+            args.gif_fps,
+            File::create(gif_output)?,
+        )?;
+    }
+
+    if let Some(pockets_dir) = pockets_dir {
+        println!("Writing pockets to {:?}", pockets_dir.display());
+
+        let pockets = hull_pockets(&in_points, &hull);
+        write_pockets(&pockets_dir, &pockets)?;
+        println!("Wrote {} pockets", pockets.len());
+    }
+
     Ok(())
 }
+
+/// Reads every input file, concatenating their points into one cloud in the order given
+///
+/// Also returns the number of points read from each file, in the same order.
+fn read_inputs(
+    inputs: &[PathBuf],
+    options: &CsvOptions,
+) -> anyhow::Result<(Vec<Point>, Vec<usize>)> {
+    let mut points = Vec::new();
+    let mut counts = Vec::with_capacity(inputs.len());
+
+    for input in inputs {
+        let before = points.len();
+        for point in read_points_csv(File::open(input)?, options) {
+            points.push(point?);
+        }
+        counts.push(points.len() - before);
+    }
+
+    Ok((points, counts))
+}
+
+/// Writes the hull points to `path` as an Nx2 array, which can be loaded with `numpy.load`
+///
+/// Each row is one point, as `[x, y]`, in the same order as the hull.
+fn write_npy(path: &Path, hull: &[(usize, Point)]) -> anyhow::Result<()> {
+    let flat = hull.iter().flat_map(|(_, p)| [p.x, p.y]).collect();
+    let array = Array2::from_shape_vec((hull.len(), 2), flat)?;
+    ndarray_npy::write_npy(path, &array)?;
+
+    Ok(())
+}
+
+/// Writes each pocket to its own CSV of boundary points in `dir`, creating `dir` if it doesn't exist
+///
+/// Files are named after the position of the pocket in `pockets`, so the names are the same on every run.
+fn write_pockets(dir: &Path, pockets: &[Vec<(usize, Point)>]) -> anyhow::Result<()> {
+    fs::create_dir_all(dir)?;
+
+    for (k, pocket) in pockets.iter().enumerate() {
+        let mut writer = Writer::from_path(dir.join(format!("pocket_{k:03}.csv")))?;
+        for (_, point) in pocket {
+            writer.write_record(&[point.x.to_string(), point.y.to_string()])?;
+        }
+        writer.flush()?;
+    }
+
+    Ok(())
+}
+
+/// Builds a machine-parseable summary line of the hull, as space-separated `key=value` pairs
+fn summary(points: &[Point], hull: &[(usize, Point)]) -> String {
+    format!(
+        "points={} vertices={} area={} perimeter={}",
+        points.len(),
+        hull.len(),
+        area(hull),
+        perimeter(hull)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn summary_format() {
+        let points = [
+            Point::new(0., 0.),
+            Point::new(4., 0.),
+            Point::new(4., 3.),
+            Point::new(0., 3.),
+            Point::new(2., 1.),
+        ];
+        let hull = concave_hull(&points, f32::INFINITY);
+
+        assert_eq!(
+            summary(&points, &hull),
+            "points=5 vertices=4 area=12 perimeter=14"
+        );
+    }
+
+    #[test]
+    fn multiple_inputs() {
+        let dir = std::env::temp_dir().join(format!("concave_hull_inputs_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let a = dir.join("a.csv");
+        let b = dir.join("b.csv");
+        fs::write(&a, "0,0\n4,0\n").unwrap();
+        fs::write(&b, "4,3\n0,3\n2,1\n").unwrap();
+
+        let (points, counts) = read_inputs(&[a, b], &CsvOptions::new()).unwrap();
+        assert_eq!(counts, [2, 3]);
+        assert_eq!(points[1], Point::new(4., 0.));
+        assert_eq!(points[2], Point::new(4., 3.));
+
+        // Indices count through both files, so the inner point from the second file is never on the hull
+        let hull = concave_hull(&points, f32::INFINITY);
+        let mut ids: Vec<usize> = hull.iter().map(|(id, _)| *id).collect();
+        ids.sort_unstable();
+        assert_eq!(ids, [0, 1, 2, 3]);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn npy_file() {
+        let points = [
+            Point::new(0., 0.),
+            Point::new(4., 0.),
+            Point::new(4., 3.),
+            Point::new(0., 3.),
+            Point::new(2., 1.),
+        ];
+        let hull = concave_hull(&points, f32::INFINITY);
+
+        let path = std::env::temp_dir().join(format!("concave_hull_{}.npy", std::process::id()));
+        write_npy(&path, &hull).unwrap();
+        let array: Array2<f32> = ndarray_npy::read_npy(&path).unwrap();
+
+        assert_eq!(array.shape(), [4, 2]);
+        for (row, (_, point)) in array.rows().into_iter().zip(&hull) {
+            assert_eq!(row.to_vec(), [point.x, point.y]);
+        }
+
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn pocket_files() {
+        // A 12x10 rectangle, with two notches
+        let points = [
+            Point::new(0., 0.),
+            Point::new(12., 0.),
+            Point::new(12., 10.),
+            Point::new(0., 10.),
+            Point::new(6., 4.),
+            Point::new(6., 0.5),
+        ];
+        let pockets = hull_pockets(&points, &concave_hull(&points, 9.));
+        assert_eq!(pockets.len(), 2);
+
+        let dir = std::env::temp_dir().join(format!("concave_hull_pockets_{}", std::process::id()));
+        write_pockets(&dir, &pockets).unwrap();
+
+        for (k, pocket) in pockets.iter().enumerate() {
+            let contents = fs::read_to_string(dir.join(format!("pocket_{k:03}.csv"))).unwrap();
+            assert_eq!(contents.lines().count(), pocket.len());
+        }
+        assert!(!dir.join("pocket_002.csv").exists());
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
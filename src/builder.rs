@@ -0,0 +1,521 @@
+use std::{fmt, sync::Arc};
+
+use nalgebra::{Point2 as Point, Scalar};
+use num_traits::One;
+
+use crate::wrap::Wrap;
+
+/// A user-supplied function giving the priority of an edge from its two endpoints
+pub(crate) type EdgePriority<T> = Arc<dyn Fn(&Point<T>, &Point<T>) -> T + Send + Sync>;
+
+/// A user-supplied function giving the distance between two points
+pub(crate) type Distance<T> = Arc<dyn Fn(&Point<T>, &Point<T>) -> T + Send + Sync>;
+
+/// How the length of each edge is checked against the concavity parameter, to decide whether to split it
+///
+/// Reference implementations of gift opening disagree here:
+/// the JS implementation compares every edge against the same limit, while the original paper scales the limit by the local point density.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LengthCheck<T: Scalar> {
+    /// Every edge longer than the concavity parameter is split, matching the JS implementation
+    Flat,
+    /// The concavity parameter is scaled by the ratio of the local spacing between points to the average spacing, raised to the power `k`
+    ///
+    /// The local spacing is measured around each edge's endpoints, with a `DensityField`.
+    /// A `k` of one matches the original paper: an edge in a region half as dense as average (where points are √2 times further apart)
+    /// can be √2 times longer before it's split.
+    /// A `k` of zero is the same as [`Self::Flat`], and larger values scale the limit more strongly.
+    DensityScaled(T),
+}
+
+/// Configurable concave hull computation
+///
+/// Construct one with [`Self::new`], chain any options, then compute a hull with `compute`.
+/// Options that are not set keep their defaults, which reproduce the plain `concave_hull` function.
+#[derive(Clone)]
+pub struct ConcaveHullBuilder<T: Scalar> {
+    pub(crate) concavity: T,
+    pub(crate) min_notch_area: Option<T>,
+    pub(crate) min_feature: Option<T>,
+    pub(crate) next_best_on_boundary: bool,
+    pub(crate) geometric_epsilon: Option<T>,
+    pub(crate) fix_longest_convex: usize,
+    pub(crate) close_degenerate: bool,
+    pub(crate) length_check: LengthCheck<T>,
+    pub(crate) cache_density: bool,
+    pub(crate) cheap_convex_checks: bool,
+    pub(crate) angle_surrogate: bool,
+    pub(crate) parallel_checks: bool,
+    pub(crate) eager_finalize: bool,
+    pub(crate) small_cloud_queue: bool,
+    pub(crate) stable_ordering: bool,
+    pub(crate) priority: Option<EdgePriority<T>>,
+    pub(crate) distance: Option<Distance<T>>,
+    pub(crate) edge_point_tolerance: Option<T>,
+    pub(crate) axis_bias: Option<T>,
+    /// Closed rings of points which edges of the hull must not cross
+    pub(crate) obstacles: Vec<Vec<Point<T>>>,
+    /// Per-point weights for candidate selection, indexed the same way as the point cloud
+    pub(crate) weights: Option<Vec<T>>,
+    pub(crate) capacity_hint: usize,
+    pub(crate) snap: Option<T>,
+    pub(crate) max_aspect_ratio: Option<T>,
+    pub(crate) max_edge_ratio: Option<T>,
+    pub(crate) wrap: Option<Wrap<T>>,
+}
+
+impl<T: Scalar> ConcaveHullBuilder<T> {
+    /// Creates a new builder with the provided concavity parameter, and all other options set to their defaults
+    ///
+    /// See the crate-level docs for guidance on picking the concavity parameter.
+    pub fn new(concavity: T) -> Self {
+        Self {
+            concavity,
+            min_notch_area: None,
+            min_feature: None,
+            next_best_on_boundary: false,
+            geometric_epsilon: None,
+            fix_longest_convex: 0,
+            close_degenerate: false,
+            length_check: LengthCheck::Flat,
+            cache_density: true,
+            cheap_convex_checks: true,
+            angle_surrogate: true,
+            parallel_checks: true,
+            eager_finalize: true,
+            small_cloud_queue: true,
+            stable_ordering: false,
+            priority: None,
+            distance: None,
+            edge_point_tolerance: None,
+            axis_bias: None,
+            obstacles: Vec::new(),
+            weights: None,
+            capacity_hint: 0,
+            snap: None,
+            max_aspect_ratio: None,
+            max_edge_ratio: None,
+            wrap: None,
+        }
+    }
+
+    /// Fills in any notches in the hull with an area smaller than `area`
+    ///
+    /// After the concave hull is computed, it is split into pockets: the regions between the concave hull
+    /// and the straight convex hull edge spanning them.
+    /// Pockets smaller than `area` are replaced with that straight edge, while larger pockets are kept as-is.
+    /// This smooths out small features, while preserving the large concave features of the shape.
+    ///
+    /// Defaults to `None`, which keeps every notch.
+    pub fn min_notch_area(mut self, area: T) -> Self {
+        self.min_notch_area = Some(area);
+        self
+    }
+
+    /// Stops the hull from resolving features smaller than `size`
+    ///
+    /// Each split digs a new point into the hull, making a notch as deep as that point's distance from the split edge.
+    /// When set, a split is only made if the new point is more than `size` away from the line through the edge,
+    /// and the edge is kept as-is otherwise.
+    /// This ignores small wiggles in the boundary, no matter how low the concavity is.
+    ///
+    /// The two options limit the hull in different ways: the concavity decides which edges are long enough to split,
+    /// while this decides whether the best point to split with makes a deep enough notch.
+    /// Since a rejected edge is never split, points behind a shallow point are not reached through that edge,
+    /// even if they would make a deeper notch.
+    /// Unlike [`Self::min_notch_area`], this applies during gift opening, so it limits the depth of every single split,
+    /// rather than the total area of each pocket.
+    ///
+    /// Defaults to `None`, which splits edges regardless of how shallow the resulting notch is.
+    pub fn min_feature(mut self, size: T) -> Self {
+        self.min_feature = Some(size);
+        self
+    }
+
+    /// Whether to fall back to the best point which isn't on the hull yet, when the best point to split an edge with already is
+    ///
+    /// Each split adds a new point to the hull, so the best point to split an edge with must not be on the hull already.
+    /// By default, if the best point is already on the hull, the edge is kept as-is, even if it's long.
+    /// When enabled, points already on the hull are skipped while searching, so the edge is split with the best point that isn't,
+    /// which lets the hull dig in further (as long as the new edges don't cross any existing ones).
+    /// The fallback point can be a much worse fit for the edge, so this can make deep, narrow cuts into the cloud.
+    ///
+    /// This costs a hash set lookup for every candidate point, on every split,
+    /// on top of the extra splits from edges which would otherwise have been kept.
+    ///
+    /// Defaults to `false`, which keeps edges whose best point is already on the hull.
+    pub fn next_best_on_boundary(mut self, next_best: bool) -> Self {
+        self.next_best_on_boundary = next_best;
+        self
+    }
+
+    /// How far from straight three points can be, and still count as being on one line
+    ///
+    /// Three points are collinear when the sine of the angle that the path through them turns by is at most `epsilon`,
+    /// which covers paths that run straight on, and paths that double straight back.
+    /// This is a ratio, so it doesn't depend on the scale of the points, but it may need raising for points with noisy coordinates.
+    /// It's used everywhere that gift opening decides whether points are collinear:
+    /// a point cloud is degenerate if every corner of its convex hull is collinear,
+    /// in which case its hull is just the two ends of the line (see [`Self::close_degenerate`]),
+    /// and degenerate triangles are only reoriented if they aren't collinear.
+    /// An `epsilon` of zero only treats exactly collinear points as collinear.
+    ///
+    /// Defaults to the machine epsilon of the precision, which is about `1.2e-7` for `f32` and `2.2e-16` for `f64`.
+    pub fn geometric_epsilon(mut self, epsilon: T) -> Self {
+        self.geometric_epsilon = Some(epsilon);
+        self
+    }
+
+    /// How many of the longest convex hull edges to keep straight, by never splitting them
+    ///
+    /// Gift opening starts from the edges of the convex hull, and the `count` longest of them (by Euclidean length)
+    /// are kept in the hull as-is, so that the concavity only carves into the rest of the boundary.
+    /// This is useful when the longest spans of the cloud are meant to be straight, such as the outer edges of a map.
+    /// A `count` of at least the number of convex hull edges keeps the whole convex hull, regardless of the concavity.
+    /// Ties in length are broken by the edges' coordinates, so the same edges are always fixed.
+    ///
+    /// Defaults to `0`, which lets every edge be split.
+    pub fn fix_longest_convex(mut self, count: usize) -> Self {
+        self.fix_longest_convex = count;
+        self
+    }
+
+    /// Whether to return degenerate hulls (with one or two points) as closed rings
+    ///
+    /// When enabled, the first point is repeated at the end of the hull,
+    /// so a single point `a` becomes `[a, a]`, and two points `a` and `b` become `[a, b, a]`.
+    /// This is convenient for consumers which expect every hull to be a closed polygon.
+    /// Empty hulls and hulls with three or more points are unaffected.
+    ///
+    /// Defaults to `false`, which returns degenerate hulls as-is.
+    pub fn close_degenerate(mut self, close: bool) -> Self {
+        self.close_degenerate = close;
+        self
+    }
+
+    /// Whether to scale the concavity parameter by the local density of the point cloud
+    ///
+    /// The original gift opening paper scales the edge length check by the local point density,
+    /// so that sparse regions of the cloud aren't dug into as aggressively as dense ones.
+    /// When enabled, the concavity limit for each edge is multiplied by the ratio of the local spacing between points
+    /// (measured around the edge's endpoints) to the average spacing across the whole cloud.
+    /// This makes a single concavity value behave more consistently on clouds with uneven density.
+    ///
+    /// The local density is looked up in a `DensityField`, which is built once per hull.
+    ///
+    /// This is shorthand for [`Self::length_check`], with [`LengthCheck::DensityScaled`] (with a `k` of one) when enabled,
+    /// and [`LengthCheck::Flat`] when not.
+    ///
+    /// Defaults to `false`, which uses the same concavity everywhere (matching the JS implementation).
+    pub fn adaptive_concavity(mut self, adaptive: bool) -> Self
+    where
+        T: One,
+    {
+        self.length_check = if adaptive {
+            LengthCheck::DensityScaled(T::one())
+        } else {
+            LengthCheck::Flat
+        };
+        self
+    }
+
+    /// How the length of each edge is checked against the concavity parameter, to decide whether to split it
+    ///
+    /// Use [`LengthCheck::Flat`] to reproduce the JS implementation of gift opening,
+    /// and [`LengthCheck::DensityScaled`] with a `k` of one to reproduce the original paper.
+    /// See [`Self::adaptive_concavity`] for more on density scaling.
+    ///
+    /// Defaults to [`LengthCheck::Flat`], which uses the same concavity everywhere (matching the JS implementation).
+    pub fn length_check(mut self, check: LengthCheck<T>) -> Self {
+        self.length_check = check;
+        self
+    }
+
+    /// Sets the order in which edges are considered for splitting
+    ///
+    /// Gift opening keeps a queue of edges to split, and always tries to split the edge with the highest priority next.
+    /// `priority` is called with the two endpoints of each edge, and returns its priority.
+    /// Edges with larger priorities are split first, and ties are broken arbitrarily (or by coordinates, with [`Self::stable_ordering`]).
+    /// Since edges are only ever split if they're longer than the concavity limit,
+    /// this changes the shape of the hull, but not how tight it is.
+    ///
+    /// Defaults to the squared length of the edge (as measured by [`Self::distance`]), so that the longest edges are split first.
+    pub fn edge_priority(
+        mut self,
+        priority: impl Fn(&Point<T>, &Point<T>) -> T + Send + Sync + 'static,
+    ) -> Self {
+        self.priority = Some(Arc::new(priority));
+        self
+    }
+
+    /// Breaks ties between equally long edges, and equally good candidate points, by their coordinates
+    ///
+    /// Without this, ties between edges are broken by the internal order of the edge heap, and ties between candidates go to the first point.
+    /// Both depend on the order of the point cloud, so symmetric clouds (like regular grids) can hull differently when shuffled.
+    /// With this, the hull only depends on the positions of the points:
+    /// shuffling the point cloud gives the same hull, starting from the same point, with the indices shuffled to match.
+    /// The only exception is repeat points, which are told apart by their indices.
+    ///
+    /// Since this gives edges a total order, it also lets short edges be finalized without going through the heap,
+    /// and tiny point clouds keep their edges in a plain list, both of which make hulls a little faster.
+    ///
+    /// Defaults to `false`, which breaks ties in the same way as the plain `concave_hull` function always has.
+    pub fn stable_ordering(mut self, stable: bool) -> Self {
+        self.stable_ordering = stable;
+        self
+    }
+
+    /// Sets how the length of an edge is measured, for comparing it against the concavity limit
+    ///
+    /// `distance` is called with the two endpoints of each edge, and returns its length, in the same units as the concavity.
+    /// This is useful when coordinates aren't in a flat space, such as latitudes and longitudes:
+    /// passing a great-circle distance (such as the haversine formula) lets the concavity be given in meters.
+    /// It also sets the default [`Self::edge_priority`], so that the longest edges by this measure are split first.
+    ///
+    /// Only edge lengths are affected: candidate points and intersection checks still use the planar coordinates,
+    /// so this works best when the mapping from coordinates to distances is smooth over the size of the point cloud.
+    /// The distance should be non-negative and symmetric.
+    ///
+    /// Defaults to the Euclidean distance.
+    pub fn distance(
+        mut self,
+        distance: impl Fn(&Point<T>, &Point<T>) -> T + Send + Sync + 'static,
+    ) -> Self {
+        self.distance = Some(Arc::new(distance));
+        self
+    }
+
+    /// Adds any point which lies on an edge of the hull to the hull, so that points are only ever on the hull at vertices
+    ///
+    /// Gift opening only adds points to the hull when it splits an edge, so a point can end up sitting exactly on
+    /// an edge which was never split (for example, a point in the middle of a straight side of the point cloud).
+    /// When enabled, every point which is within `tolerance` of an edge, and is not already a hull vertex,
+    /// is inserted into that edge as a vertex, in order along the edge.
+    /// Every other point is then strictly inside of the hull.
+    ///
+    /// This happens after filling notches, and does not affect `is_convex` in the hull stats.
+    /// Hulls of three or fewer points are unaffected.
+    ///
+    /// Defaults to `None`, which leaves points on edges as-is.
+    pub fn include_edge_points(mut self, tolerance: T) -> Self {
+        self.edge_point_tolerance = Some(tolerance);
+        self
+    }
+
+    /// Nudges the hull towards horizontal and vertical edges, such as for floorplans and other architectural data
+    ///
+    /// When an edge is split, it is split with the point which minimizes the larger of the angles between the edge
+    /// and the two new edges.
+    /// With a bias, each candidate's angle is first multiplied by `1 + bias * d`, where `d` is the diagonality
+    /// of the more diagonal of the two new edges: `|sin 2θ|` for an edge in direction `θ`,
+    /// which is zero for horizontal and vertical edges, and one for edges at 45 degrees.
+    /// This favours points which make axis-aligned edges, but a well-placed diagonal can still beat a poorly placed alternative,
+    /// so it nudges the shape rather than constraining it.
+    /// Small biases (around one) are usually enough, and large biases can keep the hull from digging in as far.
+    ///
+    /// The bias only changes which point an edge is split with, not which edges are split,
+    /// so it has no effect on the convex hull.
+    ///
+    /// Defaults to `None`, which treats every direction the same.
+    pub fn axis_bias(mut self, bias: T) -> Self {
+        self.axis_bias = Some(bias);
+        self
+    }
+
+    /// Keeps the edges of the hull from crossing any of the provided obstacles, such as lakes on a map
+    ///
+    /// Each obstacle is a polygon, given as a ring of points (the last point connects back to the first).
+    /// When an edge is split, any candidate point whose two new edges would cross an obstacle's boundary is skipped,
+    /// so the edge is split with the best point that routes around the obstacles instead.
+    /// If there is no such point, the edge is kept as-is.
+    ///
+    /// Only the new edges are checked, and they can never cross an obstacle, so an obstacle stays on the same side of the hull
+    /// as the convex hull starts it on.
+    /// In particular, an obstacle entirely inside of the convex hull stays inside of the hull,
+    /// while an obstacle which sticks out of the convex hull is dug around (as long as the edges over it are split).
+    /// Checking every candidate against every obstacle edge is slow, so keep obstacles simple.
+    ///
+    /// Defaults to no obstacles.
+    pub fn obstacles(mut self, obstacles: &[&[Point<T>]]) -> Self {
+        self.obstacles = obstacles.iter().map(|ring| ring.to_vec()).collect();
+        self
+    }
+
+    /// Rounds every point to the nearest multiple of `grid` before computing the hull
+    ///
+    /// This is useful for noisy coordinates, since small amounts of noise (less than half of `grid`)
+    /// usually won't change which grid point a point rounds to, which stabilizes the hull.
+    /// If several points round to the same place, only the first one (in the order of the point cloud) is kept.
+    ///
+    /// The hull is computed from the rounded points, but is returned with the original indices and
+    /// the original (unrounded) coordinates of its points.
+    ///
+    /// Defaults to `None`, which uses the points as-is.
+    pub fn snap_to_grid(mut self, grid: T) -> Self {
+        self.snap = Some(grid);
+        self
+    }
+
+    /// Treats the coordinates as periodic, for point clouds on a cylinder or torus (such as longitudes, which wrap at ±180°)
+    ///
+    /// Without this, a cloud straddling the seam (say, with longitudes on both sides of 180°) is split in two,
+    /// and its hull stretches all the way around the other side to join them up.
+    /// When set, each wrapped axis is cut open at the widest empty gap around it,
+    /// and points are moved by whole periods so that the cloud is contiguous.
+    /// Edges then take the shorter way around, across the seam if needed.
+    ///
+    /// The hull is returned with the original indices, but with the moved coordinates,
+    /// so that its edges can be drawn as straight lines.
+    /// The first point in the cloud always keeps its coordinates, and every other point is within a whole number of periods of its original.
+    /// Point clouds which cover most of a wrapped axis don't have a sensible seam to cut at, so this works best on local clouds.
+    ///
+    /// Defaults to `None`, which treats coordinates as flat.
+    pub fn wrap(mut self, wrap: Wrap<T>) -> Self {
+        self.wrap = Some(wrap);
+        self
+    }
+
+    /// Pre-allocates space for a hull with `capacity` points
+    ///
+    /// By default, space is only allocated for the points of the convex hull,
+    /// so large concave hulls reallocate several times as they grow.
+    /// If you know roughly how many points the hull will have (say, from a previous run on similar data),
+    /// then passing that here avoids the reallocations.
+    /// This is only a hint, and never changes the resulting hull.
+    ///
+    /// Defaults to `0`, which only allocates space for the convex hull.
+    pub fn capacity_hint(mut self, capacity: usize) -> Self {
+        self.capacity_hint = capacity;
+        self
+    }
+
+    /// Leaves outlying points out of the hull, instead of letting the hull spike out to reach them
+    ///
+    /// A lone point far away from the rest of the cloud forces the hull to reach out to it with two long edges,
+    /// which no amount of splitting can shorten.
+    /// When set, any hull vertex whose edges are both longer than `ratio` times the median edge length of the hull
+    /// is treated as an outlier: it is removed from the point cloud, and the hull is recomputed without it.
+    /// This repeats until there are no outliers left.
+    ///
+    /// Outliers are left outside of the hull, so the hull no longer contains every point.
+    /// The returned indices still refer to the original point cloud.
+    ///
+    /// Defaults to `None`, which never excludes any points.
+    pub fn max_edge_ratio(mut self, ratio: T) -> Self {
+        self.max_edge_ratio = Some(ratio);
+        self
+    }
+
+    /// Rejects point clouds whose bounding box is more stretched out than `ratio` in the checked functions
+    ///
+    /// The aspect ratio is the length of the longer side of the bounding box, divided by the length of the shorter side.
+    /// An extreme aspect ratio usually hints at a units or parsing mistake, rather than a genuinely long and thin cloud.
+    /// Point clouds with a zero-width or zero-height bounding box are always rejected by the checked functions.
+    ///
+    /// This only affects `try_compute`, and is ignored by `compute`.
+    ///
+    /// Defaults to `None`, which allows any aspect ratio.
+    pub fn max_aspect_ratio(mut self, ratio: T) -> Self {
+        self.max_aspect_ratio = Some(ratio);
+        self
+    }
+
+    /// Whether adaptive concavity should use a precomputed density field, or rescan the point cloud for every sample
+    ///
+    /// This only exists for benchmarking, and should always be left enabled.
+    #[cfg(feature = "benches")]
+    pub fn cache_density(mut self, cache: bool) -> Self {
+        self.cache_density = cache;
+        self
+    }
+
+    /// Whether splits should skip the full intersection check against edges of the convex hull which haven't been split yet
+    ///
+    /// This only exists for benchmarking, and should always be left enabled.
+    #[cfg(feature = "benches")]
+    pub fn cheap_convex_checks(mut self, cheap: bool) -> Self {
+        self.cheap_convex_checks = cheap;
+        self
+    }
+
+    /// Whether candidate points should be compared by the cosines of their angles, rather than the angles themselves
+    ///
+    /// This only exists for benchmarking, and should always be left enabled.
+    #[cfg(feature = "benches")]
+    pub fn angle_surrogate(mut self, surrogate: bool) -> Self {
+        self.angle_surrogate = surrogate;
+        self
+    }
+
+    /// Whether splits in large hulls should check for intersections with the existing edges in parallel
+    ///
+    /// This has no effect without the `rayon` feature.
+    ///
+    /// This only exists for benchmarking, and should always be left enabled.
+    #[cfg(feature = "benches")]
+    pub fn parallel_checks(mut self, parallel: bool) -> Self {
+        self.parallel_checks = parallel;
+        self
+    }
+
+    /// Whether edges created by splits which are already too short to split should skip the heap, and be finalized straight away
+    ///
+    /// This only applies with the default edge priority, and without adaptive concavity,
+    /// since otherwise short edges aren't guaranteed to come off of the heap after every long edge.
+    /// It also only applies with [`Self::stable_ordering`], since otherwise the heap's order between equally short edges can't be reproduced.
+    ///
+    /// This only exists for benchmarking, and should always be left enabled.
+    #[cfg(feature = "benches")]
+    pub fn eager_finalize(mut self, eager: bool) -> Self {
+        self.eager_finalize = eager;
+        self
+    }
+
+    /// Whether tiny point clouds should keep their edges in a plain list, rather than a heap, and their hull points in a plain list, rather than a hash set
+    ///
+    /// With only a few edges, scanning the whole list for the longest one is cheaper than keeping a heap in order,
+    /// and scanning the hull points is cheaper than hashing them.
+    /// Either way, edges are split in the same order, so the hull is identical.
+    /// This only applies with [`Self::stable_ordering`], since otherwise the heap's order between equally long edges can't be reproduced.
+    ///
+    /// This only exists for benchmarking, and should always be left enabled.
+    #[cfg(feature = "benches")]
+    pub fn small_cloud_queue(mut self, linear: bool) -> Self {
+        self.small_cloud_queue = linear;
+        self
+    }
+}
+
+impl<T: Scalar> fmt::Debug for ConcaveHullBuilder<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ConcaveHullBuilder")
+            .field("concavity", &self.concavity)
+            .field("min_notch_area", &self.min_notch_area)
+            .field("min_feature", &self.min_feature)
+            .field("next_best_on_boundary", &self.next_best_on_boundary)
+            .field("geometric_epsilon", &self.geometric_epsilon)
+            .field("fix_longest_convex", &self.fix_longest_convex)
+            .field("close_degenerate", &self.close_degenerate)
+            .field("length_check", &self.length_check)
+            .field("cache_density", &self.cache_density)
+            .field("cheap_convex_checks", &self.cheap_convex_checks)
+            .field("angle_surrogate", &self.angle_surrogate)
+            .field("parallel_checks", &self.parallel_checks)
+            .field("eager_finalize", &self.eager_finalize)
+            .field("small_cloud_queue", &self.small_cloud_queue)
+            .field("stable_ordering", &self.stable_ordering)
+            // Closures can't be printed, so just show whether there is one
+            .field("priority", &self.priority.as_ref().map(|_| "custom"))
+            .field("distance", &self.distance.as_ref().map(|_| "custom"))
+            .field("edge_point_tolerance", &self.edge_point_tolerance)
+            .field("axis_bias", &self.axis_bias)
+            .field("obstacles", &self.obstacles)
+            .field("weights", &self.weights)
+            .field("capacity_hint", &self.capacity_hint)
+            .field("snap", &self.snap)
+            .field("max_aspect_ratio", &self.max_aspect_ratio)
+            .field("max_edge_ratio", &self.max_edge_ratio)
+            .field("wrap", &self.wrap)
+            .finish()
+    }
+}
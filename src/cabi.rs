@@ -0,0 +1,167 @@
+//! C ABI bindings, for calling into this crate from C (or anything that can call C, like Python's `ctypes`)
+//!
+//! To get a library that C can link against, build this crate as a `cdylib` (or `staticlib`) with the `cabi` feature:
+//! ```text
+//! cargo rustc --release --features cabi --crate-type cdylib
+//! ```
+//!
+//! Every function returns one of the status codes in this module, and never unwinds across the boundary.
+//! Buffers returned by this module are owned by Rust, and must be freed with the matching free function.
+
+use std::{panic, ptr, slice};
+
+use crate::f32::{Point, concave_hull};
+
+/// The call succeeded
+pub const CONCAVE_HULL_OK: i32 = 0;
+/// A required pointer was null
+pub const CONCAVE_HULL_NULL_POINTER: i32 = -1;
+/// The inputs were invalid, such as a point count which overflows the address space, or a NaN concavity
+pub const CONCAVE_HULL_INVALID_INPUT: i32 = -2;
+/// The computation panicked, which is always a bug in this crate
+pub const CONCAVE_HULL_PANICKED: i32 = -3;
+
+/// Computes the concave hull of a point cloud, returning the indices of the hull points in winding order
+///
+/// `points_ptr` points to `len` points, with their coordinates interleaved as `[x0, y0, x1, y1, ...]`
+/// (so `2 * len` floats in total).
+/// It may be null if `len` is zero.
+/// See `concave_hull` for details on the concavity parameter and the hull.
+///
+/// On success, a newly allocated buffer of indices is written to `out_indices_ptr`, and its length to `out_len_ptr`.
+/// The buffer must be freed with [`concave_hull_free_indices`], along with that length.
+/// On failure, the outputs are left untouched, and one of the error codes in this module is returned.
+///
+/// # Safety
+///
+/// Unless `len` is zero, `points_ptr` must be valid for reads of `2 * len` `f32`s.
+/// `out_indices_ptr` and `out_len_ptr` must be valid for writes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn concave_hull_f32(
+    points_ptr: *const f32,
+    len: usize,
+    concavity: f32,
+    out_indices_ptr: *mut *mut usize,
+    out_len_ptr: *mut usize,
+) -> i32 {
+    if out_indices_ptr.is_null() || out_len_ptr.is_null() || (points_ptr.is_null() && len != 0) {
+        return CONCAVE_HULL_NULL_POINTER;
+    }
+    let Some(coordinate_count) = len.checked_mul(2) else {
+        return CONCAVE_HULL_INVALID_INPUT;
+    };
+    if concavity.is_nan() {
+        return CONCAVE_HULL_INVALID_INPUT;
+    }
+
+    let coordinates: &[f32] = if len == 0 {
+        &[]
+    } else {
+        // SAFETY: The caller guarantees that `points_ptr` is valid for `2 * len` reads, and we checked that it isn't null
+        unsafe { slice::from_raw_parts(points_ptr, coordinate_count) }
+    };
+    let points: Vec<Point> = coordinates
+        .chunks_exact(2)
+        .map(|xy| Point::new(xy[0], xy[1]))
+        .collect();
+
+    let Ok(hull) = panic::catch_unwind(|| concave_hull(&points, concavity)) else {
+        return CONCAVE_HULL_PANICKED;
+    };
+
+    let indices: Box<[usize]> = hull.into_iter().map(|(id, _)| id).collect();
+    let out_len = indices.len();
+    let out_indices = Box::into_raw(indices).cast::<usize>();
+
+    // SAFETY: The caller guarantees that both outputs are valid for writes, and we checked that they aren't null
+    unsafe {
+        out_indices_ptr.write(out_indices);
+        out_len_ptr.write(out_len);
+    }
+
+    CONCAVE_HULL_OK
+}
+
+/// Frees a buffer of indices returned by [`concave_hull_f32`]
+///
+/// Passing a null pointer does nothing.
+///
+/// # Safety
+///
+/// `indices_ptr` must either be null, or be a buffer returned by [`concave_hull_f32`] which hasn't been freed yet,
+/// and `len` must be the length that was returned alongside it.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn concave_hull_free_indices(indices_ptr: *mut usize, len: usize) {
+    if indices_ptr.is_null() {
+        return;
+    }
+
+    // SAFETY: The caller guarantees that this is a live buffer from `concave_hull_f32`, which came from a boxed slice of this length
+    drop(unsafe { Box::from_raw(ptr::slice_from_raw_parts_mut(indices_ptr, len)) });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Calls [`concave_hull_f32`] on `points`, and copies the returned indices out
+    fn hull_through_c(points: &[Point], concavity: f32) -> Result<Vec<usize>, i32> {
+        let coordinates: Vec<f32> = points.iter().flat_map(|p| [p.x, p.y]).collect();
+        let mut indices_ptr = ptr::null_mut();
+        let mut len = 0;
+
+        let status = unsafe {
+            concave_hull_f32(
+                coordinates.as_ptr(),
+                points.len(),
+                concavity,
+                &mut indices_ptr,
+                &mut len,
+            )
+        };
+        if status != CONCAVE_HULL_OK {
+            return Err(status);
+        }
+
+        let indices = unsafe { slice::from_raw_parts(indices_ptr, len) }.to_vec();
+        unsafe { concave_hull_free_indices(indices_ptr, len) };
+        Ok(indices)
+    }
+
+    #[test]
+    fn matches_native() {
+        let points = [
+            Point::new(0., 0.),
+            Point::new(4., 0.),
+            Point::new(2., 1.),
+            Point::new(4., 3.),
+            Point::new(0., 3.),
+            Point::new(1., 1.5),
+        ];
+
+        for concavity in [0., 1., f32::INFINITY] {
+            let expected: Vec<usize> = concave_hull(&points, concavity)
+                .into_iter()
+                .map(|(id, _)| id)
+                .collect();
+            assert_eq!(hull_through_c(&points, concavity), Ok(expected));
+        }
+
+        assert_eq!(hull_through_c(&[], 1.), Ok(Vec::new()));
+    }
+
+    #[test]
+    fn error_codes() {
+        assert_eq!(
+            hull_through_c(&[Point::new(0., 0.)], f32::NAN),
+            Err(CONCAVE_HULL_INVALID_INPUT)
+        );
+
+        let status =
+            unsafe { concave_hull_f32(ptr::null(), 3, 1., ptr::null_mut(), ptr::null_mut()) };
+        assert_eq!(status, CONCAVE_HULL_NULL_POINTER);
+
+        // Freeing nothing is fine
+        unsafe { concave_hull_free_indices(ptr::null_mut(), 0) };
+    }
+}
@@ -0,0 +1,92 @@
+use std::{cmp::Ordering, error::Error, fmt};
+
+use nalgebra::Point2 as Point;
+
+use crate::{
+    HullScalar,
+    metrics::signed_area,
+    validate::{contains, on_boundary},
+};
+
+/// The reasons a point cloud can be rejected by the checked concave hull functions
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConcaveHullError {
+    /// The bounding box of the point cloud has zero width or zero height,
+    /// or is more stretched out than the configured maximum aspect ratio
+    ///
+    /// This usually means the points are collinear along an axis, or that the coordinates were read in wrong
+    /// (for example, with the x and y columns swapped, or in mismatched units).
+    DegenerateExtent,
+    /// The point which was supposed to be inside of the hull is outside of it, or on its boundary
+    HintOutside,
+}
+
+impl fmt::Display for ConcaveHullError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::DegenerateExtent => write!(f, "point cloud has a degenerate bounding box"),
+            Self::HintOutside => write!(f, "inside hint is not inside of the hull"),
+        }
+    }
+}
+
+impl Error for ConcaveHullError {}
+
+/// Checks that the bounding box of `points` has nonzero extent along both axes,
+/// and that the ratio of its longer side to its shorter side is at most `max_aspect_ratio` (if set)
+///
+/// Empty point clouds have no bounding box, so they always pass.
+pub(crate) fn check_extent<T: HullScalar>(
+    points: &[Point<T>],
+    max_aspect_ratio: Option<T>,
+) -> Result<(), ConcaveHullError> {
+    let Some(first) = points.first() else {
+        return Ok(());
+    };
+
+    let (mut mins, mut maxs) = (*first, *first);
+    for p in points {
+        mins = mins.inf(p);
+        maxs = maxs.sup(p);
+    }
+    let extents = maxs - mins;
+
+    let (short, long) = (extents.x.min(extents.y), extents.x.max(extents.y));
+    // Compared this way so that NaN extents fail too
+    if short.partial_cmp(&T::zero()) != Some(Ordering::Greater) {
+        return Err(ConcaveHullError::DegenerateExtent);
+    }
+    if max_aspect_ratio.is_some_and(|ratio| long / short > ratio) {
+        return Err(ConcaveHullError::DegenerateExtent);
+    }
+
+    Ok(())
+}
+
+/// Winds a hull so that `inside` is on the left of its edges, checking that it's strictly inside of the hull
+///
+/// For a simple polygon, the inside is on the left of every edge exactly when it winds counter-clockwise.
+/// Hulls with fewer than three points have no inside, so they are always rejected.
+pub(crate) fn orient_around<T: HullScalar>(
+    mut hull: Vec<(usize, Point<T>)>,
+    inside: &Point<T>,
+) -> Result<Vec<(usize, Point<T>)>, ConcaveHullError> {
+    if hull.len() < 3 || !contains(&hull, inside) || on_boundary(&hull, inside) {
+        return Err(ConcaveHullError::HintOutside);
+    }
+
+    if signed_area(hull.iter().map(|(_, p)| *p)) < T::zero() {
+        hull.reverse();
+    }
+
+    Ok(hull)
+}
+
+/// Finds the indices of the points with finite coordinates, in order
+///
+/// Points with a NaN or infinite coordinate have no sensible place on (or in) a hull.
+pub(crate) fn finite_indices<T: HullScalar>(points: &[Point<T>]) -> Vec<usize> {
+    (0..points.len())
+        .filter(|&id| points[id].x.is_finite() && points[id].y.is_finite())
+        .collect()
+}
@@ -0,0 +1,113 @@
+use std::collections::{BinaryHeap, HashMap, HashSet};
+
+use nalgebra::{Point2 as Point, Scalar};
+
+use crate::{HullScalar, edge::Edge};
+
+/// Computes the chi-shape of a point cloud from its Delaunay triangulation
+///
+/// Starting from the full triangulation (whose boundary is the convex hull),
+/// the longest boundary edge is repeatedly removed, along with the triangle behind it,
+/// until every boundary edge is at most `length_threshold` long.
+/// An edge is only removed if the third vertex of its triangle isn't on the boundary yet,
+/// which keeps the boundary a simple polygon that every point is inside of (or on).
+///
+/// `triangles` must be the counter-clockwise triangles of the Delaunay triangulation of `points`.
+/// Returns the boundary as a counter-clockwise ring, starting from an arbitrary point,
+/// or nothing if there are no triangles.
+pub(crate) fn chi_shape<T: HullScalar>(
+    points: &[Point<T>],
+    triangles: &[[usize; 3]],
+    length_threshold: T,
+) -> Vec<(usize, Point<T>)> {
+    // Every directed edge belongs to exactly one triangle, and boundary edges have no reverse
+    let mut third_vertex: HashMap<(usize, usize), usize> = triangles
+        .iter()
+        .flat_map(|&[a, b, c]| [((a, b), c), ((b, c), a), ((c, a), b)])
+        .collect();
+    let is_boundary = |edges: &HashMap<(usize, usize), usize>, (i, j): (usize, usize)| {
+        !edges.contains_key(&(j, i))
+    };
+
+    let mut boundary_points = HashSet::new();
+    let mut boundary_edges = BinaryHeap::new();
+    for &[a, b, c] in triangles {
+        for (i, j) in [(a, b), (b, c), (c, a)] {
+            if is_boundary(&third_vertex, (i, j)) {
+                boundary_points.insert(i);
+                boundary_edges.push(Edge::new(i, j, points));
+            }
+        }
+    }
+
+    // Edges are ordered by their squared length, so compare against the squared threshold
+    let threshold = length_threshold.powi(2);
+    while let Some(edge) = boundary_edges.pop() {
+        if edge.norm_squared() <= threshold {
+            // Every remaining edge is shorter than this one
+            break;
+        }
+
+        let c = third_vertex[&(edge.i, edge.j)];
+        if boundary_points.contains(&c) {
+            // Removing this triangle would pinch the boundary, and the third vertex will never leave the boundary,
+            // so this edge is final
+            continue;
+        }
+
+        // Remove the triangle, exposing its other two edges (from the neighbouring triangles' side)
+        for key in [(edge.i, edge.j), (edge.j, c), (c, edge.i)] {
+            third_vertex.remove(&key);
+        }
+        boundary_points.insert(c);
+        boundary_edges.push(Edge::new(edge.i, c, points));
+        boundary_edges.push(Edge::new(c, edge.j, points));
+    }
+
+    // Walk the boundary, which has exactly one outgoing edge per boundary point
+    let next: HashMap<usize, usize> = third_vertex
+        .keys()
+        .filter(|&&edge| is_boundary(&third_vertex, edge))
+        .copied()
+        .collect();
+    let Some(&start) = next.keys().min() else {
+        return Vec::new();
+    };
+
+    let mut ring = Vec::with_capacity(next.len());
+    let mut current = start;
+    loop {
+        ring.push((current, points[current]));
+        current = next[&current];
+        if current == start {
+            break;
+        }
+    }
+
+    ring
+}
+
+/// A Delaunay triangulation of a point cloud, kept around so that chi-shapes with several thresholds can share it
+///
+/// Triangulating is O(n²), while carving out a chi-shape from an existing triangulation is only O(t log t)
+/// in the number of triangles, so sweeping over many thresholds is much cheaper with a shared triangulation.
+#[derive(Debug, Clone)]
+pub struct DelaunayHuller<'a, T: Scalar> {
+    pub(crate) points: &'a [Point<T>],
+    pub(crate) triangles: Vec<[usize; 3]>,
+}
+
+impl<'a, T: HullScalar> DelaunayHuller<'a, T> {
+    /// Triangulates the point cloud up front
+    pub(crate) fn triangulate(points: &'a [Point<T>]) -> Self {
+        Self {
+            points,
+            triangles: crate::delaunay::triangulate(points),
+        }
+    }
+
+    /// Carves out the chi-shape for a single threshold, as in [`chi_shape`]
+    pub(crate) fn carve(&self, length_threshold: T) -> Vec<(usize, Point<T>)> {
+        chi_shape(self.points, &self.triangles, length_threshold)
+    }
+}
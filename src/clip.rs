@@ -0,0 +1,105 @@
+use nalgebra::Point2 as Point;
+
+use crate::{HullScalar, metrics, triangulate::ear_clip};
+
+/// Clips a closed ring of points against a convex clip ring, using the Sutherland-Hodgman algorithm
+///
+/// The subject ring is clipped against each edge of the clip ring in turn, keeping the part on the inside of that edge.
+/// Where the subject crosses a clip edge, a new vertex is created at the crossing.
+/// The clip ring may wind either way, and the result winds the same way as the subject.
+///
+/// The result is exact for convex clip rings.
+/// Concave clip rings are clipped against every edge's full line, so they can cut away more than just the outside.
+/// If the subject is concave, the result can contain zero-width slivers along the clip edges,
+/// where parts of the subject that were cut apart are still joined together.
+///
+/// If either ring has fewer than three points, or they don't overlap, the result is empty.
+pub(crate) fn clip_ring<T: HullScalar>(subject: &[Point<T>], clip: &[Point<T>]) -> Vec<Point<T>> {
+    if subject.len() < 3 || clip.len() < 3 {
+        return Vec::new();
+    }
+
+    // Inside is to the left of each edge for counter-clockwise clip rings, and to the right for clockwise ones
+    let orientation = metrics::signed_area(clip.iter().copied());
+    let inside = |a: Point<T>, b: Point<T>, p: Point<T>| {
+        let side = metrics::cross(a, b, p);
+        if orientation < T::zero() {
+            side <= T::zero()
+        } else {
+            side >= T::zero()
+        }
+    };
+
+    let mut output = subject.to_vec();
+    for k in 0..clip.len() {
+        let (a, b) = (clip[k], clip[(k + 1) % clip.len()]);
+        if a == b || output.is_empty() {
+            continue;
+        }
+
+        let input = std::mem::take(&mut output);
+        for n in 0..input.len() {
+            let (current, next) = (input[n], input[(n + 1) % input.len()]);
+            match (inside(a, b, current), inside(a, b, next)) {
+                (true, true) => output.push(next),
+                (true, false) => output.push(crossing(a, b, current, next)),
+                (false, true) => {
+                    output.push(crossing(a, b, current, next));
+                    output.push(next);
+                }
+                (false, false) => {}
+            }
+        }
+    }
+
+    if output.len() < 3 {
+        output.clear();
+    }
+    output
+}
+
+/// Computes the area of the overlap between two simple polygons
+///
+/// `b` is split into triangles, and `a` is clipped against each of them with [`clip_ring`].
+/// Triangles are convex, so each clip is exact (apart from zero-width slivers, which have no area),
+/// and the triangles don't overlap, so the clipped areas add up to the whole overlap.
+/// Both rings may wind either way.
+///
+/// This is O(n·m) in the number of points in each ring, plus the cost of triangulating `b`.
+pub(crate) fn overlap_area<T: HullScalar>(a: &[Point<T>], b: &[Point<T>]) -> T {
+    ear_clip(b)
+        .into_iter()
+        .map(|[i, j, k]| {
+            let clipped = clip_ring(a, &[b[i], b[j], b[k]]);
+            metrics::signed_area(clipped).abs()
+        })
+        .fold(T::zero(), |total, area| total + area)
+}
+
+/// Computes the intersection over union of two simple polygons
+///
+/// This is the area of their overlap divided by the area of their union, which is one for identical polygons,
+/// and zero for polygons which don't overlap at all.
+/// Also returns the area of their symmetric difference (the area covered by exactly one of them).
+/// If neither polygon has any area, the intersection over union is zero.
+pub(crate) fn intersection_over_union<T: HullScalar>(a: &[Point<T>], b: &[Point<T>]) -> (T, T) {
+    let overlap = overlap_area(a, b);
+    let union = metrics::signed_area(a.iter().copied()).abs()
+        + metrics::signed_area(b.iter().copied()).abs()
+        - overlap;
+
+    if union <= T::zero() {
+        return (T::zero(), T::zero());
+    }
+    (overlap / union, union - overlap)
+}
+
+/// Finds where the segment from `p` to `q` crosses the line through `a` and `b`
+///
+/// The segment must actually cross the line, so that `p` and `q` are on opposite sides of it.
+fn crossing<T: HullScalar>(a: Point<T>, b: Point<T>, p: Point<T>, q: Point<T>) -> Point<T> {
+    let (side_p, side_q) = (metrics::cross(a, b, p), metrics::cross(a, b, q));
+    let t = side_p / (side_p - side_q);
+
+    p + (q - p) * t
+}
@@ -1,8 +1,51 @@
-use std::collections::{BinaryHeap, HashSet};
+use std::{
+    borrow::Cow,
+    collections::{HashMap, HashSet},
+    sync::mpsc::Sender,
+};
 
-use nalgebra::Point2 as Point;
+use nalgebra::{Point2 as Point, Vector2};
 
-use crate::{HullScalar, edge::Edge, segment_intersect::edges_intersect};
+use crate::{
+    HullScalar,
+    builder::{ConcaveHullBuilder, LengthCheck},
+    density::DensityField,
+    edge::{Edge, cmp_edge_ties, cmp_points},
+    is_nan,
+    metrics::{cross, is_collinear},
+    pockets::fill_notches,
+    queue::{BoundarySet, DefaultQueue, EdgeQueue, LinearQueue, QueuedEdge},
+    refine::include_edge_points,
+    result::HullResult,
+    segment_intersect::edges_intersect,
+    spacing::mean_kth_neighbor_distance,
+    validate::validate_hull,
+};
+
+/// The largest point cloud we can handle
+///
+/// Point counts are converted to `f64` in a few places (such as density and spacing estimates),
+/// which only represents integers exactly up to 2^53.
+/// For `f32` hulls, those counts are then rounded to the nearest `f32` (exact only up to 2^24),
+/// but they only ever feed estimates, so the rounding doesn't matter there.
+/// In practice, runtime is the limiting factor long before this.
+/// This is a `u64`, since the bound doesn't fit in a `usize` on 32-bit targets (where it can never be reached anyway).
+pub(crate) const MAX_POINTS: u64 = 1 << 53;
+
+/// The number of edges in the hull above which intersection checks are split across threads
+///
+/// Below this, the overhead of handing the work out to rayon outweighs the time saved.
+/// Tests use a much lower threshold, so that the parallel checks run on clouds small enough to hull quickly.
+#[cfg(all(feature = "rayon", not(test)))]
+pub(crate) const PARALLEL_CHECK_EDGES: usize = 1024;
+#[cfg(all(feature = "rayon", test))]
+pub(crate) const PARALLEL_CHECK_EDGES: usize = 32;
+
+/// Point clouds with fewer points than this keep their edges in a [`LinearQueue`], rather than a heap (with stable ordering)
+///
+/// Hulls of clouds this small have at most a handful of edges, so scanning for the longest one beats keeping a heap,
+/// and scanning the points on the hull beats hashing them.
+pub(crate) const SMALL_CLOUD_POINTS: usize = 8;
 
 /// Inner logic for the concave hull functions
 ///
@@ -12,34 +55,715 @@ use crate::{HullScalar, edge::Edge, segment_intersect::edges_intersect};
 #[inline]
 pub(crate) fn concave_hull_inner<T: HullScalar>(
     points: &[Point<T>],
-    concavity: T,
-    convex_hull: Vec<usize>,
-) -> Vec<(usize, Point<T>)> {
-    if points.len() <= 3 {
-        // Degenerate case with enough points for a convex hull, but too few points to make a concave hull
+    options: &ConcaveHullBuilder<T>,
+    convex_hull: &[usize],
+) -> HullResult<T> {
+    if options.small_cloud_queue && options.stable_ordering && points.len() < SMALL_CLOUD_POINTS {
+        return concave_hull_queued_inner::<T, LinearQueue<T>>(points, options, convex_hull);
+    }
+
+    concave_hull_queued_inner::<T, DefaultQueue<T>>(points, options, convex_hull)
+}
+
+/// Computes the concave hull in the same way as [`concave_hull_inner`], and counts the pushes and pops on the edge queue
+///
+/// Empty pops (which end gift opening) count too.
+#[cfg(feature = "benches")]
+pub(crate) fn count_queue_operations<T: HullScalar>(
+    points: &[Point<T>],
+    options: &ConcaveHullBuilder<T>,
+    convex_hull: &[usize],
+) -> usize {
+    use crate::queue::{CountingQueue, QUEUE_OPERATIONS};
+
+    QUEUE_OPERATIONS.set(0);
+    concave_hull_queued_inner::<T, CountingQueue<T>>(points, options, convex_hull);
+    QUEUE_OPERATIONS.get()
+}
+
+/// The same as [`concave_hull_inner`], but with the edges waiting to be split kept in a `Q`
+///
+/// Every [`EdgeQueue`] gives the same hull, so the queue only changes how fast it's found.
+pub(crate) fn concave_hull_queued_inner<T: HullScalar, Q: EdgeQueue<T>>(
+    points: &[Point<T>],
+    options: &ConcaveHullBuilder<T>,
+    convex_hull: &[usize],
+) -> HullResult<T> {
+    debug_assert!(
+        points.len() as u64 <= MAX_POINTS,
+        "Point cloud has more than 2^53 points"
+    );
+
+    let epsilon = geometric_epsilon(options);
+    if is_degenerate(points, convex_hull, epsilon) {
+        // Degenerate case with too few points to make a concave hull
         // Just return the convex hull
-        return convex_hull.into_iter().map(|id| (id, points[id])).collect();
+        let mut hull: Vec<_> = degenerate_order(points, convex_hull, epsilon)
+            .into_iter()
+            .map(|id| (id, points[id]))
+            .collect();
+        if options.close_degenerate && !hull.is_empty() && hull.len() < 3 {
+            // Repeat the first point to close the ring
+            hull.push(hull[0]);
+        }
+
+        return HullResult {
+            hull,
+            is_convex: true,
+        };
+    }
+
+    let edges = convex_edges(points, convex_hull);
+    let fixed = longest_edges(&edges, options.fix_longest_convex);
+    let mut hull = sort_hull(open_edges::<T, Q>(points, options, edges, fixed));
+
+    if let Some(min_area) = options.min_notch_area {
+        hull = fill_notches(hull, convex_hull, min_area);
+    }
+
+    // Every split adds exactly one point to the hull, so this is only true if nothing was split
+    // (or if every split was undone by filling notches)
+    let is_convex = hull.len() == convex_hull.len();
+
+    if let Some(tolerance) = options.edge_point_tolerance {
+        hull = include_edge_points(points, hull, tolerance);
+    }
+
+    HullResult { hull, is_convex }
+}
+
+/// Computes the concave hull for each of the provided concavity values, sharing the convex hull between them
+///
+/// With the `rayon` feature enabled, the hulls are computed in parallel.
+pub(crate) fn concave_hull_sweep_inner<T: HullScalar>(
+    points: &[Point<T>],
+    concavities: &[T],
+    convex_hull: &[usize],
+) -> Vec<Vec<(usize, Point<T>)>> {
+    let hull = |&concavity: &T| {
+        concave_hull_inner(points, &ConcaveHullBuilder::new(concavity), convex_hull).hull
+    };
+
+    #[cfg(feature = "rayon")]
+    {
+        use rayon::prelude::*;
+        concavities.par_iter().map(hull).collect()
+    }
+
+    #[cfg(not(feature = "rayon"))]
+    {
+        concavities.iter().map(hull).collect()
+    }
+}
+
+/// Scores the hull for every combination of relative concavity and neighbour count, returning the combination with the highest score
+///
+/// Each concavity is `factor` times the mean distance to each point's `k`th nearest neighbour (see [`mean_kth_neighbor_distance`]).
+/// The distances are measured once per `k`, and the convex hull is shared between every hull.
+/// NaN scores never win, and ties go to the combination which comes first, going through `knns` for each factor in turn.
+/// Returns `None` if no combination has a score (because either grid is empty, or every score is NaN).
+///
+/// With the `rayon` feature enabled, the hulls are computed in parallel.
+pub(crate) fn parameter_grid_search_inner<T: HullScalar>(
+    points: &[Point<T>],
+    convex_hull: &[usize],
+    factors: &[T],
+    knns: &[usize],
+    objective: impl Fn(&HullResult<T>) -> T + Sync,
+) -> Option<(T, usize)> {
+    let spacings: Vec<T> = knns
+        .iter()
+        .map(|&k| mean_kth_neighbor_distance(points, k))
+        .collect();
+    let grid: Vec<(T, usize, T)> = factors
+        .iter()
+        .flat_map(|&factor| {
+            knns.iter()
+                .zip(&spacings)
+                .map(move |(&k, &spacing)| (factor, k, spacing))
+        })
+        .collect();
+
+    let score = |&(factor, k, spacing): &(T, usize, T)| {
+        let options = ConcaveHullBuilder::new(factor * spacing);
+        (
+            factor,
+            k,
+            objective(&concave_hull_inner(points, &options, convex_hull)),
+        )
+    };
+
+    #[cfg(feature = "rayon")]
+    let scores: Vec<(T, usize, T)> = {
+        use rayon::prelude::*;
+        grid.par_iter().map(score).collect()
+    };
+
+    #[cfg(not(feature = "rayon"))]
+    let scores: Vec<(T, usize, T)> = grid.iter().map(score).collect();
+
+    // `min_by` keeps the first of several equal elements, so compare in reverse to find the first highest score
+    scores
+        .into_iter()
+        .filter(|(_, _, score)| !is_nan(*score))
+        .min_by(|(_, _, a), (_, _, b)| b.total_cmp(a))
+        .map(|(factor, k, _)| (factor, k))
+}
+
+/// Computes the edges of the concave hull, in the order that they were finalized
+///
+/// Unlike [`open_gift`], this handles degenerate point clouds, in the same way as [`concave_hull_inner`].
+/// Sorting the result with [`sort_hull`] gives the same hull as [`concave_hull_inner`], as long as it isn't empty.
+pub(crate) fn concave_hull_unsorted_inner<T: HullScalar>(
+    points: &[Point<T>],
+    options: &ConcaveHullBuilder<T>,
+    convex_hull: &[usize],
+) -> Vec<Edge<T>> {
+    let epsilon = geometric_epsilon(options);
+    if is_degenerate(points, convex_hull, epsilon) {
+        // Degenerate case with too few points to make a concave hull
+        // Just return the edges of the convex hull
+        let convex_hull = degenerate_order(points, convex_hull, epsilon);
+        return (0..convex_hull.len())
+            .map(|id| {
+                let j = convex_hull[(id + 1) % convex_hull.len()];
+                Edge::new(convex_hull[id], j, points)
+            })
+            .collect();
+    }
+
+    open_gift(points, options, convex_hull)
+}
+
+/// Checks if a point cloud is too degenerate to run gift opening on, in which case its hull is just its convex hull
+///
+/// This is the case when there are three points or fewer, or when the convex hull has fewer than three points.
+/// The convex hull only has two points when every point is on a single line (the two ends of that line),
+/// one point when every point is the same, and none when there are no points.
+/// Gift opening needs a convex hull which encloses some area, so these are all returned as-is.
+/// Clouds of three points are degenerate regardless of their convex hull, since there's nothing left to split their edges with.
+/// Clouds which are only nearly on a single line are degenerate too, if every corner of their convex hull
+/// is collinear to within `epsilon` (see [`ConcaveHullBuilder::geometric_epsilon`]).
+pub(crate) fn is_degenerate<T: HullScalar>(
+    points: &[Point<T>],
+    convex_hull: &[usize],
+    epsilon: T,
+) -> bool {
+    points.len() <= 3 || convex_hull.len() < 3 || is_flat(points, convex_hull, epsilon)
+}
+
+/// Checks if every corner of a convex hull is collinear to within `epsilon`, so that the whole hull is (nearly) a line
+fn is_flat<T: HullScalar>(points: &[Point<T>], convex_hull: &[usize], epsilon: T) -> bool {
+    let n = convex_hull.len();
+    (0..n).all(|k| {
+        is_collinear(
+            points[convex_hull[(k + n - 1) % n]],
+            points[convex_hull[k]],
+            points[convex_hull[(k + 1) % n]],
+            epsilon,
+        )
+    })
+}
+
+/// The collinearity tolerance to use, filling in the default for the precision if it wasn't set
+pub(crate) fn geometric_epsilon<T: HullScalar>(options: &ConcaveHullBuilder<T>) -> T {
+    options.geometric_epsilon.unwrap_or_else(T::default_epsilon)
+}
+
+/// Puts the convex hull of a degenerate point cloud into the crate's own order, rather than whatever order parry found it in
+///
+/// Triangles are wound counter-clockwise, and every hull starts from its lexicographically smallest point (see [`cmp_points`]).
+/// Repeat points are told apart by their position in `convex_hull`, so the first one is kept at the start.
+/// Hulls which are collinear to within `epsilon` (see [`is_degenerate`]) are cut down to the two points farthest apart,
+/// in the same way as parry does for exactly collinear points.
+fn degenerate_order<T: HullScalar>(
+    points: &[Point<T>],
+    convex_hull: &[usize],
+    epsilon: T,
+) -> Vec<usize> {
+    let mut hull = convex_hull.to_vec();
+    if hull.len() >= 3 && is_flat(points, &hull, epsilon) {
+        let pairs = (0..hull.len()).flat_map(|a| (a + 1..hull.len()).map(move |b| (a, b)));
+        let distance = |(a, b): (usize, usize)| (points[hull[b]] - points[hull[a]]).norm_squared();
+        let (a, b) = pairs
+            .max_by(|&x, &y| distance(x).total_cmp(&distance(y)))
+            .expect("Hull has at least three points");
+        hull = Vec::from([hull[a], hull[b]]);
+    } else if hull.len() == 3
+        && cross(points[hull[0]], points[hull[1]], points[hull[2]]) < T::zero()
+    {
+        hull.swap(1, 2);
+    }
+
+    let start = (0..hull.len()).min_by(|&a, &b| cmp_points(&points[hull[a]], &points[hull[b]]));
+    if let Some(start) = start {
+        hull.rotate_left(start);
+    }
+
+    hull
+}
+
+/// Runs the gift opening process, returning the edges of the concave hull in an arbitrary order
+///
+/// Assumes that the point cloud is not degenerate (see [`is_degenerate`]).
+/// The last edge in the returned [`Vec`] is the last edge that was finalized.
+#[inline]
+pub(crate) fn open_gift<T: HullScalar>(
+    points: &[Point<T>],
+    options: &ConcaveHullBuilder<T>,
+    convex_hull: &[usize],
+) -> Vec<Edge<T>> {
+    let edges = convex_edges(points, convex_hull);
+    let fixed = longest_edges(&edges, options.fix_longest_convex);
+
+    open_edges::<T, DefaultQueue<T>>(points, options, edges, fixed)
+}
+
+/// Computes progressively refined concave hulls, yielding a complete hull after every split
+///
+/// The first hull is the convex hull, and the last hull is the same as the one from [`concave_hull_inner`]
+/// (ignoring any post-processing options).
+/// Degenerate point clouds yield their only hull once.
+pub(crate) fn concave_hull_anytime_inner<'a, T: HullScalar>(
+    points: &'a [Point<T>],
+    mut options: ConcaveHullBuilder<T>,
+    convex_hull: &[usize],
+) -> impl Iterator<Item = Vec<(usize, Point<T>)>> + use<'a, T> {
+    // Snapshots are sorted from the last edge in the heap, so finalizing edges early would rotate them
+    options.eager_finalize = false;
+
+    let epsilon = geometric_epsilon(&options);
+    let (mut degenerate, mut opening) = if is_degenerate(points, convex_hull, epsilon) {
+        let hull = concave_hull_inner(points, &options, convex_hull).hull;
+        (Some(hull), None)
+    } else {
+        let edges = convex_edges(points, convex_hull);
+        let fixed = longest_edges(&edges, options.fix_longest_convex);
+        let opening =
+            GiftOpening::<T, DefaultQueue<T>>::new(points, Cow::Owned(options), edges, fixed);
+        (None, Some(opening))
+    };
+    let mut started = false;
+
+    std::iter::from_fn(move || {
+        if let Some(hull) = degenerate.take() {
+            return Some(hull);
+        }
+
+        let current = opening.as_mut()?;
+        if !started {
+            started = true;
+            return Some(current.snapshot());
+        }
+
+        loop {
+            match current.step() {
+                Some(true) => return Some(current.snapshot()),
+                Some(false) => {}
+                None => return opening.take().map(|opening| sort_hull(opening.finish())),
+            }
+        }
+    })
+}
+
+/// Runs the gift opening process, sending each edge of the concave hull down `sender` as soon as it's finalized
+///
+/// Edges are sent in the order that they're finalized, so the last edge sent is the last edge finalized,
+/// and sorting every edge sent with [`sort_hull`] gives the same hull as [`concave_hull_inner`]
+/// (ignoring any post-processing options).
+/// Degenerate point clouds send every edge of their hull at once, in the same way as [`concave_hull_unsorted_inner`].
+/// If the receiver hangs up, nobody is left to use the hull, so this stops early.
+pub(crate) fn concave_hull_channeled_inner<T: HullScalar>(
+    points: &[Point<T>],
+    mut options: ConcaveHullBuilder<T>,
+    convex_hull: &[usize],
+    sender: &Sender<Edge<T>>,
+) {
+    // Short edges would be finalized (and sent) as soon as they were made, out of order, rather than when they come off of the heap
+    options.eager_finalize = false;
+
+    if is_degenerate(points, convex_hull, geometric_epsilon(&options)) {
+        for edge in concave_hull_unsorted_inner(points, &options, convex_hull) {
+            if sender.send(edge).is_err() {
+                return;
+            }
+        }
+        return;
+    }
+
+    let edges = convex_edges(points, convex_hull);
+    let fixed = longest_edges(&edges, options.fix_longest_convex);
+    let mut opening =
+        GiftOpening::<T, DefaultQueue<T>>::new(points, Cow::Owned(options), edges, fixed);
+
+    while let Some(split) = opening.step() {
+        if split {
+            continue;
+        }
+
+        let edge = opening
+            .concave_hull
+            .last()
+            .expect("An edge was just finalized")
+            .clone();
+        if sender.send(edge).is_err() {
+            return;
+        }
+    }
+}
+
+/// Runs the gift opening process starting from the edges of a previous hull, rather than from the convex hull
+///
+/// `previous` lists the indices of the previous hull's points, in order.
+/// Returns `None` if the previous hull isn't a valid counter-clockwise hull of the current points (see [`validate_hull`]),
+/// or if the point cloud is degenerate, in which case the hull should be computed from scratch.
+pub(crate) fn concave_hull_warm_inner<T: HullScalar>(
+    points: &[Point<T>],
+    options: &ConcaveHullBuilder<T>,
+    previous: &[usize],
+) -> Option<Vec<(usize, Point<T>)>> {
+    let ring = previous
+        .iter()
+        .map(|&id| Some((id, *points.get(id)?)))
+        .collect::<Option<Vec<_>>>()?;
+    if points.len() <= 3 || ring.len() < 3 || validate_hull(points, &ring).is_err() {
+        return None;
+    }
+
+    let edges: Vec<Edge<T>> = (0..ring.len())
+        .map(|k| Edge::new(ring[k].0, ring[(k + 1) % ring.len()].0, points))
+        .collect();
+
+    // The starting edges aren't the edges of the convex hull, so they need the full intersection checks
+    let mut options = options.clone();
+    options.cheap_convex_checks = false;
+    let opening =
+        GiftOpening::<T, DefaultQueue<T>>::new(points, Cow::Owned(options), edges, HashSet::new());
+
+    Some(sort_hull(opening.finish()))
+}
+
+/// Collects up the edges of the convex hull
+fn convex_edges<T: HullScalar>(points: &[Point<T>], convex_hull: &[usize]) -> Vec<Edge<T>> {
+    (0..convex_hull.len())
+        .map(|id| {
+            let i = convex_hull[id];
+            let j = convex_hull[(id + 1) % convex_hull.len()];
+
+            Edge::new(i, j, points)
+        })
+        .collect()
+}
+
+/// Picks out the `count` longest edges, which are fixed in place for [`ConcaveHullBuilder::fix_longest_convex`]
+///
+/// Returns the endpoints of each edge, as `(i, j)`.
+/// Ties in length are broken in the same way as for the edges themselves, so the same edges are always picked.
+fn longest_edges<T: HullScalar>(edges: &[Edge<T>], count: usize) -> HashSet<(usize, usize)> {
+    let mut longest: Vec<&Edge<T>> = edges.iter().collect();
+    longest.sort_unstable_by(|a, b| b.cmp(a));
+
+    longest
+        .into_iter()
+        .take(count)
+        .map(|edge| (edge.i, edge.j))
+        .collect()
+}
+
+/// Runs the gift opening process on a single edge between two endpoints, producing an open path between them
+///
+/// Returns the points of the path in order, from `start` to `end`,
+/// or `None` if the endpoints are the same point, or either isn't a valid index into `points`.
+pub(crate) fn open_path_inner<T: HullScalar>(
+    points: &[Point<T>],
+    options: &ConcaveHullBuilder<T>,
+    start: usize,
+    end: usize,
+) -> Option<Vec<(usize, Point<T>)>> {
+    if start == end || start >= points.len() || end >= points.len() {
+        return None;
+    }
+
+    let mut edges: HashMap<usize, Edge<T>> = open_edges::<T, DefaultQueue<T>>(
+        points,
+        options,
+        [Edge::new(start, end, points)],
+        HashSet::new(),
+    )
+    .into_iter()
+    .map(|edge| (edge.i, edge))
+    .collect();
+
+    // Walk the edges from start to end
+    let mut path = Vec::with_capacity(edges.len() + 1);
+    path.push((start, points[start]));
+    let mut curr = start;
+    while curr != end {
+        let edge = edges.remove(&curr).expect("Path is well-formed");
+        path.push((edge.j, edge.point_j));
+        curr = edge.j;
+    }
+
+    Some(path)
+}
+
+/// Repeatedly splits the provided edges until they can't be split any further
+///
+/// Edges whose endpoints are in `fixed` are never split.
+/// Returns the finalized edges in an arbitrary order,
+/// except that the last edge in the returned [`Vec`] is the last edge that was finalized.
+#[inline]
+fn open_edges<T: HullScalar, Q: EdgeQueue<T>>(
+    points: &[Point<T>],
+    options: &ConcaveHullBuilder<T>,
+    edges: impl IntoIterator<Item = Edge<T>>,
+    fixed: HashSet<(usize, usize)>,
+) -> Vec<Edge<T>> {
+    GiftOpening::<T, Q>::new(points, Cow::Borrowed(options), edges, fixed).finish()
+}
+
+/// An in-progress run of the gift opening process, which can be advanced one edge at a time
+pub(crate) struct GiftOpening<'a, T: HullScalar, Q: EdgeQueue<T> = DefaultQueue<T>> {
+    points: &'a [Point<T>],
+    options: Cow<'a, ConcaveHullBuilder<T>>,
+    /// Edges which might still be split
+    edge_heap: Q,
+    /// Every point which is on the hull so far
+    boundary_points: Q::Boundary,
+    /// Edges which are finalized, in the order they were finalized
+    concave_hull: Vec<Edge<T>>,
+    /// The squared concavity limit
+    concavity: T,
+    /// The density field, mean density, and strength of the scaling, if the length check is density scaled
+    density: Option<(DensityField<T>, T, T)>,
+    /// Whether each point is an endpoint of one of the initial edges
+    ///
+    /// Splits only ever add points which aren't on the boundary yet, so every edge created by a split has
+    /// at least one endpoint which isn't an initial endpoint.
+    /// This means that an edge between two initial endpoints must be one of the initial edges, which was never split.
+    initial: Vec<bool>,
+    /// The edges of every obstacle, which new edges must not cross
+    obstacle_edges: Vec<Edge<T>>,
+    /// The endpoints of the starting edges which must never be split, as `(i, j)`
+    fixed: HashSet<(usize, usize)>,
+    /// Whether edges which are too short to split are finalized as soon as they're made, rather than queued
+    ///
+    /// Short edges are always finalized when they come off of the heap, so this only changes the order they're finalized in.
+    /// That order only matters for the last finalized edge, so the lowest priority short edge is tracked,
+    /// and moved to the end once the gift is open.
+    eager: bool,
+    /// The position in the finalized edges, and the priority, of the lowest priority edge which was finalized eagerly
+    lowest_eager: Option<(usize, T)>,
+}
+
+impl<'a, T: HullScalar, Q: EdgeQueue<T>> GiftOpening<'a, T, Q> {
+    /// Starts opening the gift from the provided edges
+    ///
+    /// `edges` must either be the edges of the convex hull, or a single edge,
+    /// or the edges of a valid hull (in which case `cheap_convex_checks` must be turned off).
+    /// Edges whose endpoints are in `fixed` are finalized as-is, no matter how long they are.
+    pub(crate) fn new(
+        points: &'a [Point<T>],
+        options: Cow<'a, ConcaveHullBuilder<T>>,
+        edges: impl IntoIterator<Item = Edge<T>>,
+        fixed: HashSet<(usize, usize)>,
+    ) -> Self {
+        let edges: Vec<Edge<T>> = edges.into_iter().collect();
+        // With a density scaled length check, the limit is scaled by the local density around each edge
+        // This is in the original paper, but *not* in the JS impl, so it's opt-in
+        let density = match options.length_check {
+            LengthCheck::Flat => None,
+            LengthCheck::DensityScaled(k) => {
+                let field = DensityField::build(points);
+                let mean_density = field.mean_density();
+                Some((field, mean_density, k))
+            }
+        };
+
+        // Obstacle edges aren't between points in the cloud, so give them indices which no hull edge can share
+        // Otherwise, the intersection check would treat them as connected to hull edges
+        let obstacle_edges = options
+            .obstacles
+            .iter()
+            .flat_map(|ring| {
+                (0..ring.len()).map(|k| Edge {
+                    i: usize::MAX - 1,
+                    j: usize::MAX,
+                    point_i: ring[k],
+                    point_j: ring[(k + 1) % ring.len()],
+                })
+            })
+            .collect();
+
+        // The heap, boundary, and output each grow to at most the size of the hull, so size them with the hint
+        let capacity_hint = options.capacity_hint;
+        let mut boundary_points: Q::Boundary =
+            edges.iter().flat_map(|edge| [edge.i, edge.j]).collect();
+        boundary_points.reserve(capacity_hint.saturating_sub(edges.len()));
+
+        // With the default priority (and a fixed limit), every short edge has a lower priority than every long edge,
+        // so short edges are only ever popped after all of the splitting is done, and finalized in priority order
+        // That order is only reproducible with stable ordering, since otherwise ties come off of the heap in an arbitrary order
+        let eager = options.eager_finalize
+            && options.stable_ordering
+            && options.priority.is_none()
+            && options.length_check == LengthCheck::Flat;
+
+        let mut opening = Self {
+            points,
+            // Square the concavity limit to make the comparisons slightly faster
+            concavity: options.concavity.powi(2),
+            options,
+            edge_heap: Q::with_capacity(capacity_hint),
+            boundary_points,
+            concave_hull: Vec::with_capacity(capacity_hint),
+            density,
+            initial: vec![false; points.len()],
+            obstacle_edges,
+            fixed,
+            eager,
+            lowest_eager: None,
+        };
+
+        // Heap up the edges by priority
+        for edge in edges {
+            opening.initial[edge.i] = true;
+            opening.initial[edge.j] = true;
+            opening.push(edge);
+        }
+        let edge_count = opening.edge_heap.len() + opening.concave_hull.len();
+        opening
+            .concave_hull
+            .reserve(edge_count.max(capacity_hint) - opening.concave_hull.len());
+
+        opening
+    }
+
+    /// Puts an edge on the heap, or finalizes it straight away if it's too short to split (and eager finalization is on)
+    fn push(&mut self, edge: Edge<T>) {
+        let queued = self.queue(edge);
+        if !self.eager || self.length_squared(&queued.edge) > self.limit(&queued.edge) {
+            self.edge_heap.push(queued);
+            return;
+        }
+
+        let lowest = self.lowest_eager.is_none_or(|(position, priority)| {
+            queued
+                .priority
+                .total_cmp(&priority)
+                .then_with(|| cmp_edge_ties(&queued.edge, &self.concave_hull[position]))
+                .is_lt()
+        });
+        if lowest {
+            self.lowest_eager = Some((self.concave_hull.len(), queued.priority));
+        }
+        self.concave_hull.push(queued.edge);
+    }
+
+    /// Wraps an edge up with its priority, ready to go on the heap
+    fn queue(&self, edge: Edge<T>) -> QueuedEdge<T> {
+        QueuedEdge {
+            priority: match &self.options.priority {
+                Some(priority) => priority(&edge.point_i, &edge.point_j),
+                None => self.length_squared(&edge),
+            },
+            edge,
+            stable: self.options.stable_ordering,
+        }
     }
 
-    // Heap up the convex edges by length
-    let mut edge_heap = BinaryHeap::with_capacity(convex_hull.len());
-    let mut boundary_points = HashSet::with_capacity(convex_hull.len());
-    for id in 0..convex_hull.len() {
-        let i = convex_hull[id];
-        let j = convex_hull[(id + 1) % convex_hull.len()];
+    /// The squared length of `edge`, as measured by the configured distance function
+    fn length_squared(&self, edge: &Edge<T>) -> T {
+        match &self.options.distance {
+            Some(distance) => distance(&edge.point_i, &edge.point_j).powi(2),
+            None => edge.norm_squared(),
+        }
+    }
 
-        boundary_points.insert(i);
-        edge_heap.push(Edge::new(i, j, points));
+    /// The squared length above which `edge` should be split
+    fn limit(&self, edge: &Edge<T>) -> T {
+        let Some((field, mean_density, k)) = &self.density else {
+            return self.concavity;
+        };
+
+        let sample = |p: &Point<T>| {
+            if self.options.cache_density {
+                field.density_at(p)
+            } else {
+                field.density_at_uncached(self.points, p)
+            }
+        };
+
+        // Spacing between points goes with the inverse square root of density,
+        // so this scales the squared limit by the squared ratio of local spacing to average spacing (to the power k)
+        // Endpoints are always in cells with at least one point, so the local density is never zero
+        let local_density =
+            (sample(&edge.point_i) + sample(&edge.point_j)) / nalgebra::convert::<f64, T>(2.0);
+        self.concavity * (*mean_density / local_density).powf(*k)
     }
 
-    // Start opening the gift
-    let concavity = concavity.powi(2); // Square the concavity limit to make the comparisons slightly faster
-    let mut concave_hull: Vec<Edge<T>> = Vec::with_capacity(convex_hull.len());
+    /// Whether `point` is further from the line through `edge` than the minimum feature size (if there is one)
+    fn deep_enough(&self, edge: &Edge<T>, point: &Point<T>) -> bool {
+        let Some(min_feature) = self.options.min_feature else {
+            return true;
+        };
+
+        // The cross product is the distance from the line times the length of the edge,
+        // so compare squares to avoid the square root
+        let cross = cross(edge.point_i, edge.point_j, *point);
+        cross * cross > min_feature * min_feature * edge.norm_squared()
+    }
 
-    'edges: while let Some(edge) = edge_heap.pop() {
-        // TODO: scale this check based on local density?
-        // It's in the original paper, but *not* in the JS impl...
-        if edge.norm_squared() > concavity {
+    /// Whether splitting `edge` with the point `p` (at index `i`) would make an edge which crosses an obstacle
+    fn blocked(&self, edge: &Edge<T>, i: usize, p: &Point<T>) -> bool {
+        if self.obstacle_edges.is_empty() {
+            return false;
+        }
+
+        let (e1, e2) = edge.split_by(*p, i);
+        self.obstacle_edges
+            .iter()
+            .any(|obstacle| edges_intersect(obstacle, &e1) || edges_intersect(obstacle, &e2))
+    }
+
+    /// Checks if any edge of the hull so far (finalized or not) matches `predicate`
+    ///
+    /// With the `rayon` feature enabled, large hulls are checked in parallel.
+    /// The predicate is pure, so this gives the same answer either way.
+    /// Queues only promise an iterator, so their edges are bridged over to rayon as they're iterated.
+    fn any_edge(&self, predicate: &(impl Fn(&Edge<T>) -> bool + Sync)) -> bool {
+        #[cfg(feature = "rayon")]
+        if self.options.parallel_checks
+            && self.concave_hull.len() + self.edge_heap.len() >= PARALLEL_CHECK_EDGES
+        {
+            use rayon::prelude::*;
+            return self
+                .concave_hull
+                .par_iter()
+                .chain(
+                    self.edge_heap
+                        .iter()
+                        .par_bridge()
+                        .map(|queued| &queued.edge),
+                )
+                .any(predicate);
+        }
+
+        self.concave_hull
+            .iter()
+            .chain(self.edge_heap.iter().map(|queued| &queued.edge))
+            .any(predicate)
+    }
+
+    /// Pops the next edge off of the heap, and either splits it or finalizes it
+    ///
+    /// Returns `None` if there are no edges left, otherwise returns whether the edge was split.
+    pub(crate) fn step(&mut self) -> Option<bool> {
+        let QueuedEdge { edge, .. } = self.edge_heap.pop()?;
+        let points = self.points;
+
+        let fixed = !self.fixed.is_empty() && self.fixed.contains(&(edge.i, edge.j));
+        if !fixed && self.length_squared(&edge) > self.limit(&edge) {
             // This edge is long enough that we should try to split it
 
             // Find the best point to add in the middle
@@ -50,57 +774,196 @@ pub(crate) fn concave_hull_inner<T: HullScalar>(
                     // Do not consider points that are already on the edge
                     continue 'points;
                 }
+                if self.options.next_best_on_boundary && self.boundary_points.contains(i) {
+                    // Look past points that are already on the hull, rather than giving up on the edge
+                    continue 'points;
+                }
                 let e1 = p - edge.point_i;
                 let e2 = edge.point_j - p;
                 let e_v = edge.point_j - edge.point_i;
 
-                let angle = e_v.angle(&e1).max(e_v.angle(&e2));
-                if best.as_ref().map(|best| best.2 > angle).unwrap_or(true) {
-                    best = Some((i, p, angle));
+                // Smaller scores are better
+                let score = if self.options.angle_surrogate
+                    && self.options.weights.is_none()
+                    && self.options.axis_bias.is_none()
+                {
+                    // The best point has the smallest maximum angle, which is the largest minimum cosine,
+                    // so the cosines can be compared directly, without the expensive arccosine
+                    // Points on top of an endpoint have no meaningful angle, and NaNs can't be compared,
+                    // so treat both as the worst possible candidates, and never pick them
+                    match (cosine(&e_v, &e1), cosine(&e_v, &e2)) {
+                        (Some(c1), Some(c2)) if !is_nan(c1) && !is_nan(c2) => -c1.min(c2),
+                        _ => continue 'points,
+                    }
+                } else {
+                    let angle = e_v.angle(&e1).max(e_v.angle(&e2));
+                    // Points on top of an endpoint have no meaningful angle (nalgebra reports zero, which looks perfect),
+                    // and NaNs can't be compared, so treat both as the worst possible candidates, and never pick them
+                    // Zero-length vectors always give an angle of zero, so only check for them when the angle is zero
+                    let on_endpoint = angle == T::zero()
+                        && (e1.norm_squared() == T::zero() || e2.norm_squared() == T::zero());
+                    if on_endpoint || is_nan(angle) {
+                        continue 'points;
+                    }
+
+                    // Heavier points are favoured, by dividing their angle by their weight
+                    let angle = match &self.options.weights {
+                        Some(weights) => angle / weights[i],
+                        None => angle,
+                    };
+
+                    // Diagonal sub-edges are penalized by scaling up the angle
+                    match self.options.axis_bias {
+                        Some(bias) => {
+                            angle * (T::one() + bias * diagonality(&e1).max(diagonality(&e2)))
+                        }
+                        None => angle,
+                    }
+                };
+                // Ties go to the first point, or with stable ordering, to the point with the smallest coordinates,
+                // so that the choice doesn't depend on the order of the cloud
+                // Points which route through an obstacle are skipped, but that's only checked for points which would win,
+                // since it's far more expensive than scoring
+                if best
+                    .as_ref()
+                    .map(|best| {
+                        let order = best.2.total_cmp(&score);
+                        if self.options.stable_ordering {
+                            order.then_with(|| cmp_points(best.1, p)).is_gt()
+                        } else {
+                            order.is_gt()
+                        }
+                    })
+                    .unwrap_or(true)
+                    && !self.blocked(&edge, i, p)
+                {
+                    best = Some((i, p, score));
                 }
             }
-            let best = best.expect("Point cloud should have at least one point");
 
             // Check boundary to avoid creating a degenerate polygon
             // Note: The original paper recommends adding a check to make sure the angle is less than 90 degrees.
             //       I did a ton of testing and I could not find a single case where this made a difference
             //       in the final hull, even though the check was hit multiple times.
             //       So, I ommitted it for performance.
-            if !boundary_points.contains(&best.0) {
+            // If there are no other points at all (only possible for paths), then there's nothing to split with
+            // Points too close to the edge to count as a feature are never split with either,
+            // so the edge is finalized as-is, rather than falling back to a worse candidate
+            if let Some(best) = best.filter(|best| {
+                !self.boundary_points.contains(best.0) && self.deep_enough(&edge, best.1)
+            }) {
                 let (e1, e2) = edge.split_by(*best.1, best.0);
 
                 // Check if the new edges would intersect any existing ones
                 // TODO: BVH might be faster? Hard to say given how frequently we'd be adding new segments
-                if concave_hull
-                    .iter()
-                    .chain(edge_heap.iter())
-                    .all(|edge| !(edges_intersect(edge, &e1) || edges_intersect(edge, &e2)))
-                {
-                    edge_heap.push(e1);
-                    edge_heap.push(e2);
-                    boundary_points.insert(best.0);
-                    continue 'edges;
+                let crosses = |edge: &Edge<T>| {
+                    if self.options.cheap_convex_checks
+                        && self.initial[edge.i]
+                        && self.initial[edge.j]
+                    {
+                        // This is an unsplit edge of the convex hull, so it's on the boundary of the convex hull,
+                        // while the new edges are inside of it (or the gift is a single edge, which is never checked)
+                        // The new edges can only meet it where the new point sits on it, which is much cheaper to check
+                        on_segment(edge, best.1)
+                    } else {
+                        edges_intersect(edge, &e1) || edges_intersect(edge, &e2)
+                    }
+                };
+                if !self.any_edge(&crosses) {
+                    self.push(e1);
+                    self.push(e2);
+                    self.boundary_points.insert(best.0);
+                    return Some(true);
                 }
             }
         }
 
-        concave_hull.push(edge);
+        self.concave_hull.push(edge);
+        Some(false)
     }
 
-    // Sort the edges in the hull end to end
-    // TODO: Can we get clever with pointer shenanigans to maintain this as we build the hull?
+    /// The hull as it stands, with every edge that is still waiting to be split treated as final
+    ///
+    /// The points are in winding order, starting from an arbitrary point.
+    pub(crate) fn snapshot(&self) -> Vec<(usize, Point<T>)> {
+        let edges: Vec<Edge<T>> = self
+            .concave_hull
+            .iter()
+            .cloned()
+            .chain(self.edge_heap.iter().map(|queued| queued.edge.clone()))
+            .collect();
+
+        sort_hull(edges)
+    }
+
+    /// Runs the gift opening process to completion
+    ///
+    /// Returns the finalized edges in an arbitrary order,
+    /// except that the last edge in the returned [`Vec`] is the last edge that was finalized.
+    pub(crate) fn finish(mut self) -> Vec<Edge<T>> {
+        while self.step().is_some() {}
+
+        // The heap would have finalized the lowest priority short edge last, so put it where it would have been
+        if let Some((position, _)) = self.lowest_eager {
+            let last = self.concave_hull.len() - 1;
+            self.concave_hull.swap(position, last);
+        }
+
+        self.concave_hull
+    }
+}
+
+/// The cosine of the angle between two vectors, or `None` if either has zero length
+///
+/// This is computed in exactly the same way as [`nalgebra::Matrix::angle`], minus the final arccosine,
+/// so that comparing cosines picks the same candidates as comparing angles (up to ties in the arccosine's rounding).
+fn cosine<T: HullScalar>(a: &Vector2<T>, b: &Vector2<T>) -> Option<T> {
+    let (norm_a, norm_b) = (a.norm(), b.norm());
+    if norm_a == T::zero() || norm_b == T::zero() {
+        return None;
+    }
+
+    Some((a.dot(b) / (norm_a * norm_b)).clamp(-T::one(), T::one()))
+}
+
+/// How far a vector is from being axis-aligned, as `|sin 2θ|` for its direction `θ`
+///
+/// This is zero for horizontal and vertical vectors, and one for vectors at 45 degrees.
+/// The vector must not have zero length.
+fn diagonality<T: HullScalar>(v: &Vector2<T>) -> T {
+    (v.x * v.y).abs() * nalgebra::convert::<f64, T>(2.0) / v.norm_squared()
+}
+
+/// Checks if `point` lies on `edge`, including its endpoints
+fn on_segment<T: HullScalar>(edge: &Edge<T>, point: &Point<T>) -> bool {
+    let (a, b) = (edge.point_i, edge.point_j);
+
+    cross(a, b, *point) == T::zero()
+        && point.x >= a.x.min(b.x)
+        && point.x <= a.x.max(b.x)
+        && point.y >= a.y.min(b.y)
+        && point.y <= a.y.max(b.y)
+}
+
+/// Sorts the edges of a hull end to end, returning the points in winding order
+///
+/// The last edge in `concave_hull` is used as the starting edge.
+#[inline]
+pub fn sort_hull<T: HullScalar>(mut concave_hull: Vec<Edge<T>>) -> Vec<(usize, Point<T>)> {
     let mut sorted_hull = Vec::with_capacity(concave_hull.len());
     let mut curr = concave_hull
         .pop() // Start with an arbitrary edge
         .expect("Concave hull has at least one point");
 
-    while !concave_hull.is_empty() {
+    // Each point has exactly one outgoing edge, so we can look up the next edge by its first point
+    let mut edges: HashMap<usize, Edge<T>> = concave_hull
+        .into_iter()
+        .map(|edge| (edge.i, edge))
+        .collect();
+
+    while !edges.is_empty() {
         // Walk the pointers, grabbing edges in order
-        let next = concave_hull
-            .iter()
-            .position(|edge| edge.i == curr.j)
-            .expect("Concave hull is well-formed");
-        let next = concave_hull.swap_remove(next);
+        let next = edges.remove(&curr.j).expect("Concave hull is well-formed");
 
         sorted_hull.push((curr.i, curr.point_i));
         curr = next;
@@ -0,0 +1,82 @@
+use nalgebra::Point2 as Point;
+
+use crate::HullScalar;
+
+/// Computes the Delaunay triangulation of a point cloud, using the Bowyer-Watson algorithm
+///
+/// Returns triangles as indices into `points`, each wound counter-clockwise.
+/// Point clouds with fewer than three points, or with every point on a single line, have no triangles.
+/// It is assumed that `points` contains no repeat points.
+///
+/// Points are inserted one at a time into a large "super triangle" enclosing the whole cloud,
+/// and every triangle whose circumcircle contains the new point is replaced.
+/// The triangles are scanned in full for every insertion, so this is O(n²).
+///
+/// The geometric predicates are evaluated in `f64`, regardless of `T`,
+/// since the super triangle's vertices are far away from the cloud, and lower precisions break down quickly.
+pub(crate) fn triangulate<T: HullScalar>(points: &[Point<T>]) -> Vec<[usize; 3]> {
+    let n = points.len();
+    if n < 3 {
+        return Vec::new();
+    }
+
+    let mut vertices: Vec<Point<f64>> = points
+        .iter()
+        .map(|p| {
+            Point::new(
+                nalgebra::try_convert::<T, f64>(p.x).unwrap_or(f64::NAN),
+                nalgebra::try_convert::<T, f64>(p.y).unwrap_or(f64::NAN),
+            )
+        })
+        .collect();
+
+    // Build a super triangle, far enough away that it doesn't distort the edge of the cloud
+    let (mut mins, mut maxs) = (vertices[0], vertices[0]);
+    for p in &vertices {
+        mins = mins.inf(p);
+        maxs = maxs.sup(p);
+    }
+    let size = (maxs - mins).max().max(1.);
+    let center = nalgebra::center(&mins, &maxs);
+    vertices.extend([
+        Point::new(center.x - 100. * size, center.y - size),
+        Point::new(center.x + 100. * size, center.y - size),
+        Point::new(center.x, center.y + 100. * size),
+    ]);
+
+    let mut triangles = Vec::from([[n, n + 1, n + 2]]);
+    for (i, p) in vertices[..n].iter().enumerate() {
+        // Remove every triangle whose circumcircle contains the new point, leaving a polygonal cavity
+        let (bad, good): (Vec<[usize; 3]>, Vec<[usize; 3]>) = triangles
+            .into_iter()
+            .partition(|&[a, b, c]| in_circumcircle(vertices[a], vertices[b], vertices[c], *p));
+        triangles = good;
+
+        // Edges of the bad triangles which aren't shared between two of them make up the edge of the cavity
+        // There are only ever a handful of bad triangles, so a linear search for shared edges is fine
+        let edges: Vec<(usize, usize)> = bad
+            .iter()
+            .flat_map(|&[a, b, c]| [(a, b), (b, c), (c, a)])
+            .collect();
+        for &(a, b) in &edges {
+            if !edges.contains(&(b, a)) {
+                // The cavity is star-shaped around the new point, so these are counter-clockwise too
+                triangles.push([a, b, i]);
+            }
+        }
+    }
+
+    // Anything connected to the super triangle is outside of the point cloud's convex hull
+    triangles.retain(|triangle| triangle.iter().all(|&k| k < n));
+    triangles
+}
+
+/// Checks if `d` is strictly inside of the circumcircle of the counter-clockwise triangle `a`, `b`, `c`
+fn in_circumcircle(a: Point<f64>, b: Point<f64>, c: Point<f64>, d: Point<f64>) -> bool {
+    let (ad, bd, cd) = (a - d, b - d, c - d);
+
+    let determinant = ad.norm_squared() * (bd.x * cd.y - cd.x * bd.y)
+        - bd.norm_squared() * (ad.x * cd.y - cd.x * ad.y)
+        + cd.norm_squared() * (ad.x * bd.y - bd.x * ad.y);
+    determinant > 0.
+}
@@ -0,0 +1,124 @@
+use nalgebra::{Point2 as Point, Scalar};
+
+use crate::HullScalar;
+
+/// A precomputed estimate of the local point density across a point cloud
+///
+/// The bounding box of the point cloud is divided into a grid of square cells,
+/// sized so that there is roughly one point per cell on average,
+/// and the number of points in each cell is counted up front.
+/// Sampling the field is then just a lookup of the cell containing the sample point, which is O(1).
+///
+/// Densities are measured in points per unit area.
+/// Samples outside of the point cloud's bounding box have a density of zero.
+#[derive(Debug, Clone)]
+pub struct DensityField<T: Scalar> {
+    pub(crate) mins: Point<T>,
+    pub(crate) cell_size: T,
+    pub(crate) columns: usize,
+    pub(crate) rows: usize,
+    pub(crate) counts: Vec<usize>,
+    pub(crate) total: usize,
+}
+
+impl<T: HullScalar> DensityField<T> {
+    /// Builds the density field for the provided point cloud
+    pub(crate) fn build(points: &[Point<T>]) -> Self {
+        let Some(first) = points.first() else {
+            return Self {
+                mins: Point::origin(),
+                cell_size: T::one(),
+                columns: 0,
+                rows: 0,
+                counts: Vec::new(),
+                total: 0,
+            };
+        };
+
+        let (mut mins, mut maxs) = (*first, *first);
+        for p in points {
+            mins = mins.inf(p);
+            maxs = maxs.sup(p);
+        }
+        let extents = maxs - mins;
+
+        // Aim for about one point per cell, but never have more than n cells along the longest side,
+        // which would happen for long, thin clouds (and outright for flat ones, where the area is zero)
+        // Together, these keep the grid down to O(n) cells
+        let n: T = nalgebra::convert(points.len() as f64);
+        let mut cell_size = (extents.x * extents.y / n)
+            .sqrt()
+            .max(extents.x.max(extents.y) / n);
+        if cell_size <= T::zero() {
+            // The cloud is a single point
+            cell_size = T::one();
+        }
+
+        let columns = cell_coordinate(extents.x / cell_size) + 1;
+        let rows = cell_coordinate(extents.y / cell_size) + 1;
+
+        let mut field = Self {
+            mins,
+            cell_size,
+            columns,
+            rows,
+            counts: vec![0; columns * rows],
+            total: points.len(),
+        };
+        for p in points {
+            let cell = field
+                .cell(p)
+                .expect("Points are inside their own bounding box");
+            field.counts[cell] += 1;
+        }
+
+        field
+    }
+
+    /// Finds the index of the cell containing `point`, if it is in the grid
+    fn cell(&self, point: &Point<T>) -> Option<usize> {
+        let offset = (point - self.mins) / self.cell_size;
+        if offset.x < T::zero() || offset.y < T::zero() {
+            return None;
+        }
+
+        let (column, row) = (cell_coordinate(offset.x), cell_coordinate(offset.y));
+        (column < self.columns && row < self.rows).then_some(row * self.columns + column)
+    }
+
+    /// Converts a number of points in a cell to a density
+    fn density(&self, count: usize) -> T {
+        let count: T = nalgebra::convert(count as f64);
+        count / (self.cell_size * self.cell_size)
+    }
+
+    /// Samples the density at `point`
+    pub(crate) fn density_at(&self, point: &Point<T>) -> T {
+        self.cell(point)
+            .map(|cell| self.density(self.counts[cell]))
+            .unwrap_or(T::zero())
+    }
+
+    /// Samples the density at `point`, by scanning the whole point cloud instead of using the precomputed counts
+    ///
+    /// This gives identical results to [`Self::density_at`], and only exists for benchmarking.
+    pub(crate) fn density_at_uncached(&self, points: &[Point<T>], point: &Point<T>) -> T {
+        let Some(cell) = self.cell(point) else {
+            return T::zero();
+        };
+
+        let count = points.iter().filter(|p| self.cell(p) == Some(cell)).count();
+        self.density(count)
+    }
+
+    /// The average density across the whole grid
+    pub(crate) fn mean_density(&self) -> T {
+        let cells: T = nalgebra::convert((self.columns * self.rows).max(1) as f64);
+        self.density(self.total) / cells
+    }
+}
+
+/// Converts a (non-negative) offset measured in cells to the index of the cell containing it
+pub(crate) fn cell_coordinate<T: HullScalar>(offset: T) -> usize {
+    nalgebra::try_convert::<T, f64>(offset.floor()).unwrap_or(0.) as usize
+}
@@ -74,3 +74,19 @@ impl<T: HullScalar> Edge<T> {
         (e1, e2)
     }
 }
+
+/// Breaks ties between edges by the coordinates of their endpoints, and then by their indices
+///
+/// This is a total order, so with it, the order that edges come out of a heap never depends on the order that they went in,
+/// or on the heap's internals.
+/// Only the last tie-break depends on the order of the point cloud, and it is only reached by edges between repeat points.
+pub(crate) fn cmp_edge_ties<T: HullScalar>(a: &Edge<T>, b: &Edge<T>) -> Ordering {
+    cmp_points(&a.point_i, &b.point_i)
+        .then_with(|| cmp_points(&a.point_j, &b.point_j))
+        .then_with(|| (a.i, a.j).cmp(&(b.i, b.j)))
+}
+
+/// Orders points lexicographically, by their x coordinate and then by their y coordinate
+pub(crate) fn cmp_points<T: HullScalar>(a: &Point<T>, b: &Point<T>) -> Ordering {
+    a.x.total_cmp(&b.x).then_with(|| a.y.total_cmp(&b.y))
+}
@@ -0,0 +1,173 @@
+use nalgebra::{Point2 as Point, Scalar};
+
+use crate::{HullScalar, metrics::distance_to_segment};
+
+/// A concave hull, bundled with a bounding volume hierarchy over its edges for fast queries
+///
+/// Edge `k` of the hull goes from vertex `k` to vertex `k + 1` (wrapping around at the end).
+/// The hierarchy is built once, in O(h log h) for a hull with h vertices,
+/// after which nearest-edge and containment queries take O(log h) on typical hulls.
+#[derive(Debug, Clone)]
+pub struct IndexedHull<T: Scalar> {
+    pub(crate) hull: Vec<(usize, Point<T>)>,
+    /// The nodes of the hierarchy, with the root first (if there is one)
+    pub(crate) nodes: Vec<Node<T>>,
+}
+
+/// A node in the bounding volume hierarchy of an [`IndexedHull`]
+#[derive(Debug, Clone)]
+pub(crate) struct Node<T: Scalar> {
+    mins: Point<T>,
+    maxs: Point<T>,
+    contents: Contents,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Contents {
+    /// A single edge, by its position in the hull
+    Leaf(usize),
+    /// Two child nodes, by their positions in the node list
+    Branch(usize, usize),
+}
+
+impl<T: HullScalar> IndexedHull<T> {
+    /// Bundles a hull with a newly built hierarchy over its edges
+    pub(crate) fn build(hull: Vec<(usize, Point<T>)>) -> Self {
+        let mut indexed = Self {
+            hull,
+            nodes: Vec::new(),
+        };
+        if indexed.hull.len() >= 2 {
+            let mut edges: Vec<usize> = (0..indexed.hull.len()).collect();
+            indexed.build_node(&mut edges);
+        }
+
+        indexed
+    }
+
+    /// The endpoints of edge `k`
+    fn edge(&self, k: usize) -> (Point<T>, Point<T>) {
+        (self.hull[k].1, self.hull[(k + 1) % self.hull.len()].1)
+    }
+
+    /// Builds a node over a non-empty set of edges, returning its position in the node list
+    fn build_node(&mut self, edges: &mut [usize]) -> usize {
+        let (first_i, first_j) = self.edge(edges[0]);
+        let (mut mins, mut maxs) = (first_i.inf(&first_j), first_i.sup(&first_j));
+        for &k in &edges[1..] {
+            let (i, j) = self.edge(k);
+            mins = mins.inf(&i.inf(&j));
+            maxs = maxs.sup(&i.sup(&j));
+        }
+
+        let position = self.nodes.len();
+        self.nodes.push(Node {
+            mins,
+            maxs,
+            contents: Contents::Leaf(edges[0]),
+        });
+        if edges.len() == 1 {
+            return position;
+        }
+
+        // Split the edges in half along the longer side of the box, by their midpoints
+        let extents = maxs - mins;
+        let axis = if extents.x >= extents.y { 0 } else { 1 };
+        let middle = edges.len() / 2;
+        let midpoint = |k: usize| {
+            let (i, j) = self.edge(k);
+            i[axis] + j[axis]
+        };
+        edges.select_nth_unstable_by(middle, |&a, &b| midpoint(a).total_cmp(&midpoint(b)));
+
+        let (left, right) = edges.split_at_mut(middle);
+        let left = self.build_node(left);
+        let right = self.build_node(right);
+        self.nodes[position].contents = Contents::Branch(left, right);
+
+        position
+    }
+
+    /// Finds the edge nearest to `point`, returning its position in the hull and its distance from `point`
+    ///
+    /// Returns `None` for hulls with fewer than two vertices, which have no edges.
+    pub(crate) fn query_nearest_edge(&self, point: &Point<T>) -> Option<(usize, T)> {
+        if self.nodes.is_empty() {
+            return None;
+        }
+
+        let mut best: Option<(usize, T)> = None;
+        let mut stack = Vec::from([0]);
+        while let Some(position) = stack.pop() {
+            let node = &self.nodes[position];
+            if best.is_some_and(|(_, distance)| box_distance(node, point) >= distance) {
+                // Nothing in this node can beat what we already have
+                continue;
+            }
+
+            match node.contents {
+                Contents::Leaf(k) => {
+                    let (i, j) = self.edge(k);
+                    let distance = distance_to_segment(point, i, j);
+                    if best.is_none_or(|(_, best_distance)| distance < best_distance) {
+                        best = Some((k, distance));
+                    }
+                }
+                Contents::Branch(left, right) => {
+                    // Visit the closer child first, so that the other is more likely to be skipped
+                    let (near, far) = if box_distance(&self.nodes[left], point)
+                        <= box_distance(&self.nodes[right], point)
+                    {
+                        (left, right)
+                    } else {
+                        (right, left)
+                    };
+                    stack.push(far);
+                    stack.push(near);
+                }
+            }
+        }
+
+        best
+    }
+
+    /// Checks if `point` is inside of the hull, using the even-odd rule
+    ///
+    /// A ray is cast from `point` in the positive x direction, and only nodes which it passes through are visited.
+    /// Points exactly on the boundary may be reported either way.
+    pub(crate) fn query_contains(&self, point: &Point<T>) -> bool {
+        if self.hull.len() < 3 {
+            return false;
+        }
+
+        let mut inside = false;
+        let mut stack = Vec::from([0]);
+        while let Some(position) = stack.pop() {
+            let node = &self.nodes[position];
+            if node.maxs.x < point.x || point.y < node.mins.y || point.y > node.maxs.y {
+                continue;
+            }
+
+            match node.contents {
+                Contents::Leaf(k) => {
+                    let (a, b) = self.edge(k);
+                    if (a.y <= point.y) != (b.y <= point.y) {
+                        let crossing = a.x + (point.y - a.y) * (b.x - a.x) / (b.y - a.y);
+                        if crossing > point.x {
+                            inside = !inside;
+                        }
+                    }
+                }
+                Contents::Branch(left, right) => stack.extend([left, right]),
+            }
+        }
+
+        inside
+    }
+}
+
+/// The distance from `point` to the nearest point in a node's bounding box, which is zero inside of it
+fn box_distance<T: HullScalar>(node: &Node<T>, point: &Point<T>) -> T {
+    let nearest = point.sup(&node.mins).inf(&node.maxs);
+    (point - nearest).norm()
+}
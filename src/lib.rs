@@ -26,205 +26,5781 @@
 //! - `f32` (default feature): Enables `f32`-precision versions of the concave hull computation and relevant re-exports (an `f32`-precision point, for example)
 //! - `f64`: Enables `f64`-precision versions of the concave hull computation and relevant re-exports (an `f64`-precision point, for example)
 //!
-//! If neither feature is enabled, then this crate has no public exports.
+//! If neither feature is enabled, then this crate has no public exports, other than the [`exact`] module,
+//! which works on any signed scalar (including integers), and so doesn't need either.
 //! Enabling both simultaneously is supported (cargo features must be purely additive), with relevant functions being exported under the `f32` or `f64` submodules, respectively.
 //!
-//! This crate has one additional feature, `benches`, which is only used for benchmarks.
+//! This crate also has some optional features:
+//! - `cabi`: Exposes C ABI bindings for the `f32` concave hull, in the [`cabi`] module (implies `f32`)
+//! - `csv`: Enables reading point clouds from CSV files, with `read_points_csv`
+//! - `delaunay`: Enables `chi_shape`, an alternative concave hull algorithm based on Delaunay triangulation, and `hull_skeleton`
+//! - `geo-types`: Enables conversions between hulls and [`geo-types`](https://crates.io/crates/geo-types) polygons, without pulling in the rest of `geo`
+//! - `half`: Lets `concave_hull_generic` take `half::f16` points, which are widened for the math (see `AsPoint2` for the precision caveats)
+//! - `image`: Enables `concave_hull_from_mask`, which hulls the foreground pixels of an [`image`](https://crates.io/crates/image) mask
+//! - `rayon`: Parallelizes computations which produce several independent hulls, such as `concave_hull_sweep`, and the intersection checks in very large hulls
+//! - `shapefile`: Enables writing hulls to polygon shapefiles, with `write_hull_shapefile`
+//!
+//! Finally, this crate has one additional feature, `benches`, which is only used for benchmarks.
 //! End users of this library should never enable it.
 
-#![warn(missing_docs)]
-#![feature(trait_alias)]
+#![warn(missing_docs)]
+#![feature(trait_alias)]
+
+mod builder;
+#[cfg(feature = "cabi")]
+pub mod cabi;
+mod checked;
+#[cfg(feature = "delaunay")]
+mod chi_shape;
+mod clip;
+mod concave;
+#[cfg(feature = "delaunay")]
+mod delaunay;
+mod density;
+mod edge;
+pub mod exact;
+mod indexed;
+mod metrics;
+mod morph;
+mod offset;
+mod outliers;
+mod pinch;
+#[cfg(feature = "image")]
+mod pixel_mask;
+mod pockets;
+mod point;
+#[cfg(feature = "csv")]
+mod points_csv;
+mod queue;
+mod raster;
+mod refine;
+mod result;
+mod rng;
+mod segment_intersect;
+#[cfg(feature = "shapefile")]
+mod shapefile;
+mod simplify;
+#[cfg(feature = "delaunay")]
+mod skeleton;
+mod sliding;
+mod snap;
+mod spacing;
+mod stability;
+mod triangulate;
+mod validate;
+mod wrap;
+
+#[cfg(feature = "benches")]
+pub use concave::sort_hull;
+#[cfg(feature = "benches")]
+pub use edge::Edge;
+#[cfg(feature = "benches")]
+pub use segment_intersect::edges_intersect;
+
+mod scalar {
+    use nalgebra::{RealField, Scalar};
+    use num_traits::float::TotalOrder;
+
+    /// Trait bound for scalars we can work with
+    ///
+    /// In practice, I think this is just the float types
+    ///
+    /// This is nominally public (but unnameable outside of the crate), so that it can bound the impls of
+    /// generic types which are exported through the precision modules.
+    pub trait HullScalar = Scalar + RealField + Copy + TotalOrder;
+
+    /// Checks if a scalar is NaN, since [`RealField`] doesn't have a method for it
+    #[allow(clippy::eq_op)]
+    pub(crate) fn is_nan<T: HullScalar>(x: T) -> bool {
+        // NaN is the only value which isn't equal to itself
+        x != x
+    }
+}
+
+#[cfg(not(feature = "benches"))]
+pub(crate) use scalar::HullScalar;
+#[cfg(feature = "benches")]
+pub use scalar::HullScalar;
+pub(crate) use scalar::is_nan;
+
+/// Spatial points and concave hull generation for [`prim@f32`] precision
+#[cfg(feature = "f32")]
+pub mod f32 {
+    /// [`parry2d`]'s point type, which [`concave_hull`] uses internally for all its math
+    ///
+    /// This is also the point type used in function signatures and returns
+    pub type Point = parry2d::math::Point<f32>;
+    /// [`parry2d`]'s vector type, used for directions in function returns
+    pub type Vector = parry2d::math::Vector<f32>;
+    pub use parry2d;
+
+    pub use crate::point::AsPoint2;
+
+    use crate::{
+        clip,
+        concave::{
+            concave_hull_anytime_inner, concave_hull_channeled_inner, concave_hull_inner,
+            concave_hull_sweep_inner, concave_hull_unsorted_inner, concave_hull_warm_inner,
+            open_path_inner, parameter_grid_search_inner,
+        },
+        metrics, outliers, pockets, raster,
+        refine::remove_micro_reflex,
+        rng::sample_indices,
+        snap::snap_points,
+        spacing::mean_nearest_neighbor_distance,
+        triangulate::ear_clip,
+        validate::is_empty_hull,
+        wrap::unwrap_points,
+    };
+
+    /// Gets the indices of the convex hull from parry, handling point clouds which are too small for parry
+    fn convex_hull_idx(points: &[Point]) -> Vec<usize> {
+        if points.len() <= 1 {
+            // Degenerate case with too few points to make a convex hull
+            // The original point (or nothing) is trivially its own hull
+            (0..points.len()).collect()
+        } else if points.iter().all(|p| *p == points[0]) {
+            // Parry can't find a hull when every point is the same, so the first one stands in for the rest
+            Vec::from([0])
+        } else {
+            parry2d::transformation::convex_hull_idx(points)
+        }
+    }
+
+    /// Configurable concave hull computation, for when [`concave_hull`] isn't flexible enough
+    ///
+    /// Options are set with chained method calls, then the hull is computed with [`ConcaveHullBuilder::compute`].
+    pub type ConcaveHullBuilder = crate::builder::ConcaveHullBuilder<f32>;
+
+    /// A concave hull, along with some extra information about how it was computed
+    pub type HullResult = crate::result::HullResult<f32>;
+
+    /// A single vertex of a concave hull, with both its index in the point cloud and its position in the hull
+    pub type HullVertex = crate::result::HullVertex<f32>;
+
+    /// A concave hull, triangulated and ready to render, as returned by [`hull_mesh`]
+    pub type HullMesh = crate::result::HullMesh<f32>;
+
+    /// Periodic coordinates, for point clouds which wrap around, used with [`ConcaveHullBuilder::wrap`]
+    pub type Wrap = crate::wrap::Wrap<f32>;
+
+    /// How edge lengths are checked against the concavity parameter, used with [`ConcaveHullBuilder::length_check`]
+    pub type LengthCheck = crate::builder::LengthCheck<f32>;
+
+    /// An edge of a hull, going from point `i` to point `j`
+    ///
+    /// See [`concave_hull_unsorted`].
+    pub type Edge = crate::edge::Edge<f32>;
+
+    pub use crate::{checked::ConcaveHullError, result::EdgeKind, validate::HullValidationError};
+
+    #[cfg(feature = "csv")]
+    pub use crate::points_csv::{CsvOptions, CsvPointError};
+
+    impl ConcaveHullBuilder {
+        /// Computes the concave hull of the provided point cloud, using the configured options
+        ///
+        /// See [`concave_hull`] for details on the inputs and outputs.
+        pub fn compute(&self, points: &[Point]) -> Vec<(usize, Point)> {
+            self.compute_with_stats(points).hull
+        }
+
+        /// Computes the concave hull of the provided point cloud, using the configured options,
+        /// along with some extra information about the computation
+        pub fn compute_with_stats(&self, points: &[Point]) -> HullResult {
+            if let Some(wrap) = &self.wrap {
+                // Make the cloud contiguous, then hull it as usual, keeping the moved coordinates
+                let unwrapped = unwrap_points(points, wrap);
+                let mut options = self.clone();
+                options.wrap = None;
+
+                return options.compute_with_stats(&unwrapped);
+            }
+
+            if let Some(grid) = self.snap {
+                // Compute the hull of the snapped points, then map it back to the originals
+                let (snapped, originals) = snap_points(points, grid);
+                let mut options = self.clone();
+                options.snap = None;
+                if let Some(weights) = &self.weights {
+                    options.weights = Some(originals.iter().map(|&id| weights[id]).collect());
+                }
+
+                let mut result = options.compute_with_stats(&snapped);
+                for (id, p) in &mut result.hull {
+                    *id = originals[*id];
+                    *p = points[*id];
+                }
+
+                return result;
+            }
+
+            if let Some(ratio) = self.max_edge_ratio {
+                // Repeatedly hull the points which are left, dropping any outliers, until there are none
+                let mut options = self.clone();
+                options.max_edge_ratio = None;
+                let mut kept: Vec<usize> = (0..points.len()).collect();
+
+                loop {
+                    let subset: Vec<Point> = kept.iter().map(|&id| points[id]).collect();
+                    if let Some(weights) = &self.weights {
+                        options.weights = Some(kept.iter().map(|&id| weights[id]).collect());
+                    }
+
+                    let mut result = options.compute_with_stats(&subset);
+                    let outliers = outliers::spikes(&result.hull, ratio);
+                    if outliers.is_empty() {
+                        for (id, _) in &mut result.hull {
+                            *id = kept[*id];
+                        }
+
+                        return result;
+                    }
+
+                    // Outliers are positions in the subset, so remove them from the back to keep the rest valid
+                    let mut outliers = outliers;
+                    outliers.sort_unstable();
+                    for position in outliers.into_iter().rev() {
+                        kept.remove(position);
+                    }
+                }
+            }
+
+            let convex = convex_hull_idx(points);
+
+            concave_hull_inner(points, self, &convex)
+        }
+
+        /// Computes the concave hull of the provided point cloud, and returns how many pushes and pops it made on the edge queue
+        ///
+        /// This skips wrapping, snapping, and outlier removal, so it's only meaningful without those options.
+        ///
+        /// This only exists for benchmarking.
+        #[cfg(feature = "benches")]
+        pub fn count_queue_operations(&self, points: &[Point]) -> usize {
+            let convex = convex_hull_idx(points);
+
+            crate::concave::count_queue_operations(points, self, &convex)
+        }
+
+        /// Computes the concave hull of the provided point cloud, using the configured options,
+        /// after checking that the point cloud is sensible
+        ///
+        /// The bounding box of the point cloud must have nonzero width and height,
+        /// and must not be more stretched out than [`ConcaveHullBuilder::max_aspect_ratio`] (if set).
+        /// Otherwise, this returns [`ConcaveHullError::DegenerateExtent`].
+        ///
+        /// If the last point is identical to the first, it is dropped before computing the hull.
+        /// This happens when a closed ring (like the rings in a shapefile) is passed in as the point cloud,
+        /// and the repeated point would otherwise be an exact duplicate, which the hull computation doesn't expect.
+        /// Since only the last point is dropped, the indices in the hull still refer to the original slice.
+        ///
+        /// Points with a NaN or infinite coordinate can't be placed on a hull, so they are left out,
+        /// and the indices in the hull still refer to the original slice.
+        /// If that leaves no points, the hull is empty, and if it leaves a single point (possibly repeated),
+        /// the hull is that point, with the index of its first occurrence.
+        /// These are returned as-is, without checking the bounding box.
+        ///
+        /// See [`concave_hull`] for details on the inputs and outputs.
+        pub fn try_compute(
+            &self,
+            points: &[Point],
+        ) -> Result<Vec<(usize, Point)>, ConcaveHullError> {
+            let points = match points {
+                [first, .., last] if first == last => &points[..points.len() - 1],
+                _ => points,
+            };
+
+            let finite = crate::checked::finite_indices(points);
+            match finite.as_slice() {
+                [] => return Ok(Vec::new()),
+                [first, rest @ ..] if rest.iter().all(|&id| points[id] == points[*first]) => {
+                    return Ok(vec![(*first, points[*first])]);
+                }
+                _ => {}
+            }
+
+            if finite.len() == points.len() {
+                crate::checked::check_extent(points, self.max_aspect_ratio)?;
+                return Ok(self.compute(points));
+            }
+
+            let gathered: Vec<Point> = finite.iter().map(|&id| points[id]).collect();
+            crate::checked::check_extent(&gathered, self.max_aspect_ratio)?;
+
+            Ok(self
+                .compute(&gathered)
+                .into_iter()
+                .map(|(position, p)| (finite[position], p))
+                .collect())
+        }
+    }
+
+    /// A precomputed estimate of the local point density across a point cloud, used for adaptive concavity
+    pub type DensityField = crate::density::DensityField<f32>;
+
+    impl DensityField {
+        /// Builds the density field for the provided point cloud
+        ///
+        /// This is O(n) in the number of points.
+        pub fn new(points: &[Point]) -> Self {
+            Self::build(points)
+        }
+
+        /// Samples the density (in points per unit area) at the provided point
+        ///
+        /// This is O(1).
+        pub fn sample(&self, point: &Point) -> f32 {
+            self.density_at(point)
+        }
+    }
+
+    /// A concave hull, bundled with a spatial index over its edges for fast nearest-edge and containment queries
+    pub type IndexedHull = crate::indexed::IndexedHull<f32>;
+
+    impl IndexedHull {
+        /// Builds the index for a hull returned by [`concave_hull`] (or any other closed ring of points)
+        ///
+        /// This is O(h log h) in the number of hull points.
+        pub fn new(hull: Vec<(usize, Point)>) -> Self {
+            Self::build(hull)
+        }
+
+        /// The hull points, in the same format as returned by [`concave_hull`]
+        pub fn hull(&self) -> &[(usize, Point)] {
+            &self.hull
+        }
+
+        /// Finds the hull edge nearest to `point`, returning its position in the hull and its distance from `point`
+        ///
+        /// The edge at position `k` goes from hull point `k` to hull point `k + 1`, wrapping around at the end.
+        /// Hulls with fewer than two points have no edges, so this returns `None` for them.
+        ///
+        /// This is O(log h) in the number of hull points, for typical hulls.
+        pub fn nearest_edge(&self, point: &Point) -> Option<(usize, f32)> {
+            self.query_nearest_edge(point)
+        }
+
+        /// Checks if `point` is inside of the hull
+        ///
+        /// Points exactly on the boundary may be reported either way, and hulls with fewer than three points (including empty hulls) contain nothing.
+        ///
+        /// This is O(log h) in the number of hull points, for typical hulls.
+        pub fn contains(&self, point: &Point) -> bool {
+            self.query_contains(point)
+        }
+    }
+
+    /// A concave hull over a sliding window of the most recent points from a stream
+    pub type SlidingHull = crate::sliding::SlidingHull<f32>;
+
+    impl SlidingHull {
+        /// Returns the concave hull of the points currently in the window
+        ///
+        /// The returned indices are positions in the window, where `0` is the oldest point (see [`SlidingHull::points`]).
+        /// The hull is only recomputed if the window has changed since the last call.
+        pub fn hull(&mut self) -> &[(usize, Point)] {
+            let concavity = self.concavity;
+            let window = self.window.make_contiguous();
+
+            self.hull
+                .get_or_insert_with(|| concave_hull(window, concavity))
+        }
+    }
+
+    /// Computes the concave hull of the provided point cloud, using the provided concavity parameter
+    ///
+    /// Inputs:
+    /// - `points`: A list of points, making up the point cloud to generate the concave hull for.
+    ///   It is assumed that this list contains no repeat points.
+    /// - `concavity`: A parameter determining how concave the hull should be.
+    ///   See the crate-level docs for guidance on picking the concavity parameter.
+    ///
+    /// The returned [`Vec`] contains a tuple of:
+    /// - The index of the hull point in the original slice
+    /// - The value of the point in the original slice
+    ///
+    /// The points are returned in counter-clockwise order.
+    ///
+    /// Point clouds which don't enclose any area can't have a concave hull, so their convex hull is returned instead:
+    /// - An empty point cloud has an empty hull.
+    /// - If every point is the same, the hull is just the first point.
+    /// - If every point is on a single line, the hull is the two points at the ends of the line.
+    ///
+    /// Any other cloud of three points is its own hull, as a triangle.
+    /// These hulls always start from their lexicographically smallest point (by x, then by y),
+    /// and triangles are counter-clockwise like every other hull.
+    ///
+    /// The hull is deterministic, but symmetric clouds (like regular grids) have ties between equally long edges,
+    /// which are broken in a way that depends on the order of `points`.
+    /// For a hull which only depends on the positions of the points, use [`ConcaveHullBuilder::stable_ordering`].
+    ///
+    /// Every time an edge is split, every point in the cloud is checked as a candidate to split it with,
+    /// so runtime grows with the number of points times the number of hull points.
+    /// This makes runtime, rather than memory or indexing, the practical limit on input size:
+    /// clouds with millions of points are fine for large concavity values,
+    /// but small concavity values (which produce large hulls) get slow long before then.
+    pub fn concave_hull(points: &[Point], concavity: f32) -> Vec<(usize, Point)> {
+        ConcaveHullBuilder::new(concavity).compute(points)
+    }
+
+    /// Computes the edges of the concave hull of the provided point cloud, without ordering them
+    ///
+    /// Ordering the edges of the hull end to end is the last step of [`concave_hull`],
+    /// which takes a linear pass over the edges, and a [`HashMap`](std::collections::HashMap) from each point to its outgoing edge.
+    /// This skips that step, saving that time and memory, for callers which don't need the hull in order, or would rather order it themselves.
+    ///
+    /// See [`concave_hull`] for details on the inputs.
+    /// Each returned [`Edge`] goes from point `i` to point `j`, with the hull on its left (since the hull is counter-clockwise).
+    /// The order of the edges in the returned [`Vec`] is unspecified.
+    /// Currently, they are in the order that the gift opening process finalized them, but this may change in the future.
+    pub fn concave_hull_unsorted(points: &[Point], concavity: f32) -> Vec<Edge> {
+        let convex = convex_hull_idx(points);
+
+        concave_hull_unsorted_inner(points, &ConcaveHullBuilder::new(concavity), &convex)
+    }
+
+    /// Computes the concave hull of the provided point cloud, with a concavity relative to the spacing between points
+    ///
+    /// The mean distance from each point to its nearest neighbour is measured,
+    /// and the concavity parameter passed to [`concave_hull`] is `factor` times that distance.
+    /// Since edges longer than the concavity parameter are split, `factor` is roughly how many point spacings
+    /// an edge of the hull may span before it gets bent in towards the point cloud.
+    /// This ties the tightness of the hull to the point spacing, making `factor` scale invariant:
+    /// scaling a point cloud up or down doesn't change its hull.
+    ///
+    /// Like the concavity parameter, `factor` ranges from zero to positive infinity.
+    /// `2` is usually a good starting point (on the question mark dataset, this is roughly equivalent to a concavity of `40`).
+    ///
+    /// Measuring the point spacing is O(n) for reasonably even point clouds.
+    pub fn concave_hull_nn_relative(points: &[Point], factor: f32) -> Vec<(usize, Point)> {
+        concave_hull(points, factor * mean_nearest_neighbor_distance(points))
+    }
+
+    /// Computes the concave hull of the provided point cloud, where heavier points are more likely to end up on the hull
+    ///
+    /// Whenever an edge is split, [`concave_hull`] picks the point which minimizes the larger of the two angles
+    /// between the edge and the new edges to that point.
+    /// Here, each point's angle is divided by its weight before comparing, so the point which minimizes
+    /// `max(angle_1, angle_2) / weight` is picked instead.
+    /// This means that a point with a weight of `2` wins against any point with a weight of `1`
+    /// whose angle is less than twice as large.
+    /// With every weight set to `1`, this is identical to [`concave_hull`].
+    ///
+    /// `weights` must contain one positive, finite weight for each point, in the same order as `points`.
+    /// Panics if the lengths don't match, or if any weight isn't positive and finite.
+    ///
+    /// See [`concave_hull`] for details on the other inputs, and the output.
+    pub fn concave_hull_weighted(
+        points: &[Point],
+        weights: &[f32],
+        concavity: f32,
+    ) -> Vec<(usize, Point)> {
+        assert_eq!(
+            points.len(),
+            weights.len(),
+            "There must be exactly one weight per point"
+        );
+        assert!(
+            weights
+                .iter()
+                .all(|&weight| weight.is_finite() && weight > 0.),
+            "Weights must be positive and finite"
+        );
+
+        let mut builder = ConcaveHullBuilder::new(concavity);
+        builder.weights = Some(weights.to_vec());
+        builder.compute(points)
+    }
+
+    /// Computes the concave hull of the provided point cloud, for any point type implementing [`AsPoint2`]
+    ///
+    /// This is identical to [`concave_hull`], except that the points are converted to [`Point`]s first.
+    /// [`AsPoint2`] is implemented for [`Point`], `(f32, f32)` tuples, and `[f32; 2]` arrays,
+    /// and you can implement it for your own point types.
+    ///
+    /// The returned indices are indices into `points`, but the returned points are converted [`Point`]s.
+    pub fn concave_hull_generic<P: AsPoint2<f32>>(
+        points: &[P],
+        concavity: f32,
+    ) -> Vec<(usize, Point)> {
+        let points: Vec<Point> = points
+            .iter()
+            .map(|p| {
+                let (x, y) = p.xy();
+                Point::new(x, y)
+            })
+            .collect();
+
+        concave_hull(&points, concavity)
+    }
+
+    /// Computes progressively refined versions of the concave hull of the provided point cloud
+    ///
+    /// The returned iterator yields a complete, well-formed hull (in the same format as [`concave_hull`])
+    /// at every stage of the computation: first the convex hull, then a new hull after every edge split.
+    /// The final hull is identical to the one returned by [`concave_hull`].
+    /// This is useful for interactive applications, which can render a rough hull immediately,
+    /// then refine it (or animate the refinement), and stop early if the hull is good enough.
+    ///
+    /// Work is done lazily, as the iterator is advanced.
+    /// Each intermediate hull costs an extra O(h) to assemble, where h is the number of hull points at that stage.
+    ///
+    /// See [`concave_hull`] for details on the inputs.
+    pub fn concave_hull_anytime(
+        points: &[Point],
+        concavity: f32,
+    ) -> impl Iterator<Item = Vec<(usize, Point)>> {
+        let convex = convex_hull_idx(points);
+
+        concave_hull_anytime_inner(points, ConcaveHullBuilder::new(concavity), &convex)
+    }
+
+    /// Computes the concave hull of the provided point cloud, sending each edge over a channel as soon as it's finalized
+    ///
+    /// The work is done on the calling thread, and this returns once the hull is complete,
+    /// but a receiver on another thread (like a UI thread) can pick up each edge as it comes in, without polling.
+    /// `sender` is dropped at the end, which closes the channel if it's the only sender.
+    /// If the receiver hangs up early, nobody is left to use the hull, so the computation stops early too.
+    ///
+    /// Edges are sent in the order they're finalized, and the set of edges is the same as from [`concave_hull_unsorted`].
+    /// Each edge goes from point `i` to point `j`, with the hull on its left.
+    /// To reassemble the hull, start from the last edge sent, and follow each edge to the one starting where it ends:
+    /// this gives exactly the hull returned by [`concave_hull`].
+    ///
+    /// See [`concave_hull`] for details on the other inputs.
+    pub fn concave_hull_channeled(
+        points: &[Point],
+        concavity: f32,
+        sender: std::sync::mpsc::Sender<Edge>,
+    ) {
+        let convex = convex_hull_idx(points);
+
+        concave_hull_channeled_inner(points, ConcaveHullBuilder::new(concavity), &convex, &sender);
+    }
+
+    /// Computes the concave hull of the provided point cloud, with a concavity relative to the longest convex hull edge
+    ///
+    /// The concavity parameter passed to [`concave_hull`] is `fraction` times the length of the longest edge
+    /// of the point cloud's convex hull, which ties the parameter to the scale of the data automatically.
+    /// Since only edges longer than the concavity parameter are split:
+    /// - A `fraction` of `1` or more leaves (roughly) the convex hull, since no edge is long enough to split
+    /// - Smaller fractions dig further into the point cloud
+    /// - `0` produces a maximally crinkly shape, just like a concavity of `0`
+    ///
+    /// For example, on the question mark dataset, a `fraction` of `0.1` is roughly equivalent to a concavity of `40`.
+    ///
+    /// See [`concave_hull`] for details on the other inputs, and the output.
+    pub fn concave_hull_relative_to_longest(
+        points: &[Point],
+        fraction: f32,
+    ) -> Vec<(usize, Point)> {
+        let convex = convex_hull_idx(points);
+        let ring: Vec<Point> = convex.iter().map(|&id| points[id]).collect();
+        let concavity = fraction * metrics::longest_edge(&ring);
+
+        concave_hull_inner(points, &ConcaveHullBuilder::new(concavity), &convex).hull
+    }
+
+    /// Computes the concave hull of the provided point cloud, after checking that the point cloud is sensible
+    ///
+    /// This catches common input mistakes early, rather than returning a meaningless hull.
+    /// See [`ConcaveHullBuilder::try_compute`] for the checks, and [`concave_hull`] for details on the inputs and outputs.
+    pub fn try_concave_hull(
+        points: &[Point],
+        concavity: f32,
+    ) -> Result<Vec<(usize, Point)>, ConcaveHullError> {
+        ConcaveHullBuilder::new(concavity).try_compute(points)
+    }
+
+    /// Computes a concave hull of the provided point cloud using the chi-shape algorithm, instead of gift opening
+    ///
+    /// The chi-shape starts from the Delaunay triangulation of the point cloud, whose boundary is the convex hull,
+    /// then repeatedly removes the longest edge on the boundary (along with the triangle behind it),
+    /// until every boundary edge is at most `length_threshold` long.
+    /// Edges are only removed if doing so keeps the boundary a simple polygon which contains every point.
+    /// This tends to produce smoother hulls than gift opening on evenly spaced clouds,
+    /// so it can be worth comparing the two on your data.
+    ///
+    /// `length_threshold` plays the same role as the concavity parameter of [`concave_hull`]:
+    /// `0` digs in as far as possible, and `f32::INFINITY` gives the convex hull.
+    /// The output is in the same format as [`concave_hull`], so the two are interchangeable.
+    /// If the points have no triangulation (because there are fewer than three, or they're all on a line),
+    /// then this returns the convex hull instead.
+    ///
+    /// The triangulation is O(n²), which dominates the runtime.
+    /// To compute chi-shapes with several thresholds, use a [`DelaunayHuller`] to only triangulate once.
+    #[cfg(feature = "delaunay")]
+    pub fn chi_shape(points: &[Point], length_threshold: f32) -> Vec<(usize, Point)> {
+        DelaunayHuller::new(points).hull(length_threshold)
+    }
+
+    /// A Delaunay triangulation of a point cloud, for computing chi-shapes with several thresholds
+    ///
+    /// See [`chi_shape`] for details on the hulls.
+    #[cfg(feature = "delaunay")]
+    pub type DelaunayHuller<'a> = crate::chi_shape::DelaunayHuller<'a, f32>;
+
+    #[cfg(feature = "delaunay")]
+    impl<'a> DelaunayHuller<'a> {
+        /// Triangulates the provided point cloud, ready for computing hulls
+        ///
+        /// This is O(n²), and is the expensive part of computing a chi-shape.
+        pub fn new(points: &'a [Point]) -> Self {
+            Self::triangulate(points)
+        }
+
+        /// Computes the chi-shape with the provided threshold, reusing the triangulation
+        ///
+        /// This gives exactly the same hull as [`chi_shape`], but only takes O(t log t) in the number of triangles.
+        pub fn hull(&self, length_threshold: f32) -> Vec<(usize, Point)> {
+            let hull = self.carve(length_threshold);
+            if hull.is_empty() {
+                return concave_hull(self.points, f32::INFINITY);
+            }
+
+            hull
+        }
+    }
+
+    /// Computes the concave hull of the provided point cloud, along with some extra information about the computation
+    ///
+    /// See [`concave_hull`] for details on the inputs, and [`HullResult`] for details on the output.
+    pub fn concave_hull_with_stats(points: &[Point], concavity: f32) -> HullResult {
+        ConcaveHullBuilder::new(concavity).compute_with_stats(points)
+    }
+
+    /// Computes the concave hull of the provided point cloud for each of the provided concavity values
+    ///
+    /// This is equivalent to calling [`concave_hull`] once per concavity value,
+    /// but the convex hull is only computed once, and shared between all of the computations.
+    /// This is useful when sweeping over concavity values to tune the parameter.
+    ///
+    /// With the `rayon` feature enabled, the hulls are computed in parallel.
+    ///
+    /// The returned hulls are in the same order as `concavities`.
+    pub fn concave_hull_sweep(points: &[Point], concavities: &[f32]) -> Vec<Vec<(usize, Point)>> {
+        let convex = convex_hull_idx(points);
+
+        concave_hull_sweep_inner(points, concavities, &convex)
+    }
+
+    /// Searches a grid of relative concavities and neighbour counts for the hull which scores highest on `objective`
+    ///
+    /// Each of the `factors` is a concavity relative to the point spacing, in the same way as [`concave_hull_nn_relative`],
+    /// except that the spacing is the mean distance from each point to its `k`th nearest neighbour, for each `k` in `knns`.
+    /// Larger neighbour counts measure the spacing over a wider area, which is less sensitive to clumps of points.
+    /// With `k = 1`, the hulls are exactly those from [`concave_hull_nn_relative`].
+    ///
+    /// Every combination of factor and neighbour count is hulled and scored by `objective` (higher is better),
+    /// such as the [`iou_with`] a known boundary, or a tradeoff between area and perimeter.
+    /// Returns the best factor and neighbour count, or `None` if `factors` or `knns` is empty, or every score is NaN.
+    /// NaN scores never win, and ties go to the first combination, going through `knns` for each factor in turn.
+    /// The spacing is measured once for each neighbour count, and the convex hull is shared between every hull.
+    ///
+    /// With the `rayon` feature enabled, the hulls are computed in parallel.
+    pub fn parameter_grid_search(
+        points: &[Point],
+        factors: &[f32],
+        knns: &[usize],
+        objective: impl Fn(&HullResult) -> f32 + Sync,
+    ) -> Option<(f32, usize)> {
+        let convex = convex_hull_idx(points);
+
+        parameter_grid_search_inner(points, &convex, factors, knns, objective)
+    }
+
+    /// Measures how deeply each point on the concave hull protrudes into the point cloud
+    ///
+    /// The concave hull can be decomposed into pockets: regions carved out of the convex hull, each of which
+    /// is bounded by a straight convex hull edge on one side, and a chain of concave hull points on the other.
+    /// The depth of a concave hull point is its perpendicular distance from the convex hull edge spanning its pocket.
+    /// Points which are on the convex hull have a depth of zero.
+    ///
+    /// See [`concave_hull`] for details on the inputs.
+    /// Returns the original index of each hull point, paired with its depth, in the same order as [`concave_hull`].
+    pub fn protrusion_depths(points: &[Point], concavity: f32) -> Vec<(usize, f32)> {
+        let convex = convex_hull_idx(points);
+        let hull = concave_hull_inner(points, &ConcaveHullBuilder::new(concavity), &convex).hull;
+
+        pockets::protrusion_depths(&hull, &convex)
+    }
+
+    /// Splits the concave hull into the pockets carved out of the convex hull
+    ///
+    /// Each pocket is the region between a chain of concave hull points and the straight convex hull edge spanning them
+    /// (see [`protrusion_depths`] for more on pockets).
+    /// Pockets are returned as their boundary points, in the same order as in the hull:
+    /// the chain starts and ends on convex hull points, and the pocket is closed by the edge from its last point back to its first.
+    /// Pockets are listed in the order that they appear around the hull.
+    ///
+    /// See [`concave_hull`] for details on the inputs.
+    /// Convex hulls have no pockets, so this returns nothing for them.
+    pub fn concave_hull_pockets(points: &[Point], concavity: f32) -> Vec<Vec<(usize, Point)>> {
+        let convex = convex_hull_idx(points);
+        let hull = concave_hull_inner(points, &ConcaveHullBuilder::new(concavity), &convex).hull;
+
+        pockets::find_pockets(&hull, &convex)
+            .into_iter()
+            .map(|pocket| pocket.into_iter().map(|pos| hull[pos]).collect())
+            .collect()
+    }
+
+    /// Splits an existing concave hull of `points` into the pockets carved out of the convex hull
+    ///
+    /// This gives the same pockets as [`concave_hull_pockets`], without computing the hull again.
+    /// `hull` must be a hull of `points`, such as from [`concave_hull`] or [`ConcaveHullBuilder::compute`].
+    pub fn hull_pockets(points: &[Point], hull: &[(usize, Point)]) -> Vec<Vec<(usize, Point)>> {
+        let convex = convex_hull_idx(points);
+
+        pockets::find_pockets(hull, &convex)
+            .into_iter()
+            .map(|pocket| pocket.into_iter().map(|pos| hull[pos]).collect())
+            .collect()
+    }
+
+    /// Computes the concave hull of the provided point cloud, labelling each edge by whether it was ever split
+    ///
+    /// Each vertex is paired with the [`EdgeKind`] of the edge leaving it, towards the next vertex in the hull
+    /// (and from the last vertex back to the first).
+    /// [`EdgeKind::Convex`] edges are straight chords of the convex hull which survived gift opening unsplit,
+    /// while [`EdgeKind::Concave`] edges bend in towards the point cloud.
+    ///
+    /// See [`concave_hull`] for details on the inputs.
+    /// The vertices are the same, and in the same order, as in [`concave_hull`].
+    pub fn concave_hull_classified(
+        points: &[Point],
+        concavity: f32,
+    ) -> Vec<(usize, Point, EdgeKind)> {
+        let convex = convex_hull_idx(points);
+        let hull = concave_hull_inner(points, &ConcaveHullBuilder::new(concavity), &convex).hull;
+
+        pockets::classify_edges(&hull, &convex)
+    }
+
+    /// Computes the concave hull of the provided point cloud, flagging which vertices were already on the convex hull
+    ///
+    /// Gift opening starts from the convex hull, and every split inserts a new vertex, without ever removing one.
+    /// Each vertex is paired with `true` if it's one of the original convex hull vertices, and `false` if it was inserted by a split.
+    /// With a concavity of infinity, nothing is split, so every flag is `true`.
+    ///
+    /// See [`concave_hull`] for details on the inputs.
+    /// The vertices are the same, and in the same order, as in [`concave_hull`].
+    pub fn concave_hull_provenance(points: &[Point], concavity: f32) -> Vec<(usize, Point, bool)> {
+        let convex = convex_hull_idx(points);
+        let hull = concave_hull_inner(points, &ConcaveHullBuilder::new(concavity), &convex).hull;
+        let convex: std::collections::HashSet<usize> = convex.into_iter().collect();
+
+        hull.into_iter()
+            .map(|(id, p)| (id, p, convex.contains(&id)))
+            .collect()
+    }
+
+    /// Triangulates the pockets carved out of the convex hull by the concave hull
+    ///
+    /// Each pocket is the region between a chain of concave hull points and the straight convex hull edge spanning them
+    /// (see [`protrusion_depths`] for more on pockets).
+    /// Together, the pockets make up exactly the area that the concavity parameter carved away from the convex hull,
+    /// so filling these triangles is a convenient way to visualize it.
+    ///
+    /// See [`concave_hull`] for details on the inputs.
+    /// Returns the triangles from every pocket, with each vertex as its index and value in the original slice.
+    /// Each triangle is wound counter-clockwise.
+    pub fn pocket_triangles(points: &[Point], concavity: f32) -> Vec<[(usize, Point); 3]> {
+        let convex = convex_hull_idx(points);
+        let hull = concave_hull_inner(points, &ConcaveHullBuilder::new(concavity), &convex).hull;
+
+        pockets::pocket_triangles(&hull, &convex)
+    }
+
+    /// Finds the convex hull edges which were split while opening up the concave hull
+    ///
+    /// Every convex hull point stays on the concave hull, so a convex hull edge was split (at least once)
+    /// exactly when its two ends are no longer neighbours on the concave hull.
+    /// Each of these edges spans one pocket (see [`concave_hull_pockets`]), and the rest survived unsplit.
+    ///
+    /// See [`concave_hull`] for details on the inputs.
+    /// Returns the original indices of the start and end of each split edge, counter-clockwise around the hull,
+    /// in the order that they appear around the hull.
+    pub fn replaced_convex_edges(points: &[Point], concavity: f32) -> Vec<(usize, usize)> {
+        let convex = convex_hull_idx(points);
+        let hull = concave_hull_inner(points, &ConcaveHullBuilder::new(concavity), &convex).hull;
+
+        pockets::find_pockets(&hull, &convex)
+            .into_iter()
+            .map(|pocket| (hull[pocket[0]].0, hull[pocket[pocket.len() - 1]].0))
+            .collect()
+    }
+
+    /// Interpolates between the concave hulls for two concavities, for animating between them
+    ///
+    /// `t` goes from `0`, which gives the hull for `concavity_a`, to `1`, which gives the hull for `concavity_b`.
+    /// Points on the two hulls are matched up by how far around their hull they are, as a fraction of its perimeter,
+    /// measured from the start of the first hull (and from the nearest point to it on the second).
+    /// Both hulls are resampled at the position of every vertex of either hull, so they have the same number of points,
+    /// and each pair is interpolated linearly.
+    /// Every vertex is kept, so the ends have exactly the shapes of the two hulls, with extra points along their edges.
+    ///
+    /// See [`concave_hull`] for details on the other inputs.
+    /// The in-between points usually aren't in the point cloud, so only their values are returned.
+    /// Intermediate shapes can self-intersect where the hulls differ a lot, so this is only meant for presentation.
+    pub fn morph_hulls(points: &[Point], concavity_a: f32, concavity_b: f32, t: f32) -> Vec<Point> {
+        let ring = |concavity| -> Vec<Point> {
+            concave_hull(points, concavity)
+                .into_iter()
+                .map(|(_, p)| p)
+                .collect()
+        };
+
+        crate::morph::morph_rings(&ring(concavity_a), &ring(concavity_b), t)
+    }
+
+    /// Computes the regions which are inside the concave hull for one concavity, but not the other
+    ///
+    /// This is the symmetric difference between the two hulls, which is useful for visualizing the effect of changing concavity.
+    /// Both hulls share the same convex hull points, so they can only differ inside of pockets
+    /// (see [`protrusion_depths`] for more on pockets).
+    ///
+    /// See [`concave_hull`] for details on the inputs.
+    /// Returns each region as a ring of points, which is implicitly closed.
+    /// Regions are returned in no particular winding order, and either hull may be the larger in any one region.
+    /// In the rare case that the two hulls cross each other between shared points,
+    /// a region may be self-intersecting, in which case it should be filled with the even-odd rule.
+    pub fn hull_difference(
+        points: &[Point],
+        concavity_a: f32,
+        concavity_b: f32,
+    ) -> Vec<Vec<Point>> {
+        let convex = convex_hull_idx(points);
+        let a = concave_hull_inner(points, &ConcaveHullBuilder::new(concavity_a), &convex).hull;
+        let b = concave_hull_inner(points, &ConcaveHullBuilder::new(concavity_b), &convex).hull;
+
+        pockets::hull_difference(&a, &b, &convex)
+    }
+
+    /// Computes a concave path between two points in the provided point cloud
+    ///
+    /// This is the open counterpart to [`concave_hull`], for point clouds which represent an open curve
+    /// (like a segment of coastline), rather than an enclosed region.
+    /// Instead of starting from the convex hull, the gift opening process starts from a single straight edge
+    /// between the two endpoints, and repeatedly splits it using the same rules as [`concave_hull`].
+    ///
+    /// Inputs:
+    /// - `points`: A list of points, making up the point cloud to generate the path for.
+    ///   It is assumed that this list contains no repeat points.
+    /// - `concavity`: A parameter determining how closely the path should follow the point cloud.
+    ///   This behaves the same way as the concavity parameter of [`concave_hull`].
+    /// - `start`, `end`: The indices of the points at either end of the path.
+    ///   These must be distinct, valid indices into `points`, or there is no path and this returns `None`.
+    ///
+    /// Since the path starts as a single edge, it can bend towards points on either side of that edge.
+    /// In practice, it bends towards whichever side has points closest to the straight edge.
+    ///
+    /// The returned [`Vec`] contains the index and value of each point on the path, in order from `start` to `end`.
+    pub fn concave_path(
+        points: &[Point],
+        concavity: f32,
+        start: usize,
+        end: usize,
+    ) -> Option<Vec<(usize, Point)>> {
+        open_path_inner(points, &ConcaveHullBuilder::new(concavity), start, end)
+    }
+
+    /// Computes the concave hull of the provided point cloud, with the output split into flat buffers
+    ///
+    /// This is identical to [`concave_hull`], but returns the hull in a layout that is friendlier to
+    /// C interop and vertex buffers, rather than as a [`Vec`] of tuples.
+    ///
+    /// Returns a tuple of:
+    /// - The index of each hull point in the original slice, in winding order.
+    /// - The coordinates of each hull point, interleaved as `[x0, y0, x1, y1, ...]`, in the same order.
+    ///
+    /// The coordinate buffer is always exactly twice as long as the index buffer,
+    /// and the coordinates of the `k`th hull point are at positions `2 * k` and `2 * k + 1`.
+    pub fn concave_hull_flat(points: &[Point], concavity: f32) -> (Vec<usize>, Vec<f32>) {
+        let hull = concave_hull(points, concavity);
+
+        let indices = hull.iter().map(|(id, _)| *id).collect();
+        let coordinates = hull.iter().flat_map(|(_, p)| [p.x, p.y]).collect();
+        (indices, coordinates)
+    }
+
+    /// Computes the concave hull of a point cloud which has changed slightly, starting from its previous hull
+    ///
+    /// This is meant for clouds which change a little at a time, such as tracked objects from one video frame to the next.
+    /// `previous` is the indices of the previous hull (as returned by [`concave_hull`]), into the current `points`.
+    /// Rather than starting from the convex hull, gift opening starts from the edges of the previous hull,
+    /// and only splits those which are now too long, so the work already done isn't repeated.
+    ///
+    /// The previous hull is only used if it's still a valid hull of the current points (see [`validate_hull`]):
+    /// every index must be in range, its edges must not cross, and every point must still be inside of it.
+    /// Otherwise (say, because a point moved outside of it), the hull is computed from scratch with [`concave_hull`].
+    ///
+    /// This is an approximation: edges are only ever split, never merged, so the hull can dig in where points have moved,
+    /// but never fills back out where a full recompute would.
+    /// Over many updates, the hull drifts towards being more concave than [`concave_hull`] would make it,
+    /// so it's best to recompute it from scratch every so often (say, every few dozen frames).
+    pub fn concave_hull_warm(
+        previous: &[usize],
+        points: &[Point],
+        concavity: f32,
+    ) -> Vec<(usize, Point)> {
+        concave_hull_warm_inner(points, &ConcaveHullBuilder::new(concavity), previous)
+            .unwrap_or_else(|| concave_hull(points, concavity))
+    }
+
+    /// Computes the concave hull of the provided point cloud, wound so that `inside_hint` is on its inside
+    ///
+    /// Some systems define a ring's orientation by which side of it is the inside, rather than by a fixed winding.
+    /// The hull is returned so that walking along it, `inside_hint` is always on the left,
+    /// which (for the simple polygons returned by [`concave_hull`]) is the usual counter-clockwise winding.
+    /// Any point strictly inside of the hull works as a hint, such as a point known to be in the middle of the shape.
+    ///
+    /// If `inside_hint` is outside of the hull, or exactly on its boundary, this returns [`ConcaveHullError::HintOutside`].
+    /// Hulls with fewer than three points don't have an inside, so they always return that error too.
+    ///
+    /// See [`concave_hull`] for details on the other inputs, and on the hull itself.
+    pub fn concave_hull_oriented(
+        points: &[Point],
+        concavity: f32,
+        inside_hint: Point,
+    ) -> Result<Vec<(usize, Point)>, ConcaveHullError> {
+        crate::checked::orient_around(concave_hull(points, concavity), &inside_hint)
+    }
+
+    /// Computes the concave hull of the provided point cloud, bundled with a spatial index over its edges
+    ///
+    /// Building the index takes O(h log h) in the number of hull points, after which [`IndexedHull::nearest_edge`]
+    /// and [`IndexedHull::contains`] take O(log h), rather than O(h) each.
+    /// This is worthwhile when the hull will be queried many times.
+    ///
+    /// See [`concave_hull`] for details on the inputs, and on the hull itself.
+    pub fn concave_hull_indexed(points: &[Point], concavity: f32) -> IndexedHull {
+        IndexedHull::new(concave_hull(points, concavity))
+    }
+
+    /// Computes the concave hull of a subset of the provided point cloud, returning indices into the full point cloud
+    ///
+    /// `subset` lists the indices of the points to hull, such as every point with a particular label.
+    /// Only the points in the subset are gathered up for the computation, so the rest of the cloud is never copied,
+    /// and the returned indices refer to `points` (not to positions in `subset`), so no remapping is needed.
+    ///
+    /// Otherwise, this is identical to [`concave_hull`] on the points in the subset.
+    /// Repeated indices in `subset` are treated as repeat points.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any index in `subset` is out of bounds for `points`.
+    pub fn concave_hull_subset(
+        points: &[Point],
+        subset: &[usize],
+        concavity: f32,
+    ) -> Vec<(usize, Point)> {
+        let gathered: Vec<Point> = subset.iter().map(|&id| points[id]).collect();
+
+        concave_hull(&gathered, concavity)
+            .into_iter()
+            .map(|(position, p)| (subset[position], p))
+            .collect()
+    }
+
+    /// Computes a separate concave hull for each label, for point clouds made up of several labelled groups
+    ///
+    /// `labels` gives the label of each point, in the same order as `points`.
+    /// Points are grouped by their label (regardless of where they are), and each group is hulled with [`concave_hull_subset`],
+    /// so the returned indices refer to `points`.
+    /// Groups don't affect each other, so their hulls can overlap.
+    ///
+    /// Panics if the lengths of `points` and `labels` don't match.
+    /// See [`concave_hull`] for details on the other inputs, and on each hull.
+    pub fn concave_hull_by_label(
+        points: &[Point],
+        labels: &[u32],
+        concavity: f32,
+    ) -> std::collections::HashMap<u32, Vec<(usize, Point)>> {
+        concave_hull_by_label_min_size(points, labels, concavity, 0)
+    }
+
+    /// Computes a separate concave hull for each label with at least `min_cluster_size` points, treating the rest as noise
+    ///
+    /// This is [`concave_hull_by_label`], except that labels with fewer than `min_cluster_size` points are left out,
+    /// like noise points in DBSCAN, rather than each getting a degenerate hull of a point or two.
+    /// A `min_cluster_size` of zero (or one) keeps every label.
+    ///
+    /// Panics if the lengths of `points` and `labels` don't match.
+    /// See [`concave_hull`] for details on the other inputs, and on each hull.
+    pub fn concave_hull_by_label_min_size(
+        points: &[Point],
+        labels: &[u32],
+        concavity: f32,
+        min_cluster_size: usize,
+    ) -> std::collections::HashMap<u32, Vec<(usize, Point)>> {
+        assert_eq!(
+            points.len(),
+            labels.len(),
+            "There must be exactly one label per point"
+        );
+
+        let mut groups: std::collections::HashMap<u32, Vec<usize>> =
+            std::collections::HashMap::new();
+        for (id, &label) in labels.iter().enumerate() {
+            groups.entry(label).or_default().push(id);
+        }
+
+        groups
+            .into_iter()
+            .filter(|(_, group)| group.len() >= min_cluster_size)
+            .map(|(label, group)| (label, concave_hull_subset(points, &group, concavity)))
+            .collect()
+    }
+
+    /// Computes the concave hull of a random sample of the provided point cloud, returning indices into the full point cloud
+    ///
+    /// Each point is kept with probability `sample_fraction`, and the hull of the kept points is computed with [`concave_hull_subset`].
+    /// The sample only depends on `seed` and the number of points, so the same inputs always give the same hull.
+    ///
+    /// This is much faster than hulling the whole cloud on large inputs, which makes it useful for quick previews,
+    /// but the result is only approximate.
+    /// Points which weren't sampled may be left outside of the hull, and fine details are lost as the sample gets sparser,
+    /// so the hull can be off by around the spacing between sampled points, or the concavity, whichever is larger.
+    /// Sparser samples also dig in less, since the concavity is measured against a sparser cloud.
+    ///
+    /// A `sample_fraction` of one (or more) keeps every point, and is identical to [`concave_hull`].
+    /// See [`concave_hull`] for details on the other inputs.
+    pub fn concave_hull_sampled(
+        points: &[Point],
+        concavity: f32,
+        sample_fraction: f64,
+        seed: u64,
+    ) -> Vec<(usize, Point)> {
+        let subset = sample_indices(points.len(), sample_fraction, seed);
+
+        concave_hull_subset(points, &subset, concavity)
+    }
+
+    /// Merges concave hulls computed on separate parts of a point cloud into a single hull of the whole cloud
+    ///
+    /// Each entry of `hulls` lists the indices (into `points`) of the vertices of one part's hull,
+    /// such as the indices from [`concave_hull_subset`] on each partition of the cloud.
+    /// The parts may overlap, and the same index may appear in several hulls.
+    /// The union of all the hull vertices is treated as a reduced point cloud, and hulled again with [`concave_hull_subset`].
+    ///
+    /// This is much cheaper than hulling the whole cloud again, since only the boundary points of each part are considered,
+    /// but it is an approximation.
+    /// The convex hull is always exact, since every convex hull point is on the hull of its part,
+    /// but points which were inside of their own part's hull never make it onto the merged hull,
+    /// even if the full hull would have dug in to reach them (such as points near the seam between two parts).
+    /// The merged hull is exact when every point of the full hull is on the hull of some part,
+    /// which is more likely when the parts overlap.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any index in `hulls` is out of bounds for `points`.
+    pub fn merge_hulls(
+        points: &[Point],
+        hulls: &[Vec<usize>],
+        concavity: f32,
+    ) -> Vec<(usize, Point)> {
+        let mut candidates: Vec<usize> = hulls.iter().flatten().copied().collect();
+        candidates.sort_unstable();
+        candidates.dedup();
+
+        concave_hull_subset(points, &candidates, concavity)
+    }
+
+    /// Computes the concave hull of the combined vertices of several hulls, as an outer boundary around all of them
+    ///
+    /// Each entry of `hulls` lists the vertices of one hull, such as regional hulls computed separately.
+    /// Unlike [`merge_hulls`], the hulls don't need to come from a shared point cloud, so their vertices are taken by value.
+    /// Vertices that are in exactly the same place (such as a corner shared by two hulls) are only counted once.
+    ///
+    /// This only operates on the provided boundary points: whatever was inside of each hull is gone,
+    /// so the result can't dig in to where the original points were, only to other hulls' vertices.
+    /// It isn't the union of the hulls either, since the union has corners where the hulls' edges cross, which aren't vertices of any hull.
+    /// This makes it a cheap approximation, suited to aggregating hulls up a hierarchy of regions.
+    ///
+    /// See [`concave_hull`] for details on `concavity`.
+    /// The result is in the same order as [`concave_hull`], but only the values of its points are returned,
+    /// since there's no single point cloud for indices to refer to.
+    pub fn hull_of_hulls(hulls: &[Vec<Point>], concavity: f32) -> Vec<Point> {
+        let mut vertices: Vec<Point> = hulls.iter().flatten().copied().collect();
+        vertices.sort_unstable_by(|a, b| a.x.total_cmp(&b.x).then(a.y.total_cmp(&b.y)));
+        vertices.dedup();
+
+        concave_hull(&vertices, concavity)
+            .into_iter()
+            .map(|(_, p)| p)
+            .collect()
+    }
+
+    /// Finds the vertices where a hull touches itself, making it non-simple even though none of its edges cross
+    ///
+    /// A vertex is a pinch if another vertex is in exactly the same place (in which case both are returned),
+    /// or if it lies exactly on an edge which doesn't end at it.
+    /// Some consumers (such as strict GIS validators) reject polygons like this; see [`split_pinch_points`] to repair them.
+    ///
+    /// Hulls from [`concave_hull`] never pinch, since a split is rejected if its new edges so much as touch an existing edge,
+    /// but post-processing (or hulls from elsewhere) can introduce them.
+    ///
+    /// Returns the positions of the pinched vertices in `hull`, in increasing order.
+    /// This is O(h²) in the number of hull points.
+    pub fn find_pinch_points(hull: &[(usize, Point)]) -> Vec<usize> {
+        if is_empty_hull(hull) {
+            return Vec::new();
+        }
+
+        crate::pinch::find_pinches(hull)
+    }
+
+    /// Splits a hull into separate rings at every point where it touches itself, so that each ring is simple
+    ///
+    /// At a pinch between two vertices in the same place, the hull is cut into the loop between them and the rest.
+    /// A vertex touching the middle of another edge is first copied into that edge, then cut in the same way.
+    /// The pinched vertex ends up on each of the rings that meet there, with the same index.
+    /// See [`find_pinch_points`] for what counts as a pinch.
+    ///
+    /// Each ring winds in the same direction as `hull`, and the rings are returned in an unspecified order.
+    /// Hulls without any pinches are returned unchanged, as a single ring, and empty hulls have no rings at all.
+    pub fn split_pinch_points(hull: &[(usize, Point)]) -> Vec<Vec<(usize, Point)>> {
+        if is_empty_hull(hull) {
+            return Vec::new();
+        }
+
+        crate::pinch::split_pinches(hull.to_vec())
+    }
+
+    /// Simplifies a hull down to exactly `count` vertices (or leaves it alone if it already has no more than that)
+    ///
+    /// Vertices are dropped one at a time, least important first, in the style of Visvalingam–Whyatt.
+    /// A vertex's importance is the area of the triangle it makes with its two neighbours,
+    /// which is how much the hull's area changes when it's dropped, so the overall shape is kept as well as possible.
+    /// Unlike tolerance-based simplification, this gives a predictable number of vertices.
+    ///
+    /// The remaining vertices keep their order and indices.
+    /// Dropping vertices can make a hull self-intersect, especially for small counts, so check with [`validate_hull`] if that matters.
+    /// This is O(h²) in the number of hull points.
+    pub fn simplify_to_count(hull: &[(usize, Point)], count: usize) -> Vec<(usize, Point)> {
+        if is_empty_hull(hull) {
+            return Vec::new();
+        }
+
+        crate::simplify::simplify_to_count(hull, count)
+    }
+
+    /// Computes the concave hull of the provided point cloud, clipped so that it doesn't extend beyond `clip`
+    ///
+    /// This is useful when the hull has to stay inside of a known boundary, such as the border of a map.
+    /// The hull is computed as usual with [`concave_hull`], then clipped against `clip` with the Sutherland-Hodgman algorithm.
+    ///
+    /// `clip` should be a convex polygon, and may wind either way.
+    /// Simple (but concave) polygons are accepted, but are clipped against the full lines through each of their edges,
+    /// so more of the hull may be cut away than lies outside of them.
+    /// Where a concave hull is cut into several pieces, they are returned as a single ring, joined by zero-width slivers along `clip`.
+    ///
+    /// Clipping creates new vertices wherever the hull crosses `clip`, which aren't points in the point cloud,
+    /// so only the clipped points are returned, without any indices.
+    /// They are in the same (counter-clockwise) order as the hull.
+    /// If the hull and `clip` don't overlap, or either has fewer than three points, the result is empty.
+    pub fn concave_hull_clipped(points: &[Point], concavity: f32, clip: &[Point]) -> Vec<Point> {
+        let hull: Vec<Point> = concave_hull(points, concavity)
+            .into_iter()
+            .map(|(_, p)| p)
+            .collect();
+
+        clip::clip_ring(&hull, clip)
+    }
+
+    /// Computes the concave hull of the foreground pixels of a mask, such as the output of image segmentation
+    ///
+    /// Every pixel brighter than `threshold` is foreground, and contributes its center to the point cloud.
+    /// The hull is in pixel coordinates: pixel `(x, y)` covers the square from `(x, y)` to `(x + 1, y + 1)`, so its center is at `(x + 0.5, y + 0.5)`.
+    /// Like the image itself, the y axis points down, starting from the top row.
+    /// This flips the winding: the hull is counter-clockwise with the y axis pointing up (as in [`concave_hull`]),
+    /// so it runs clockwise when drawn over the image.
+    ///
+    /// Each vertex is paired with its index among the foreground pixels, counting in row-major order (along each row, from the top row down).
+    /// See [`concave_hull`] for details on `concavity`, and on the output.
+    #[cfg(feature = "image")]
+    pub fn concave_hull_from_mask(
+        mask: &image::GrayImage,
+        threshold: u8,
+        concavity: f32,
+    ) -> Vec<(usize, Point)> {
+        let points = crate::pixel_mask::foreground_points(mask, threshold);
+
+        concave_hull(&points, concavity)
+    }
+
+    /// Computes the concave hull of the provided point cloud, returning each vertex with both of its indices
+    ///
+    /// This is identical to [`concave_hull`], except that each vertex also records its position in the hull,
+    /// which saves enumerating the hull when cross-referencing it with the point cloud.
+    /// The `k`th vertex always has an `order` of `k`.
+    pub fn concave_hull_vertices(points: &[Point], concavity: f32) -> Vec<HullVertex> {
+        concave_hull(points, concavity)
+            .into_iter()
+            .enumerate()
+            .map(|(order, (original, point))| HullVertex {
+                original,
+                order,
+                point,
+            })
+            .collect()
+    }
+
+    /// Computes the concave hull of the provided point cloud, along with a triangulation of its interior
+    ///
+    /// This bundles together everything needed to draw the hull, filled and outlined, in one call.
+    /// The outline is exactly the hull returned by [`concave_hull`], and the triangles exactly cover it,
+    /// with `n - 2` triangles for a hull of `n` vertices.
+    /// Hulls with fewer than three vertices have no triangles.
+    ///
+    /// Triangulation is O(n³) in the number of hull vertices in the worst case, though it's usually much faster.
+    pub fn hull_mesh(points: &[Point], concavity: f32) -> HullMesh {
+        let (outline, vertices): (Vec<usize>, Vec<Point>) =
+            concave_hull(points, concavity).into_iter().unzip();
+        let triangles = ear_clip(&vertices);
+
+        HullMesh {
+            vertices,
+            triangles,
+            outline,
+        }
+    }
+
+    /// Approximates the medial axis of a hull, for analyzing its shape
+    ///
+    /// The medial axis (or skeleton) is the set of points inside the hull with more than one nearest point on its boundary,
+    /// which traces out a spine along the middle of each part of the hull, with branches into its corners.
+    ///
+    /// This is only an approximation: the boundary is resampled with at most `sampling` between samples,
+    /// and the skeleton is the part of the samples' Voronoi diagram inside the hull, found from their Delaunay triangulation.
+    /// It converges to the true medial axis as `sampling` shrinks, but the triangulation is O(m²) in the number of samples,
+    /// so `sampling` should only be small enough to resolve the narrowest parts of the hull.
+    /// Segments with an end outside of the hull, and the very tips of branches into corners, are pruned.
+    ///
+    /// Returns the skeleton as unordered line segments, sorted by their coordinates so that the output is deterministic.
+    /// Hulls with fewer than three points, or a `sampling` which isn't positive, have no skeleton.
+    #[cfg(feature = "delaunay")]
+    pub fn hull_skeleton(hull: &[(usize, Point)], sampling: f32) -> Vec<(Point, Point)> {
+        if is_empty_hull(hull) {
+            return Vec::new();
+        }
+
+        crate::skeleton::hull_skeleton(hull, sampling)
+    }
+
+    /// Thickens the boundary of a hull returned by [`concave_hull`] into a band `width` wide, for drawing it with emphasis
+    ///
+    /// Returns the outer and inner rings of the band, which are the hull offset outwards and inwards by half of `width`,
+    /// with mitred corners. The band is the region between them, centred on the hull's boundary.
+    /// Both rings have one point per hull vertex, in the same order as the hull.
+    ///
+    /// Where the hull is too narrow for the full offset (like a tight concavity, for the outer ring, or a thin spike, for the inner ring),
+    /// the offset is clamped at each corner so that the ring doesn't fold over itself, and the band is thinner there instead.
+    /// Very sharp corners have their mitres cut off at twice `width`.
+    /// Hulls with fewer than three points have no band.
+    pub fn hull_band(hull: &[(usize, Point)], width: f32) -> (Vec<Point>, Vec<Point>) {
+        if is_empty_hull(hull) || hull.len() < 3 {
+            return (Vec::new(), Vec::new());
+        }
+
+        let ring: Vec<Point> = hull.iter().map(|(_, p)| *p).collect();
+        (
+            crate::offset::offset_ring(&ring, width / 2.),
+            crate::offset::offset_ring(&ring, -width / 2.),
+        )
+    }
+
+    /// Checks that a hull (possibly from another source) is a valid concave hull of the provided point cloud
+    ///
+    /// A valid hull:
+    /// - Only has vertices which are points in the point cloud, each paired with its index, and each appearing once
+    /// - Is a simple polygon: none of its edges intersect, except for neighbouring edges at their shared endpoint
+    /// - Winds counter-clockwise
+    /// - Contains every point in the point cloud, either inside of it or on its boundary
+    ///
+    /// Every hull returned by [`concave_hull`] is valid (as long as the point cloud has no repeat points).
+    /// Hulls with fewer than three vertices are valid if every point in the cloud is one of their vertices, or lies on them.
+    /// If the hull is invalid, the first failed check is returned.
+    pub fn validate_hull(
+        points: &[Point],
+        hull: &[(usize, Point)],
+    ) -> Result<(), HullValidationError> {
+        crate::validate::validate_hull(points, hull)
+    }
+
+    /// Computes the perimeter of a hull returned by [`concave_hull`]
+    ///
+    /// The hull is treated as a closed ring, so the edge from the last point back to the first is included.
+    /// Empty hulls have a perimeter of zero.
+    pub fn perimeter(hull: &[(usize, Point)]) -> f32 {
+        if is_empty_hull(hull) {
+            return 0.;
+        }
+
+        metrics::perimeter(hull.iter().map(|(_, p)| *p))
+    }
+
+    /// Computes the area enclosed by a hull returned by [`concave_hull`]
+    ///
+    /// The hull is treated as a closed ring, so the edge from the last point back to the first is included.
+    /// Hulls with fewer than three points have an area of zero.
+    pub fn area(hull: &[(usize, Point)]) -> f32 {
+        if is_empty_hull(hull) {
+            return 0.;
+        }
+
+        metrics::signed_area(hull.iter().map(|(_, p)| *p)).abs()
+    }
+
+    /// Computes the concave hull of a set of points, along with its area and perimeter, accumulated in `f64`
+    ///
+    /// The hull itself is computed in `f32`, exactly as in [`concave_hull`].
+    /// Only the metrics are promoted, since summing many `f32` products loses a lot of precision
+    /// when the coordinates are large compared to the size of the hull.
+    ///
+    /// Returns the hull, its area, and its perimeter, in that order.
+    /// See [`area`] and [`perimeter`] for details on the metrics.
+    pub fn concave_hull_with_metrics_f64(
+        points: &[Point],
+        concavity: f32,
+    ) -> (Vec<(usize, Point)>, f64, f64) {
+        let hull = concave_hull(points, concavity);
+        let ring = || hull.iter().map(|(_, p)| p.cast::<f64>());
+        let area = metrics::signed_area(ring()).abs();
+        let perimeter = metrics::perimeter(ring());
+
+        (hull, area, perimeter)
+    }
+
+    /// Computes the area scooped out of the convex hull by the concave hull
+    ///
+    /// This is the area which is inside of the convex hull of the point cloud, but outside of its concave hull:
+    /// the total area of the notches dug into the shape.
+    /// It is zero when the concave hull is convex (such as when `concavity` is infinite),
+    /// and grows as the hull digs deeper into the point cloud.
+    ///
+    /// See [`concave_hull`] for details on the inputs.
+    pub fn concavity_area(points: &[Point], concavity: f32) -> f32 {
+        let convex = convex_hull_idx(points);
+        let hull = concave_hull_inner(points, &ConcaveHullBuilder::new(concavity), &convex).hull;
+        let convex_area = metrics::signed_area(convex.iter().map(|&id| points[id])).abs();
+
+        // The concave hull is inside of the convex hull, so this is never negative (barring floating point error)
+        (convex_area - area(&hull)).max(0.)
+    }
+
+    /// Finds the reflex corners of a hull returned by [`concave_hull`]
+    ///
+    /// Reflex corners have an interior angle greater than 180 degrees, and are the defining feature of concavity:
+    /// a hull is convex exactly when it has no reflex corners.
+    /// Straight corners (with an interior angle of exactly 180 degrees) are not reflex.
+    ///
+    /// Returns the original index of each reflex corner, in the same order as `hull`.
+    pub fn reflex_vertices(hull: &[(usize, Point)]) -> Vec<usize> {
+        if is_empty_hull(hull) {
+            return Vec::new();
+        }
+
+        metrics::reflex_vertices(hull)
+    }
+
+    /// Computes the concave hull of the provided point cloud, with the discrete curvature at each vertex
+    ///
+    /// The curvature is the signed turning angle at the vertex, in radians: the angle between the edge coming into it
+    /// and the edge going out of it, walking the hull in its usual counter-clockwise order.
+    /// It is zero where the hull runs straight, positive at convex corners, and negative at reflex corners,
+    /// and is at most π in either direction, so sharp corners have large magnitudes.
+    /// The curvatures of a hull always sum to 2π (barring floating point error).
+    ///
+    /// Otherwise, this is identical to [`concave_hull`].
+    /// Degenerate hulls, with fewer than three points, have zero curvature everywhere.
+    pub fn hull_with_curvature(points: &[Point], concavity: f32) -> Vec<(usize, Point, f32)> {
+        let hull = concave_hull(points, concavity);
+        let ring: Vec<Point> = hull.iter().map(|(_, p)| *p).collect();
+
+        hull.into_iter()
+            .zip(metrics::turning_angles(&ring))
+            .map(|((id, p), angle)| (id, p, angle))
+            .collect()
+    }
+
+    /// Removes tiny reflex corners from a hull returned by [`concave_hull`], which are usually numerical noise
+    ///
+    /// Nearly straight runs of points can zigzag very slightly, leaving reflex corners which only turn by a fraction of a degree.
+    /// These aren't real concave features, but they still count towards [`reflex_vertices`] and [`hull_with_curvature`].
+    /// This drops every reflex corner that turns by less than `max_micro_angle` (in radians), and joins its neighbours directly,
+    /// so that the sharper reflex corners of genuine notches are left alone.
+    /// Removing a corner changes the turns of its neighbours, so this repeats until there are no tiny reflex corners left.
+    ///
+    /// Removing a reflex corner only adds area to the hull, so every point in the cloud stays inside of it.
+    /// Convex corners are never removed, no matter how sharp or shallow.
+    /// This is the opposite of [`ConcaveHullBuilder::max_edge_ratio`], which removes sharp convex spikes out to outliers.
+    ///
+    /// Hulls with three or fewer points are returned as-is, and a triangle is always left behind.
+    pub fn repair_micro_reflex(
+        hull: &[(usize, Point)],
+        max_micro_angle: f32,
+    ) -> Vec<(usize, Point)> {
+        if is_empty_hull(hull) {
+            return Vec::new();
+        }
+
+        remove_micro_reflex(hull.to_vec(), max_micro_angle)
+    }
+
+    /// Measures how "crinkly" a hull returned by [`concave_hull`] is
+    ///
+    /// This is the [`perimeter`] of the hull, divided by the perimeter of its convex hull.
+    /// The convex hull is used for normalization (rather than, say, the bounding box),
+    /// since it is the shortest ring that encloses the point cloud.
+    /// This means that the result is exactly `1` for a convex hull, and grows as the hull digs deeper into the point cloud.
+    /// Because it is a ratio, it can be compared across datasets of different scales.
+    ///
+    /// Hulls with fewer than three points have a complexity of `1`.
+    pub fn boundary_complexity(hull: &[(usize, Point)]) -> f32 {
+        if is_empty_hull(hull) || hull.len() < 3 {
+            return 1.;
+        }
+
+        let points: Vec<Point> = hull.iter().map(|(_, p)| *p).collect();
+        let convex = parry2d::transformation::convex_hull(&points);
+
+        metrics::boundary_complexity(hull, &convex)
+    }
+
+    /// Finds the minimum-area oriented bounding box of a hull returned by [`concave_hull`]
+    ///
+    /// Unlike an axis-aligned bounding box, an oriented bounding box can rotate to fit the hull as tightly as possible,
+    /// which is useful for aligning shapes with each other, or for cheap culling.
+    /// The box only depends on the convex hull of the hull's points, so the concavity used to compute the hull doesn't matter.
+    ///
+    /// Returns the center of the box, its two axes, and its half-extent along each axis.
+    /// The axes are perpendicular unit vectors, and the first axis is parallel to one of the edges of the convex hull.
+    /// A single point gets a box with zero extent, aligned with the coordinate axes, and an empty hull gets that same box at the origin.
+    pub fn hull_obb(hull: &[(usize, Point)]) -> (Point, [Vector; 2], [f32; 2]) {
+        let points: Vec<Point> = hull.iter().map(|(_, p)| *p).collect();
+        if is_empty_hull(hull) || points.len() < 3 {
+            // Too few points for parry, but they are their own convex hull anyway
+            return metrics::oriented_bounding_box(&points);
+        }
+
+        let convex = parry2d::transformation::convex_hull(&points);
+        metrics::oriented_bounding_box(&convex)
+    }
+
+    /// Finds the smallest circle enclosing a hull returned by [`concave_hull`], for culling and broad-phase tests
+    ///
+    /// Like [`hull_obb`], the circle only depends on the convex hull of the hull's points,
+    /// so only the convex hull's vertices are considered, and the circle is found with Welzl's algorithm.
+    /// The circle passes through two or three of those vertices, and is O(h) to find on average.
+    ///
+    /// Returns the center and radius of the circle.
+    /// A single point gets a circle with zero radius, and an empty hull gets that same circle at the origin.
+    pub fn hull_bounding_circle(hull: &[(usize, Point)]) -> (Point, f32) {
+        let points: Vec<Point> = hull.iter().map(|(_, p)| *p).collect();
+        if is_empty_hull(hull) || points.len() < 3 {
+            // Too few points for parry, but they are their own convex hull anyway
+            return metrics::bounding_circle(&points);
+        }
+
+        let convex = parry2d::transformation::convex_hull(&points);
+        metrics::bounding_circle(&convex)
+    }
+
+    /// Finds the axis-aligned bounding box of a hull returned by [`concave_hull`]
+    ///
+    /// The hull always includes every point of the convex hull, so this is also the bounding box of the whole point cloud
+    /// (unless points were left out, such as by [`ConcaveHullBuilder::max_edge_ratio`]).
+    /// Hulls are usually much smaller than their point clouds, so this is cheaper than bounding the cloud directly.
+    ///
+    /// An empty hull gets parry's invalid bounding box, with `mins` above `maxs`, which contains nothing.
+    pub fn hull_aabb(hull: &[(usize, Point)]) -> parry2d::bounding_volume::Aabb {
+        if is_empty_hull(hull) {
+            return parry2d::bounding_volume::Aabb::new_invalid();
+        }
+
+        parry2d::bounding_volume::details::local_point_cloud_aabb(hull.iter().map(|(_, p)| p))
+    }
+
+    /// Rasterizes a hull returned by [`concave_hull`] into a filled, row-major boolean mask
+    ///
+    /// `transform` maps each hull point into mask coordinates, where the cell in column `x` and row `y`
+    /// covers the unit square from `(x, y)` to `(x + 1, y + 1)`, and is at index `y * width + x` in the mask.
+    /// For example, `|p| Point::new((p.x - min_x) / cell_size, (p.y - min_y) / cell_size)`
+    /// lays a grid of `cell_size` cells over the point cloud, starting from `(min_x, min_y)`.
+    ///
+    /// A cell is `true` if its center is inside of the hull.
+    /// Parts of the hull outside of the mask are ignored, and hulls with fewer than three points fill nothing.
+    /// Concave hulls are handled correctly, since each row is scan-filled between the points where the hull crosses it.
+    pub fn hull_mask(
+        hull: &[(usize, Point)],
+        width: usize,
+        height: usize,
+        transform: impl Fn(&Point) -> Point,
+    ) -> Vec<bool> {
+        if is_empty_hull(hull) {
+            return vec![false; width * height];
+        }
+
+        let ring: Vec<Point> = hull.iter().map(|(_, p)| transform(p)).collect();
+        raster::scan_fill(&ring, width, height)
+    }
+
+    /// Computes the intersection over union between a hull and a known true boundary, as a measure of the hull's accuracy
+    ///
+    /// This is the area covered by both the hull and `truth`, divided by the area covered by either of them.
+    /// It is one when the hull matches `truth` exactly, and zero when they don't overlap at all,
+    /// which makes it the standard way to score shape reconstruction.
+    /// If neither has any area (including when the hull is empty), it is zero.
+    ///
+    /// `truth` must be a simple polygon, and can wind either way.
+    /// The overlap is computed exactly, by splitting `truth` into triangles and clipping the hull against each of them,
+    /// which is O(h·t) in the number of hull and truth points.
+    pub fn iou_with(hull: &[(usize, Point)], truth: &[Point]) -> f32 {
+        if is_empty_hull(hull) {
+            return 0.;
+        }
+
+        let hull: Vec<Point> = hull.iter().map(|(_, p)| *p).collect();
+        clip::intersection_over_union(&hull, truth).0
+    }
+
+    /// Computes the area covered by exactly one of a hull and a known true boundary
+    ///
+    /// This is the area of the union minus the area of the overlap: the total area that the hull gets wrong,
+    /// either by covering area outside of `truth`, or by missing area inside of it.
+    /// See [`iou_with`] for the requirements on `truth`.
+    /// An empty hull misses all of `truth`, so its symmetric difference is the area of `truth`.
+    pub fn symmetric_difference_area(hull: &[(usize, Point)], truth: &[Point]) -> f32 {
+        if is_empty_hull(hull) {
+            return metrics::signed_area(truth.iter().copied()).abs();
+        }
+
+        let hull: Vec<Point> = hull.iter().map(|(_, p)| *p).collect();
+        clip::intersection_over_union(&hull, truth).1
+    }
+
+    /// Computes the symmetric Hausdorff distance between two hulls returned by [`concave_hull`]
+    ///
+    /// This is the furthest that any vertex of either hull is from the boundary of the other hull.
+    /// It is zero when the hulls trace the same shape, and grows with the largest local disagreement between them,
+    /// which makes it a good way to compare hulls computed with different options.
+    /// Hulls which start from different points, but are otherwise the same, have a distance of zero.
+    ///
+    /// If either hull is empty, the distance is zero.
+    pub fn hausdorff_distance(a: &[(usize, Point)], b: &[(usize, Point)]) -> f32 {
+        if is_empty_hull(a) || is_empty_hull(b) {
+            return 0.;
+        }
+
+        let a: Vec<Point> = a.iter().map(|(_, p)| *p).collect();
+        let b: Vec<Point> = b.iter().map(|(_, p)| *p).collect();
+
+        metrics::hausdorff_distance(&a, &b)
+    }
+
+    /// Measures how stable the concave hull of the provided point cloud is under small random perturbations
+    ///
+    /// Each of the `trials` offsets every point by a random amount, up to `jitter` along each axis,
+    /// and computes the concave hull of the perturbed points.
+    /// The result is the mean [`hausdorff_distance`] between those hulls and the hull of the unperturbed points.
+    /// Hulls are compared using the original positions of their points, so the jitter itself doesn't count,
+    /// only changes to which points end up on the hull.
+    /// A result of zero means that the jitter never changed the hull at all.
+    ///
+    /// The perturbations are generated from `seed`, so the same inputs always give the same result.
+    /// See [`concave_hull`] for details on the other inputs.
+    /// Returns zero if there are no trials.
+    pub fn hull_stability(
+        points: &[Point],
+        concavity: f32,
+        jitter: f32,
+        trials: usize,
+        seed: u64,
+    ) -> f32 {
+        crate::stability::hull_stability(points, jitter, trials, seed, |points| {
+            concave_hull(points, concavity)
+        })
+    }
+
+    /// Converts a hull returned by [`concave_hull`] to a [`geo_types::Polygon`]
+    ///
+    /// The hull becomes the exterior ring of the polygon (which `geo-types` closes by repeating the first point),
+    /// and the polygon has no interior rings.
+    /// The indices of the hull points are discarded, and an empty hull becomes an empty polygon.
+    #[cfg(feature = "geo-types")]
+    pub fn hull_to_polygon(hull: &[(usize, Point)]) -> geo_types::Polygon<f32> {
+        if is_empty_hull(hull) {
+            return geo_types::Polygon::new(geo_types::LineString::new(Vec::new()), Vec::new());
+        }
+
+        let exterior: Vec<geo_types::Coord<f32>> = hull
+            .iter()
+            .map(|(_, p)| geo_types::coord! { x: p.x, y: p.y })
+            .collect();
+
+        geo_types::Polygon::new(exterior.into(), Vec::new())
+    }
+
+    /// Converts the exterior ring of a [`geo_types::Polygon`] to a list of points
+    ///
+    /// This is the inverse of [`hull_to_polygon`]: the closing point is dropped,
+    /// so the points are in the same format as the points of a hull returned by [`concave_hull`].
+    /// Interior rings are ignored.
+    #[cfg(feature = "geo-types")]
+    pub fn polygon_to_points(polygon: &geo_types::Polygon<f32>) -> Vec<Point> {
+        let exterior = polygon.exterior();
+        let mut points: Vec<Point> = exterior.coords().map(|c| Point::new(c.x, c.y)).collect();
+        if exterior.is_closed() {
+            points.pop();
+        }
+
+        points
+    }
+
+    /// Writes a hull returned by [`concave_hull`] to a polygon shapefile, for use in GIS tools
+    ///
+    /// Three files are written next to `path`: the shapes (`.shp`), their index (`.shx`), and the attribute table (`.dbf`).
+    /// The extension of `path` is replaced for each file, so passing either `hull.shp` or `hull` writes all three.
+    ///
+    /// The hull is written as a single polygon, closed by repeating its first point at the end.
+    /// Shapefiles wind exterior rings clockwise (the opposite of GeoJSON, and of the hulls returned by [`concave_hull`]),
+    /// so the ring is reversed as needed.
+    /// The attribute table has a single numeric `ID` field, which is zero for the hull.
+    ///
+    /// An empty hull is written as a single null shape, which GIS tools read as a record without any geometry.
+    /// Hulls with one or two points aren't polygons, so they are rejected with [`std::io::ErrorKind::InvalidInput`].
+    #[cfg(feature = "shapefile")]
+    pub fn write_hull_shapefile(
+        path: impl AsRef<std::path::Path>,
+        hull: &[(usize, Point)],
+    ) -> std::io::Result<()> {
+        crate::shapefile::write_hull_shapefile(path.as_ref(), hull)
+    }
+
+    /// Lazily reads a point cloud from CSV data
+    ///
+    /// Records are read and parsed one at a time, so very large files can be streamed
+    /// (or filtered, or stopped early) without holding the whole file in memory.
+    /// Each item is either a point, or the reason that record could not be read.
+    /// Reading continues after a bad record, so collecting into a [`Result`] stops at the first error,
+    /// while filtering out the errors skips bad records.
+    ///
+    /// See [`CsvOptions`] for the supported layouts.
+    #[cfg(feature = "csv")]
+    pub fn read_points_csv<R: std::io::Read>(
+        reader: R,
+        options: &CsvOptions,
+    ) -> impl Iterator<Item = Result<Point, CsvPointError>> + use<R> {
+        crate::points_csv::read_points_csv(reader, options)
+    }
+}
+
+/// Spatial points and concave hull generation for [`prim@f64`] precision
+#[cfg(feature = "f64")]
+pub mod f64 {
+    /// [`parry2d`]'s point type, which [`concave_hull`] uses internally for all its math
+    ///
+    /// This is also the point type used in function signatures and returns
+    pub type Point = parry2d::math::Point<f64>;
+    /// [`parry2d`]'s vector type, used for directions in function returns
+    pub type Vector = parry2d::math::Vector<f64>;
+    pub use parry2d_f64 as parry2d;
+
+    pub use crate::point::AsPoint2;
+
+    use crate::{
+        clip,
+        concave::{
+            concave_hull_anytime_inner, concave_hull_channeled_inner, concave_hull_inner,
+            concave_hull_sweep_inner, concave_hull_unsorted_inner, concave_hull_warm_inner,
+            open_path_inner, parameter_grid_search_inner,
+        },
+        metrics, outliers, pockets, raster,
+        refine::remove_micro_reflex,
+        rng::sample_indices,
+        snap::snap_points,
+        spacing::mean_nearest_neighbor_distance,
+        triangulate::ear_clip,
+        validate::is_empty_hull,
+        wrap::unwrap_points,
+    };
+
+    /// Gets the indices of the convex hull from parry, handling point clouds which are too small for parry
+    fn convex_hull_idx(points: &[Point]) -> Vec<usize> {
+        if points.len() <= 1 {
+            // Degenerate case with too few points to make a convex hull
+            // The original point (or nothing) is trivially its own hull
+            (0..points.len()).collect()
+        } else if points.iter().all(|p| *p == points[0]) {
+            // Parry can't find a hull when every point is the same, so the first one stands in for the rest
+            Vec::from([0])
+        } else {
+            parry2d::transformation::convex_hull_idx(points)
+        }
+    }
+
+    /// Configurable concave hull computation, for when [`concave_hull`] isn't flexible enough
+    ///
+    /// Options are set with chained method calls, then the hull is computed with [`ConcaveHullBuilder::compute`].
+    pub type ConcaveHullBuilder = crate::builder::ConcaveHullBuilder<f64>;
+
+    /// A concave hull, along with some extra information about how it was computed
+    pub type HullResult = crate::result::HullResult<f64>;
+
+    /// A single vertex of a concave hull, with both its index in the point cloud and its position in the hull
+    pub type HullVertex = crate::result::HullVertex<f64>;
+
+    /// A concave hull, triangulated and ready to render, as returned by [`hull_mesh`]
+    pub type HullMesh = crate::result::HullMesh<f64>;
+
+    /// Periodic coordinates, for point clouds which wrap around, used with [`ConcaveHullBuilder::wrap`]
+    pub type Wrap = crate::wrap::Wrap<f64>;
+
+    /// How edge lengths are checked against the concavity parameter, used with [`ConcaveHullBuilder::length_check`]
+    pub type LengthCheck = crate::builder::LengthCheck<f64>;
+
+    /// An edge of a hull, going from point `i` to point `j`
+    ///
+    /// See [`concave_hull_unsorted`].
+    pub type Edge = crate::edge::Edge<f64>;
+
+    pub use crate::{checked::ConcaveHullError, result::EdgeKind, validate::HullValidationError};
+
+    #[cfg(feature = "csv")]
+    pub use crate::points_csv::{CsvOptions, CsvPointError};
+
+    impl ConcaveHullBuilder {
+        /// Computes the concave hull of the provided point cloud, using the configured options
+        ///
+        /// See [`concave_hull`] for details on the inputs and outputs.
+        pub fn compute(&self, points: &[Point]) -> Vec<(usize, Point)> {
+            self.compute_with_stats(points).hull
+        }
+
+        /// Computes the concave hull of the provided point cloud, using the configured options,
+        /// along with some extra information about the computation
+        pub fn compute_with_stats(&self, points: &[Point]) -> HullResult {
+            if let Some(wrap) = &self.wrap {
+                // Make the cloud contiguous, then hull it as usual, keeping the moved coordinates
+                let unwrapped = unwrap_points(points, wrap);
+                let mut options = self.clone();
+                options.wrap = None;
+
+                return options.compute_with_stats(&unwrapped);
+            }
+
+            if let Some(grid) = self.snap {
+                // Compute the hull of the snapped points, then map it back to the originals
+                let (snapped, originals) = snap_points(points, grid);
+                let mut options = self.clone();
+                options.snap = None;
+                if let Some(weights) = &self.weights {
+                    options.weights = Some(originals.iter().map(|&id| weights[id]).collect());
+                }
+
+                let mut result = options.compute_with_stats(&snapped);
+                for (id, p) in &mut result.hull {
+                    *id = originals[*id];
+                    *p = points[*id];
+                }
+
+                return result;
+            }
+
+            if let Some(ratio) = self.max_edge_ratio {
+                // Repeatedly hull the points which are left, dropping any outliers, until there are none
+                let mut options = self.clone();
+                options.max_edge_ratio = None;
+                let mut kept: Vec<usize> = (0..points.len()).collect();
+
+                loop {
+                    let subset: Vec<Point> = kept.iter().map(|&id| points[id]).collect();
+                    if let Some(weights) = &self.weights {
+                        options.weights = Some(kept.iter().map(|&id| weights[id]).collect());
+                    }
+
+                    let mut result = options.compute_with_stats(&subset);
+                    let outliers = outliers::spikes(&result.hull, ratio);
+                    if outliers.is_empty() {
+                        for (id, _) in &mut result.hull {
+                            *id = kept[*id];
+                        }
+
+                        return result;
+                    }
+
+                    // Outliers are positions in the subset, so remove them from the back to keep the rest valid
+                    let mut outliers = outliers;
+                    outliers.sort_unstable();
+                    for position in outliers.into_iter().rev() {
+                        kept.remove(position);
+                    }
+                }
+            }
+
+            let convex = convex_hull_idx(points);
+
+            concave_hull_inner(points, self, &convex)
+        }
+
+        /// Computes the concave hull of the provided point cloud, and returns how many pushes and pops it made on the edge queue
+        ///
+        /// This skips wrapping, snapping, and outlier removal, so it's only meaningful without those options.
+        ///
+        /// This only exists for benchmarking.
+        #[cfg(feature = "benches")]
+        pub fn count_queue_operations(&self, points: &[Point]) -> usize {
+            let convex = convex_hull_idx(points);
+
+            crate::concave::count_queue_operations(points, self, &convex)
+        }
+
+        /// Computes the concave hull of the provided point cloud, using the configured options,
+        /// after checking that the point cloud is sensible
+        ///
+        /// The bounding box of the point cloud must have nonzero width and height,
+        /// and must not be more stretched out than [`ConcaveHullBuilder::max_aspect_ratio`] (if set).
+        /// Otherwise, this returns [`ConcaveHullError::DegenerateExtent`].
+        ///
+        /// If the last point is identical to the first, it is dropped before computing the hull.
+        /// This happens when a closed ring (like the rings in a shapefile) is passed in as the point cloud,
+        /// and the repeated point would otherwise be an exact duplicate, which the hull computation doesn't expect.
+        /// Since only the last point is dropped, the indices in the hull still refer to the original slice.
+        ///
+        /// Points with a NaN or infinite coordinate can't be placed on a hull, so they are left out,
+        /// and the indices in the hull still refer to the original slice.
+        /// If that leaves no points, the hull is empty, and if it leaves a single point (possibly repeated),
+        /// the hull is that point, with the index of its first occurrence.
+        /// These are returned as-is, without checking the bounding box.
+        ///
+        /// See [`concave_hull`] for details on the inputs and outputs.
+        pub fn try_compute(
+            &self,
+            points: &[Point],
+        ) -> Result<Vec<(usize, Point)>, ConcaveHullError> {
+            let points = match points {
+                [first, .., last] if first == last => &points[..points.len() - 1],
+                _ => points,
+            };
+
+            let finite = crate::checked::finite_indices(points);
+            match finite.as_slice() {
+                [] => return Ok(Vec::new()),
+                [first, rest @ ..] if rest.iter().all(|&id| points[id] == points[*first]) => {
+                    return Ok(vec![(*first, points[*first])]);
+                }
+                _ => {}
+            }
+
+            if finite.len() == points.len() {
+                crate::checked::check_extent(points, self.max_aspect_ratio)?;
+                return Ok(self.compute(points));
+            }
+
+            let gathered: Vec<Point> = finite.iter().map(|&id| points[id]).collect();
+            crate::checked::check_extent(&gathered, self.max_aspect_ratio)?;
+
+            Ok(self
+                .compute(&gathered)
+                .into_iter()
+                .map(|(position, p)| (finite[position], p))
+                .collect())
+        }
+    }
+
+    /// A precomputed estimate of the local point density across a point cloud, used for adaptive concavity
+    pub type DensityField = crate::density::DensityField<f64>;
+
+    impl DensityField {
+        /// Builds the density field for the provided point cloud
+        ///
+        /// This is O(n) in the number of points.
+        pub fn new(points: &[Point]) -> Self {
+            Self::build(points)
+        }
+
+        /// Samples the density (in points per unit area) at the provided point
+        ///
+        /// This is O(1).
+        pub fn sample(&self, point: &Point) -> f64 {
+            self.density_at(point)
+        }
+    }
+
+    /// A concave hull, bundled with a spatial index over its edges for fast nearest-edge and containment queries
+    pub type IndexedHull = crate::indexed::IndexedHull<f64>;
+
+    impl IndexedHull {
+        /// Builds the index for a hull returned by [`concave_hull`] (or any other closed ring of points)
+        ///
+        /// This is O(h log h) in the number of hull points.
+        pub fn new(hull: Vec<(usize, Point)>) -> Self {
+            Self::build(hull)
+        }
+
+        /// The hull points, in the same format as returned by [`concave_hull`]
+        pub fn hull(&self) -> &[(usize, Point)] {
+            &self.hull
+        }
+
+        /// Finds the hull edge nearest to `point`, returning its position in the hull and its distance from `point`
+        ///
+        /// The edge at position `k` goes from hull point `k` to hull point `k + 1`, wrapping around at the end.
+        /// Hulls with fewer than two points have no edges, so this returns `None` for them.
+        ///
+        /// This is O(log h) in the number of hull points, for typical hulls.
+        pub fn nearest_edge(&self, point: &Point) -> Option<(usize, f64)> {
+            self.query_nearest_edge(point)
+        }
+
+        /// Checks if `point` is inside of the hull
+        ///
+        /// Points exactly on the boundary may be reported either way, and hulls with fewer than three points (including empty hulls) contain nothing.
+        ///
+        /// This is O(log h) in the number of hull points, for typical hulls.
+        pub fn contains(&self, point: &Point) -> bool {
+            self.query_contains(point)
+        }
+    }
+
+    /// A concave hull over a sliding window of the most recent points from a stream
+    pub type SlidingHull = crate::sliding::SlidingHull<f64>;
+
+    impl SlidingHull {
+        /// Returns the concave hull of the points currently in the window
+        ///
+        /// The returned indices are positions in the window, where `0` is the oldest point (see [`SlidingHull::points`]).
+        /// The hull is only recomputed if the window has changed since the last call.
+        pub fn hull(&mut self) -> &[(usize, Point)] {
+            let concavity = self.concavity;
+            let window = self.window.make_contiguous();
+
+            self.hull
+                .get_or_insert_with(|| concave_hull(window, concavity))
+        }
+    }
+
+    /// Computes the concave hull of the provided point cloud, using the provided concavity parameter
+    ///
+    /// Inputs:
+    /// - `points`: A list of points, making up the point cloud to generate the concave hull for.
+    ///   It is assumed that this list contains no repeat points.
+    /// - `concavity`: A parameter determining how concave the hull should be.
+    ///   See the crate-level docs for guidance on picking the concavity parameter.
+    ///
+    /// The returned [`Vec`] contains a tuple of:
+    /// - The index of the hull point in the original slice
+    /// - The value of the point in the original slice
+    ///
+    /// The points are returned in counter-clockwise order.
+    ///
+    /// Point clouds which don't enclose any area can't have a concave hull, so their convex hull is returned instead:
+    /// - An empty point cloud has an empty hull.
+    /// - If every point is the same, the hull is just the first point.
+    /// - If every point is on a single line, the hull is the two points at the ends of the line.
+    ///
+    /// Any other cloud of three points is its own hull, as a triangle.
+    /// These hulls always start from their lexicographically smallest point (by x, then by y),
+    /// and triangles are counter-clockwise like every other hull.
+    ///
+    /// The hull is deterministic, but symmetric clouds (like regular grids) have ties between equally long edges,
+    /// which are broken in a way that depends on the order of `points`.
+    /// For a hull which only depends on the positions of the points, use [`ConcaveHullBuilder::stable_ordering`].
+    ///
+    /// Every time an edge is split, every point in the cloud is checked as a candidate to split it with,
+    /// so runtime grows with the number of points times the number of hull points.
+    /// This makes runtime, rather than memory or indexing, the practical limit on input size:
+    /// clouds with millions of points are fine for large concavity values,
+    /// but small concavity values (which produce large hulls) get slow long before then.
+    pub fn concave_hull(points: &[Point], concavity: f64) -> Vec<(usize, Point)> {
+        ConcaveHullBuilder::new(concavity).compute(points)
+    }
+
+    /// Computes the edges of the concave hull of the provided point cloud, without ordering them
+    ///
+    /// Ordering the edges of the hull end to end is the last step of [`concave_hull`],
+    /// which takes a linear pass over the edges, and a [`HashMap`](std::collections::HashMap) from each point to its outgoing edge.
+    /// This skips that step, saving that time and memory, for callers which don't need the hull in order, or would rather order it themselves.
+    ///
+    /// See [`concave_hull`] for details on the inputs.
+    /// Each returned [`Edge`] goes from point `i` to point `j`, with the hull on its left (since the hull is counter-clockwise).
+    /// The order of the edges in the returned [`Vec`] is unspecified.
+    /// Currently, they are in the order that the gift opening process finalized them, but this may change in the future.
+    pub fn concave_hull_unsorted(points: &[Point], concavity: f64) -> Vec<Edge> {
+        let convex = convex_hull_idx(points);
+
+        concave_hull_unsorted_inner(points, &ConcaveHullBuilder::new(concavity), &convex)
+    }
+
+    /// Computes the concave hull of the provided point cloud, with a concavity relative to the spacing between points
+    ///
+    /// The mean distance from each point to its nearest neighbour is measured,
+    /// and the concavity parameter passed to [`concave_hull`] is `factor` times that distance.
+    /// Since edges longer than the concavity parameter are split, `factor` is roughly how many point spacings
+    /// an edge of the hull may span before it gets bent in towards the point cloud.
+    /// This ties the tightness of the hull to the point spacing, making `factor` scale invariant:
+    /// scaling a point cloud up or down doesn't change its hull.
+    ///
+    /// Like the concavity parameter, `factor` ranges from zero to positive infinity.
+    /// `2` is usually a good starting point (on the question mark dataset, this is roughly equivalent to a concavity of `40`).
+    ///
+    /// Measuring the point spacing is O(n) for reasonably even point clouds.
+    pub fn concave_hull_nn_relative(points: &[Point], factor: f64) -> Vec<(usize, Point)> {
+        concave_hull(points, factor * mean_nearest_neighbor_distance(points))
+    }
+
+    /// Computes the concave hull of the provided point cloud, where heavier points are more likely to end up on the hull
+    ///
+    /// Whenever an edge is split, [`concave_hull`] picks the point which minimizes the larger of the two angles
+    /// between the edge and the new edges to that point.
+    /// Here, each point's angle is divided by its weight before comparing, so the point which minimizes
+    /// `max(angle_1, angle_2) / weight` is picked instead.
+    /// This means that a point with a weight of `2` wins against any point with a weight of `1`
+    /// whose angle is less than twice as large.
+    /// With every weight set to `1`, this is identical to [`concave_hull`].
+    ///
+    /// `weights` must contain one positive, finite weight for each point, in the same order as `points`.
+    /// Panics if the lengths don't match, or if any weight isn't positive and finite.
+    ///
+    /// See [`concave_hull`] for details on the other inputs, and the output.
+    pub fn concave_hull_weighted(
+        points: &[Point],
+        weights: &[f64],
+        concavity: f64,
+    ) -> Vec<(usize, Point)> {
+        assert_eq!(
+            points.len(),
+            weights.len(),
+            "There must be exactly one weight per point"
+        );
+        assert!(
+            weights
+                .iter()
+                .all(|&weight| weight.is_finite() && weight > 0.),
+            "Weights must be positive and finite"
+        );
+
+        let mut builder = ConcaveHullBuilder::new(concavity);
+        builder.weights = Some(weights.to_vec());
+        builder.compute(points)
+    }
+
+    /// Computes the concave hull of the provided point cloud, for any point type implementing [`AsPoint2`]
+    ///
+    /// This is identical to [`concave_hull`], except that the points are converted to [`Point`]s first.
+    /// [`AsPoint2`] is implemented for [`Point`], `(f64, f64)` tuples, and `[f64; 2]` arrays,
+    /// and you can implement it for your own point types.
+    ///
+    /// The returned indices are indices into `points`, but the returned points are converted [`Point`]s.
+    pub fn concave_hull_generic<P: AsPoint2<f64>>(
+        points: &[P],
+        concavity: f64,
+    ) -> Vec<(usize, Point)> {
+        let points: Vec<Point> = points
+            .iter()
+            .map(|p| {
+                let (x, y) = p.xy();
+                Point::new(x, y)
+            })
+            .collect();
+
+        concave_hull(&points, concavity)
+    }
+
+    /// Computes progressively refined versions of the concave hull of the provided point cloud
+    ///
+    /// The returned iterator yields a complete, well-formed hull (in the same format as [`concave_hull`])
+    /// at every stage of the computation: first the convex hull, then a new hull after every edge split.
+    /// The final hull is identical to the one returned by [`concave_hull`].
+    /// This is useful for interactive applications, which can render a rough hull immediately,
+    /// then refine it (or animate the refinement), and stop early if the hull is good enough.
+    ///
+    /// Work is done lazily, as the iterator is advanced.
+    /// Each intermediate hull costs an extra O(h) to assemble, where h is the number of hull points at that stage.
+    ///
+    /// See [`concave_hull`] for details on the inputs.
+    pub fn concave_hull_anytime(
+        points: &[Point],
+        concavity: f64,
+    ) -> impl Iterator<Item = Vec<(usize, Point)>> {
+        let convex = convex_hull_idx(points);
+
+        concave_hull_anytime_inner(points, ConcaveHullBuilder::new(concavity), &convex)
+    }
+
+    /// Computes the concave hull of the provided point cloud, sending each edge over a channel as soon as it's finalized
+    ///
+    /// The work is done on the calling thread, and this returns once the hull is complete,
+    /// but a receiver on another thread (like a UI thread) can pick up each edge as it comes in, without polling.
+    /// `sender` is dropped at the end, which closes the channel if it's the only sender.
+    /// If the receiver hangs up early, nobody is left to use the hull, so the computation stops early too.
+    ///
+    /// Edges are sent in the order they're finalized, and the set of edges is the same as from [`concave_hull_unsorted`].
+    /// Each edge goes from point `i` to point `j`, with the hull on its left.
+    /// To reassemble the hull, start from the last edge sent, and follow each edge to the one starting where it ends:
+    /// this gives exactly the hull returned by [`concave_hull`].
+    ///
+    /// See [`concave_hull`] for details on the other inputs.
+    pub fn concave_hull_channeled(
+        points: &[Point],
+        concavity: f64,
+        sender: std::sync::mpsc::Sender<Edge>,
+    ) {
+        let convex = convex_hull_idx(points);
+
+        concave_hull_channeled_inner(points, ConcaveHullBuilder::new(concavity), &convex, &sender);
+    }
+
+    /// Computes the concave hull of the provided point cloud, with a concavity relative to the longest convex hull edge
+    ///
+    /// The concavity parameter passed to [`concave_hull`] is `fraction` times the length of the longest edge
+    /// of the point cloud's convex hull, which ties the parameter to the scale of the data automatically.
+    /// Since only edges longer than the concavity parameter are split:
+    /// - A `fraction` of `1` or more leaves (roughly) the convex hull, since no edge is long enough to split
+    /// - Smaller fractions dig further into the point cloud
+    /// - `0` produces a maximally crinkly shape, just like a concavity of `0`
+    ///
+    /// For example, on the question mark dataset, a `fraction` of `0.1` is roughly equivalent to a concavity of `40`.
+    ///
+    /// See [`concave_hull`] for details on the other inputs, and the output.
+    pub fn concave_hull_relative_to_longest(
+        points: &[Point],
+        fraction: f64,
+    ) -> Vec<(usize, Point)> {
+        let convex = convex_hull_idx(points);
+        let ring: Vec<Point> = convex.iter().map(|&id| points[id]).collect();
+        let concavity = fraction * metrics::longest_edge(&ring);
+
+        concave_hull_inner(points, &ConcaveHullBuilder::new(concavity), &convex).hull
+    }
+
+    /// Computes the concave hull of the provided point cloud, after checking that the point cloud is sensible
+    ///
+    /// This catches common input mistakes early, rather than returning a meaningless hull.
+    /// See [`ConcaveHullBuilder::try_compute`] for the checks, and [`concave_hull`] for details on the inputs and outputs.
+    pub fn try_concave_hull(
+        points: &[Point],
+        concavity: f64,
+    ) -> Result<Vec<(usize, Point)>, ConcaveHullError> {
+        ConcaveHullBuilder::new(concavity).try_compute(points)
+    }
+
+    /// Computes a concave hull of the provided point cloud using the chi-shape algorithm, instead of gift opening
+    ///
+    /// The chi-shape starts from the Delaunay triangulation of the point cloud, whose boundary is the convex hull,
+    /// then repeatedly removes the longest edge on the boundary (along with the triangle behind it),
+    /// until every boundary edge is at most `length_threshold` long.
+    /// Edges are only removed if doing so keeps the boundary a simple polygon which contains every point.
+    /// This tends to produce smoother hulls than gift opening on evenly spaced clouds,
+    /// so it can be worth comparing the two on your data.
+    ///
+    /// `length_threshold` plays the same role as the concavity parameter of [`concave_hull`]:
+    /// `0` digs in as far as possible, and `f64::INFINITY` gives the convex hull.
+    /// The output is in the same format as [`concave_hull`], so the two are interchangeable.
+    /// If the points have no triangulation (because there are fewer than three, or they're all on a line),
+    /// then this returns the convex hull instead.
+    ///
+    /// The triangulation is O(n²), which dominates the runtime.
+    /// To compute chi-shapes with several thresholds, use a [`DelaunayHuller`] to only triangulate once.
+    #[cfg(feature = "delaunay")]
+    pub fn chi_shape(points: &[Point], length_threshold: f64) -> Vec<(usize, Point)> {
+        DelaunayHuller::new(points).hull(length_threshold)
+    }
+
+    /// A Delaunay triangulation of a point cloud, for computing chi-shapes with several thresholds
+    ///
+    /// See [`chi_shape`] for details on the hulls.
+    #[cfg(feature = "delaunay")]
+    pub type DelaunayHuller<'a> = crate::chi_shape::DelaunayHuller<'a, f64>;
+
+    #[cfg(feature = "delaunay")]
+    impl<'a> DelaunayHuller<'a> {
+        /// Triangulates the provided point cloud, ready for computing hulls
+        ///
+        /// This is O(n²), and is the expensive part of computing a chi-shape.
+        pub fn new(points: &'a [Point]) -> Self {
+            Self::triangulate(points)
+        }
+
+        /// Computes the chi-shape with the provided threshold, reusing the triangulation
+        ///
+        /// This gives exactly the same hull as [`chi_shape`], but only takes O(t log t) in the number of triangles.
+        pub fn hull(&self, length_threshold: f64) -> Vec<(usize, Point)> {
+            let hull = self.carve(length_threshold);
+            if hull.is_empty() {
+                return concave_hull(self.points, f64::INFINITY);
+            }
+
+            hull
+        }
+    }
+
+    /// Computes the concave hull of the provided point cloud, along with some extra information about the computation
+    ///
+    /// See [`concave_hull`] for details on the inputs, and [`HullResult`] for details on the output.
+    pub fn concave_hull_with_stats(points: &[Point], concavity: f64) -> HullResult {
+        ConcaveHullBuilder::new(concavity).compute_with_stats(points)
+    }
+
+    /// Computes the concave hull of the provided point cloud for each of the provided concavity values
+    ///
+    /// This is equivalent to calling [`concave_hull`] once per concavity value,
+    /// but the convex hull is only computed once, and shared between all of the computations.
+    /// This is useful when sweeping over concavity values to tune the parameter.
+    ///
+    /// With the `rayon` feature enabled, the hulls are computed in parallel.
+    ///
+    /// The returned hulls are in the same order as `concavities`.
+    pub fn concave_hull_sweep(points: &[Point], concavities: &[f64]) -> Vec<Vec<(usize, Point)>> {
+        let convex = convex_hull_idx(points);
+
+        concave_hull_sweep_inner(points, concavities, &convex)
+    }
+
+    /// Searches a grid of relative concavities and neighbour counts for the hull which scores highest on `objective`
+    ///
+    /// Each of the `factors` is a concavity relative to the point spacing, in the same way as [`concave_hull_nn_relative`],
+    /// except that the spacing is the mean distance from each point to its `k`th nearest neighbour, for each `k` in `knns`.
+    /// Larger neighbour counts measure the spacing over a wider area, which is less sensitive to clumps of points.
+    /// With `k = 1`, the hulls are exactly those from [`concave_hull_nn_relative`].
+    ///
+    /// Every combination of factor and neighbour count is hulled and scored by `objective` (higher is better),
+    /// such as the [`iou_with`] a known boundary, or a tradeoff between area and perimeter.
+    /// Returns the best factor and neighbour count, or `None` if `factors` or `knns` is empty, or every score is NaN.
+    /// NaN scores never win, and ties go to the first combination, going through `knns` for each factor in turn.
+    /// The spacing is measured once for each neighbour count, and the convex hull is shared between every hull.
+    ///
+    /// With the `rayon` feature enabled, the hulls are computed in parallel.
+    pub fn parameter_grid_search(
+        points: &[Point],
+        factors: &[f64],
+        knns: &[usize],
+        objective: impl Fn(&HullResult) -> f64 + Sync,
+    ) -> Option<(f64, usize)> {
+        let convex = convex_hull_idx(points);
+
+        parameter_grid_search_inner(points, &convex, factors, knns, objective)
+    }
+
+    /// Measures how deeply each point on the concave hull protrudes into the point cloud
+    ///
+    /// The concave hull can be decomposed into pockets: regions carved out of the convex hull, each of which
+    /// is bounded by a straight convex hull edge on one side, and a chain of concave hull points on the other.
+    /// The depth of a concave hull point is its perpendicular distance from the convex hull edge spanning its pocket.
+    /// Points which are on the convex hull have a depth of zero.
+    ///
+    /// See [`concave_hull`] for details on the inputs.
+    /// Returns the original index of each hull point, paired with its depth, in the same order as [`concave_hull`].
+    pub fn protrusion_depths(points: &[Point], concavity: f64) -> Vec<(usize, f64)> {
+        let convex = convex_hull_idx(points);
+        let hull = concave_hull_inner(points, &ConcaveHullBuilder::new(concavity), &convex).hull;
+
+        pockets::protrusion_depths(&hull, &convex)
+    }
+
+    /// Splits the concave hull into the pockets carved out of the convex hull
+    ///
+    /// Each pocket is the region between a chain of concave hull points and the straight convex hull edge spanning them
+    /// (see [`protrusion_depths`] for more on pockets).
+    /// Pockets are returned as their boundary points, in the same order as in the hull:
+    /// the chain starts and ends on convex hull points, and the pocket is closed by the edge from its last point back to its first.
+    /// Pockets are listed in the order that they appear around the hull.
+    ///
+    /// See [`concave_hull`] for details on the inputs.
+    /// Convex hulls have no pockets, so this returns nothing for them.
+    pub fn concave_hull_pockets(points: &[Point], concavity: f64) -> Vec<Vec<(usize, Point)>> {
+        let convex = convex_hull_idx(points);
+        let hull = concave_hull_inner(points, &ConcaveHullBuilder::new(concavity), &convex).hull;
+
+        pockets::find_pockets(&hull, &convex)
+            .into_iter()
+            .map(|pocket| pocket.into_iter().map(|pos| hull[pos]).collect())
+            .collect()
+    }
+
+    /// Splits an existing concave hull of `points` into the pockets carved out of the convex hull
+    ///
+    /// This gives the same pockets as [`concave_hull_pockets`], without computing the hull again.
+    /// `hull` must be a hull of `points`, such as from [`concave_hull`] or [`ConcaveHullBuilder::compute`].
+    pub fn hull_pockets(points: &[Point], hull: &[(usize, Point)]) -> Vec<Vec<(usize, Point)>> {
+        let convex = convex_hull_idx(points);
+
+        pockets::find_pockets(hull, &convex)
+            .into_iter()
+            .map(|pocket| pocket.into_iter().map(|pos| hull[pos]).collect())
+            .collect()
+    }
+
+    /// Computes the concave hull of the provided point cloud, labelling each edge by whether it was ever split
+    ///
+    /// Each vertex is paired with the [`EdgeKind`] of the edge leaving it, towards the next vertex in the hull
+    /// (and from the last vertex back to the first).
+    /// [`EdgeKind::Convex`] edges are straight chords of the convex hull which survived gift opening unsplit,
+    /// while [`EdgeKind::Concave`] edges bend in towards the point cloud.
+    ///
+    /// See [`concave_hull`] for details on the inputs.
+    /// The vertices are the same, and in the same order, as in [`concave_hull`].
+    pub fn concave_hull_classified(
+        points: &[Point],
+        concavity: f64,
+    ) -> Vec<(usize, Point, EdgeKind)> {
+        let convex = convex_hull_idx(points);
+        let hull = concave_hull_inner(points, &ConcaveHullBuilder::new(concavity), &convex).hull;
+
+        pockets::classify_edges(&hull, &convex)
+    }
+
+    /// Computes the concave hull of the provided point cloud, flagging which vertices were already on the convex hull
+    ///
+    /// Gift opening starts from the convex hull, and every split inserts a new vertex, without ever removing one.
+    /// Each vertex is paired with `true` if it's one of the original convex hull vertices, and `false` if it was inserted by a split.
+    /// With a concavity of infinity, nothing is split, so every flag is `true`.
+    ///
+    /// See [`concave_hull`] for details on the inputs.
+    /// The vertices are the same, and in the same order, as in [`concave_hull`].
+    pub fn concave_hull_provenance(points: &[Point], concavity: f64) -> Vec<(usize, Point, bool)> {
+        let convex = convex_hull_idx(points);
+        let hull = concave_hull_inner(points, &ConcaveHullBuilder::new(concavity), &convex).hull;
+        let convex: std::collections::HashSet<usize> = convex.into_iter().collect();
+
+        hull.into_iter()
+            .map(|(id, p)| (id, p, convex.contains(&id)))
+            .collect()
+    }
+
+    /// Triangulates the pockets carved out of the convex hull by the concave hull
+    ///
+    /// Each pocket is the region between a chain of concave hull points and the straight convex hull edge spanning them
+    /// (see [`protrusion_depths`] for more on pockets).
+    /// Together, the pockets make up exactly the area that the concavity parameter carved away from the convex hull,
+    /// so filling these triangles is a convenient way to visualize it.
+    ///
+    /// See [`concave_hull`] for details on the inputs.
+    /// Returns the triangles from every pocket, with each vertex as its index and value in the original slice.
+    /// Each triangle is wound counter-clockwise.
+    pub fn pocket_triangles(points: &[Point], concavity: f64) -> Vec<[(usize, Point); 3]> {
+        let convex = convex_hull_idx(points);
+        let hull = concave_hull_inner(points, &ConcaveHullBuilder::new(concavity), &convex).hull;
+
+        pockets::pocket_triangles(&hull, &convex)
+    }
+
+    /// Finds the convex hull edges which were split while opening up the concave hull
+    ///
+    /// Every convex hull point stays on the concave hull, so a convex hull edge was split (at least once)
+    /// exactly when its two ends are no longer neighbours on the concave hull.
+    /// Each of these edges spans one pocket (see [`concave_hull_pockets`]), and the rest survived unsplit.
+    ///
+    /// See [`concave_hull`] for details on the inputs.
+    /// Returns the original indices of the start and end of each split edge, counter-clockwise around the hull,
+    /// in the order that they appear around the hull.
+    pub fn replaced_convex_edges(points: &[Point], concavity: f64) -> Vec<(usize, usize)> {
+        let convex = convex_hull_idx(points);
+        let hull = concave_hull_inner(points, &ConcaveHullBuilder::new(concavity), &convex).hull;
+
+        pockets::find_pockets(&hull, &convex)
+            .into_iter()
+            .map(|pocket| (hull[pocket[0]].0, hull[pocket[pocket.len() - 1]].0))
+            .collect()
+    }
+
+    /// Interpolates between the concave hulls for two concavities, for animating between them
+    ///
+    /// `t` goes from `0`, which gives the hull for `concavity_a`, to `1`, which gives the hull for `concavity_b`.
+    /// Points on the two hulls are matched up by how far around their hull they are, as a fraction of its perimeter,
+    /// measured from the start of the first hull (and from the nearest point to it on the second).
+    /// Both hulls are resampled at the position of every vertex of either hull, so they have the same number of points,
+    /// and each pair is interpolated linearly.
+    /// Every vertex is kept, so the ends have exactly the shapes of the two hulls, with extra points along their edges.
+    ///
+    /// See [`concave_hull`] for details on the other inputs.
+    /// The in-between points usually aren't in the point cloud, so only their values are returned.
+    /// Intermediate shapes can self-intersect where the hulls differ a lot, so this is only meant for presentation.
+    pub fn morph_hulls(points: &[Point], concavity_a: f64, concavity_b: f64, t: f64) -> Vec<Point> {
+        let ring = |concavity| -> Vec<Point> {
+            concave_hull(points, concavity)
+                .into_iter()
+                .map(|(_, p)| p)
+                .collect()
+        };
+
+        crate::morph::morph_rings(&ring(concavity_a), &ring(concavity_b), t)
+    }
+
+    /// Computes the regions which are inside the concave hull for one concavity, but not the other
+    ///
+    /// This is the symmetric difference between the two hulls, which is useful for visualizing the effect of changing concavity.
+    /// Both hulls share the same convex hull points, so they can only differ inside of pockets
+    /// (see [`protrusion_depths`] for more on pockets).
+    ///
+    /// See [`concave_hull`] for details on the inputs.
+    /// Returns each region as a ring of points, which is implicitly closed.
+    /// Regions are returned in no particular winding order, and either hull may be the larger in any one region.
+    /// In the rare case that the two hulls cross each other between shared points,
+    /// a region may be self-intersecting, in which case it should be filled with the even-odd rule.
+    pub fn hull_difference(
+        points: &[Point],
+        concavity_a: f64,
+        concavity_b: f64,
+    ) -> Vec<Vec<Point>> {
+        let convex = convex_hull_idx(points);
+        let a = concave_hull_inner(points, &ConcaveHullBuilder::new(concavity_a), &convex).hull;
+        let b = concave_hull_inner(points, &ConcaveHullBuilder::new(concavity_b), &convex).hull;
+
+        pockets::hull_difference(&a, &b, &convex)
+    }
+
+    /// Computes a concave path between two points in the provided point cloud
+    ///
+    /// This is the open counterpart to [`concave_hull`], for point clouds which represent an open curve
+    /// (like a segment of coastline), rather than an enclosed region.
+    /// Instead of starting from the convex hull, the gift opening process starts from a single straight edge
+    /// between the two endpoints, and repeatedly splits it using the same rules as [`concave_hull`].
+    ///
+    /// Inputs:
+    /// - `points`: A list of points, making up the point cloud to generate the path for.
+    ///   It is assumed that this list contains no repeat points.
+    /// - `concavity`: A parameter determining how closely the path should follow the point cloud.
+    ///   This behaves the same way as the concavity parameter of [`concave_hull`].
+    /// - `start`, `end`: The indices of the points at either end of the path.
+    ///   These must be distinct, valid indices into `points`, or there is no path and this returns `None`.
+    ///
+    /// Since the path starts as a single edge, it can bend towards points on either side of that edge.
+    /// In practice, it bends towards whichever side has points closest to the straight edge.
+    ///
+    /// The returned [`Vec`] contains the index and value of each point on the path, in order from `start` to `end`.
+    pub fn concave_path(
+        points: &[Point],
+        concavity: f64,
+        start: usize,
+        end: usize,
+    ) -> Option<Vec<(usize, Point)>> {
+        open_path_inner(points, &ConcaveHullBuilder::new(concavity), start, end)
+    }
+
+    /// Computes the concave hull of the provided point cloud, with the output split into flat buffers
+    ///
+    /// This is identical to [`concave_hull`], but returns the hull in a layout that is friendlier to
+    /// C interop and vertex buffers, rather than as a [`Vec`] of tuples.
+    ///
+    /// Returns a tuple of:
+    /// - The index of each hull point in the original slice, in winding order.
+    /// - The coordinates of each hull point, interleaved as `[x0, y0, x1, y1, ...]`, in the same order.
+    ///
+    /// The coordinate buffer is always exactly twice as long as the index buffer,
+    /// and the coordinates of the `k`th hull point are at positions `2 * k` and `2 * k + 1`.
+    pub fn concave_hull_flat(points: &[Point], concavity: f64) -> (Vec<usize>, Vec<f64>) {
+        let hull = concave_hull(points, concavity);
+
+        let indices = hull.iter().map(|(id, _)| *id).collect();
+        let coordinates = hull.iter().flat_map(|(_, p)| [p.x, p.y]).collect();
+        (indices, coordinates)
+    }
+
+    /// Computes the concave hull of the provided point cloud, returning references to the hull points rather than copies
+    ///
+    /// The returned points borrow from `points`, so the output only holds an index and a reference per hull point.
+    /// On 64-bit targets, that's 16 bytes per hull point, rather than the 24 bytes of an index and a copy of the point,
+    /// so the output is a third smaller.
+    /// The hull is still computed (and briefly held) with copies of its points, so this doesn't reduce peak memory usage.
+    /// (There's no `f32` version, since an `f32` point is already the size of a reference, so there would be nothing to save.)
+    ///
+    /// The indices and order are exactly the same as [`concave_hull`], and each reference points at `points[index]`.
+    /// See [`concave_hull`] for details on the inputs, and on the hull itself.
+    pub fn concave_hull_refs(points: &[Point], concavity: f64) -> Vec<(usize, &Point)> {
+        concave_hull(points, concavity)
+            .into_iter()
+            .map(|(id, _)| (id, &points[id]))
+            .collect()
+    }
+
+    /// Computes the concave hull of a point cloud which has changed slightly, starting from its previous hull
+    ///
+    /// This is meant for clouds which change a little at a time, such as tracked objects from one video frame to the next.
+    /// `previous` is the indices of the previous hull (as returned by [`concave_hull`]), into the current `points`.
+    /// Rather than starting from the convex hull, gift opening starts from the edges of the previous hull,
+    /// and only splits those which are now too long, so the work already done isn't repeated.
+    ///
+    /// The previous hull is only used if it's still a valid hull of the current points (see [`validate_hull`]):
+    /// every index must be in range, its edges must not cross, and every point must still be inside of it.
+    /// Otherwise (say, because a point moved outside of it), the hull is computed from scratch with [`concave_hull`].
+    ///
+    /// This is an approximation: edges are only ever split, never merged, so the hull can dig in where points have moved,
+    /// but never fills back out where a full recompute would.
+    /// Over many updates, the hull drifts towards being more concave than [`concave_hull`] would make it,
+    /// so it's best to recompute it from scratch every so often (say, every few dozen frames).
+    pub fn concave_hull_warm(
+        previous: &[usize],
+        points: &[Point],
+        concavity: f64,
+    ) -> Vec<(usize, Point)> {
+        concave_hull_warm_inner(points, &ConcaveHullBuilder::new(concavity), previous)
+            .unwrap_or_else(|| concave_hull(points, concavity))
+    }
+
+    /// Computes the concave hull of the provided point cloud, wound so that `inside_hint` is on its inside
+    ///
+    /// Some systems define a ring's orientation by which side of it is the inside, rather than by a fixed winding.
+    /// The hull is returned so that walking along it, `inside_hint` is always on the left,
+    /// which (for the simple polygons returned by [`concave_hull`]) is the usual counter-clockwise winding.
+    /// Any point strictly inside of the hull works as a hint, such as a point known to be in the middle of the shape.
+    ///
+    /// If `inside_hint` is outside of the hull, or exactly on its boundary, this returns [`ConcaveHullError::HintOutside`].
+    /// Hulls with fewer than three points don't have an inside, so they always return that error too.
+    ///
+    /// See [`concave_hull`] for details on the other inputs, and on the hull itself.
+    pub fn concave_hull_oriented(
+        points: &[Point],
+        concavity: f64,
+        inside_hint: Point,
+    ) -> Result<Vec<(usize, Point)>, ConcaveHullError> {
+        crate::checked::orient_around(concave_hull(points, concavity), &inside_hint)
+    }
+
+    /// Computes the concave hull of the provided point cloud, bundled with a spatial index over its edges
+    ///
+    /// Building the index takes O(h log h) in the number of hull points, after which [`IndexedHull::nearest_edge`]
+    /// and [`IndexedHull::contains`] take O(log h), rather than O(h) each.
+    /// This is worthwhile when the hull will be queried many times.
+    ///
+    /// See [`concave_hull`] for details on the inputs, and on the hull itself.
+    pub fn concave_hull_indexed(points: &[Point], concavity: f64) -> IndexedHull {
+        IndexedHull::new(concave_hull(points, concavity))
+    }
+
+    /// Computes the concave hull of a subset of the provided point cloud, returning indices into the full point cloud
+    ///
+    /// `subset` lists the indices of the points to hull, such as every point with a particular label.
+    /// Only the points in the subset are gathered up for the computation, so the rest of the cloud is never copied,
+    /// and the returned indices refer to `points` (not to positions in `subset`), so no remapping is needed.
+    ///
+    /// Otherwise, this is identical to [`concave_hull`] on the points in the subset.
+    /// Repeated indices in `subset` are treated as repeat points.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any index in `subset` is out of bounds for `points`.
+    pub fn concave_hull_subset(
+        points: &[Point],
+        subset: &[usize],
+        concavity: f64,
+    ) -> Vec<(usize, Point)> {
+        let gathered: Vec<Point> = subset.iter().map(|&id| points[id]).collect();
+
+        concave_hull(&gathered, concavity)
+            .into_iter()
+            .map(|(position, p)| (subset[position], p))
+            .collect()
+    }
+
+    /// Computes a separate concave hull for each label, for point clouds made up of several labelled groups
+    ///
+    /// `labels` gives the label of each point, in the same order as `points`.
+    /// Points are grouped by their label (regardless of where they are), and each group is hulled with [`concave_hull_subset`],
+    /// so the returned indices refer to `points`.
+    /// Groups don't affect each other, so their hulls can overlap.
+    ///
+    /// Panics if the lengths of `points` and `labels` don't match.
+    /// See [`concave_hull`] for details on the other inputs, and on each hull.
+    pub fn concave_hull_by_label(
+        points: &[Point],
+        labels: &[u32],
+        concavity: f64,
+    ) -> std::collections::HashMap<u32, Vec<(usize, Point)>> {
+        concave_hull_by_label_min_size(points, labels, concavity, 0)
+    }
+
+    /// Computes a separate concave hull for each label with at least `min_cluster_size` points, treating the rest as noise
+    ///
+    /// This is [`concave_hull_by_label`], except that labels with fewer than `min_cluster_size` points are left out,
+    /// like noise points in DBSCAN, rather than each getting a degenerate hull of a point or two.
+    /// A `min_cluster_size` of zero (or one) keeps every label.
+    ///
+    /// Panics if the lengths of `points` and `labels` don't match.
+    /// See [`concave_hull`] for details on the other inputs, and on each hull.
+    pub fn concave_hull_by_label_min_size(
+        points: &[Point],
+        labels: &[u32],
+        concavity: f64,
+        min_cluster_size: usize,
+    ) -> std::collections::HashMap<u32, Vec<(usize, Point)>> {
+        assert_eq!(
+            points.len(),
+            labels.len(),
+            "There must be exactly one label per point"
+        );
+
+        let mut groups: std::collections::HashMap<u32, Vec<usize>> =
+            std::collections::HashMap::new();
+        for (id, &label) in labels.iter().enumerate() {
+            groups.entry(label).or_default().push(id);
+        }
+
+        groups
+            .into_iter()
+            .filter(|(_, group)| group.len() >= min_cluster_size)
+            .map(|(label, group)| (label, concave_hull_subset(points, &group, concavity)))
+            .collect()
+    }
+
+    /// Computes the concave hull of a random sample of the provided point cloud, returning indices into the full point cloud
+    ///
+    /// Each point is kept with probability `sample_fraction`, and the hull of the kept points is computed with [`concave_hull_subset`].
+    /// The sample only depends on `seed` and the number of points, so the same inputs always give the same hull.
+    ///
+    /// This is much faster than hulling the whole cloud on large inputs, which makes it useful for quick previews,
+    /// but the result is only approximate.
+    /// Points which weren't sampled may be left outside of the hull, and fine details are lost as the sample gets sparser,
+    /// so the hull can be off by around the spacing between sampled points, or the concavity, whichever is larger.
+    /// Sparser samples also dig in less, since the concavity is measured against a sparser cloud.
+    ///
+    /// A `sample_fraction` of one (or more) keeps every point, and is identical to [`concave_hull`].
+    /// See [`concave_hull`] for details on the other inputs.
+    pub fn concave_hull_sampled(
+        points: &[Point],
+        concavity: f64,
+        sample_fraction: f64,
+        seed: u64,
+    ) -> Vec<(usize, Point)> {
+        let subset = sample_indices(points.len(), sample_fraction, seed);
+
+        concave_hull_subset(points, &subset, concavity)
+    }
+
+    /// Merges concave hulls computed on separate parts of a point cloud into a single hull of the whole cloud
+    ///
+    /// Each entry of `hulls` lists the indices (into `points`) of the vertices of one part's hull,
+    /// such as the indices from [`concave_hull_subset`] on each partition of the cloud.
+    /// The parts may overlap, and the same index may appear in several hulls.
+    /// The union of all the hull vertices is treated as a reduced point cloud, and hulled again with [`concave_hull_subset`].
+    ///
+    /// This is much cheaper than hulling the whole cloud again, since only the boundary points of each part are considered,
+    /// but it is an approximation.
+    /// The convex hull is always exact, since every convex hull point is on the hull of its part,
+    /// but points which were inside of their own part's hull never make it onto the merged hull,
+    /// even if the full hull would have dug in to reach them (such as points near the seam between two parts).
+    /// The merged hull is exact when every point of the full hull is on the hull of some part,
+    /// which is more likely when the parts overlap.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any index in `hulls` is out of bounds for `points`.
+    pub fn merge_hulls(
+        points: &[Point],
+        hulls: &[Vec<usize>],
+        concavity: f64,
+    ) -> Vec<(usize, Point)> {
+        let mut candidates: Vec<usize> = hulls.iter().flatten().copied().collect();
+        candidates.sort_unstable();
+        candidates.dedup();
+
+        concave_hull_subset(points, &candidates, concavity)
+    }
+
+    /// Computes the concave hull of the combined vertices of several hulls, as an outer boundary around all of them
+    ///
+    /// Each entry of `hulls` lists the vertices of one hull, such as regional hulls computed separately.
+    /// Unlike [`merge_hulls`], the hulls don't need to come from a shared point cloud, so their vertices are taken by value.
+    /// Vertices that are in exactly the same place (such as a corner shared by two hulls) are only counted once.
+    ///
+    /// This only operates on the provided boundary points: whatever was inside of each hull is gone,
+    /// so the result can't dig in to where the original points were, only to other hulls' vertices.
+    /// It isn't the union of the hulls either, since the union has corners where the hulls' edges cross, which aren't vertices of any hull.
+    /// This makes it a cheap approximation, suited to aggregating hulls up a hierarchy of regions.
+    ///
+    /// See [`concave_hull`] for details on `concavity`.
+    /// The result is in the same order as [`concave_hull`], but only the values of its points are returned,
+    /// since there's no single point cloud for indices to refer to.
+    pub fn hull_of_hulls(hulls: &[Vec<Point>], concavity: f64) -> Vec<Point> {
+        let mut vertices: Vec<Point> = hulls.iter().flatten().copied().collect();
+        vertices.sort_unstable_by(|a, b| a.x.total_cmp(&b.x).then(a.y.total_cmp(&b.y)));
+        vertices.dedup();
+
+        concave_hull(&vertices, concavity)
+            .into_iter()
+            .map(|(_, p)| p)
+            .collect()
+    }
+
+    /// Finds the vertices where a hull touches itself, making it non-simple even though none of its edges cross
+    ///
+    /// A vertex is a pinch if another vertex is in exactly the same place (in which case both are returned),
+    /// or if it lies exactly on an edge which doesn't end at it.
+    /// Some consumers (such as strict GIS validators) reject polygons like this; see [`split_pinch_points`] to repair them.
+    ///
+    /// Hulls from [`concave_hull`] never pinch, since a split is rejected if its new edges so much as touch an existing edge,
+    /// but post-processing (or hulls from elsewhere) can introduce them.
+    ///
+    /// Returns the positions of the pinched vertices in `hull`, in increasing order.
+    /// This is O(h²) in the number of hull points.
+    pub fn find_pinch_points(hull: &[(usize, Point)]) -> Vec<usize> {
+        if is_empty_hull(hull) {
+            return Vec::new();
+        }
+
+        crate::pinch::find_pinches(hull)
+    }
+
+    /// Splits a hull into separate rings at every point where it touches itself, so that each ring is simple
+    ///
+    /// At a pinch between two vertices in the same place, the hull is cut into the loop between them and the rest.
+    /// A vertex touching the middle of another edge is first copied into that edge, then cut in the same way.
+    /// The pinched vertex ends up on each of the rings that meet there, with the same index.
+    /// See [`find_pinch_points`] for what counts as a pinch.
+    ///
+    /// Each ring winds in the same direction as `hull`, and the rings are returned in an unspecified order.
+    /// Hulls without any pinches are returned unchanged, as a single ring, and empty hulls have no rings at all.
+    pub fn split_pinch_points(hull: &[(usize, Point)]) -> Vec<Vec<(usize, Point)>> {
+        if is_empty_hull(hull) {
+            return Vec::new();
+        }
+
+        crate::pinch::split_pinches(hull.to_vec())
+    }
+
+    /// Simplifies a hull down to exactly `count` vertices (or leaves it alone if it already has no more than that)
+    ///
+    /// Vertices are dropped one at a time, least important first, in the style of Visvalingam–Whyatt.
+    /// A vertex's importance is the area of the triangle it makes with its two neighbours,
+    /// which is how much the hull's area changes when it's dropped, so the overall shape is kept as well as possible.
+    /// Unlike tolerance-based simplification, this gives a predictable number of vertices.
+    ///
+    /// The remaining vertices keep their order and indices.
+    /// Dropping vertices can make a hull self-intersect, especially for small counts, so check with [`validate_hull`] if that matters.
+    /// This is O(h²) in the number of hull points.
+    pub fn simplify_to_count(hull: &[(usize, Point)], count: usize) -> Vec<(usize, Point)> {
+        if is_empty_hull(hull) {
+            return Vec::new();
+        }
+
+        crate::simplify::simplify_to_count(hull, count)
+    }
+
+    /// Computes the concave hull of the provided point cloud, clipped so that it doesn't extend beyond `clip`
+    ///
+    /// This is useful when the hull has to stay inside of a known boundary, such as the border of a map.
+    /// The hull is computed as usual with [`concave_hull`], then clipped against `clip` with the Sutherland-Hodgman algorithm.
+    ///
+    /// `clip` should be a convex polygon, and may wind either way.
+    /// Simple (but concave) polygons are accepted, but are clipped against the full lines through each of their edges,
+    /// so more of the hull may be cut away than lies outside of them.
+    /// Where a concave hull is cut into several pieces, they are returned as a single ring, joined by zero-width slivers along `clip`.
+    ///
+    /// Clipping creates new vertices wherever the hull crosses `clip`, which aren't points in the point cloud,
+    /// so only the clipped points are returned, without any indices.
+    /// They are in the same (counter-clockwise) order as the hull.
+    /// If the hull and `clip` don't overlap, or either has fewer than three points, the result is empty.
+    pub fn concave_hull_clipped(points: &[Point], concavity: f64, clip: &[Point]) -> Vec<Point> {
+        let hull: Vec<Point> = concave_hull(points, concavity)
+            .into_iter()
+            .map(|(_, p)| p)
+            .collect();
+
+        clip::clip_ring(&hull, clip)
+    }
+
+    /// Computes the concave hull of the foreground pixels of a mask, such as the output of image segmentation
+    ///
+    /// Every pixel brighter than `threshold` is foreground, and contributes its center to the point cloud.
+    /// The hull is in pixel coordinates: pixel `(x, y)` covers the square from `(x, y)` to `(x + 1, y + 1)`, so its center is at `(x + 0.5, y + 0.5)`.
+    /// Like the image itself, the y axis points down, starting from the top row.
+    /// This flips the winding: the hull is counter-clockwise with the y axis pointing up (as in [`concave_hull`]),
+    /// so it runs clockwise when drawn over the image.
+    ///
+    /// Each vertex is paired with its index among the foreground pixels, counting in row-major order (along each row, from the top row down).
+    /// See [`concave_hull`] for details on `concavity`, and on the output.
+    #[cfg(feature = "image")]
+    pub fn concave_hull_from_mask(
+        mask: &image::GrayImage,
+        threshold: u8,
+        concavity: f64,
+    ) -> Vec<(usize, Point)> {
+        let points = crate::pixel_mask::foreground_points(mask, threshold);
+
+        concave_hull(&points, concavity)
+    }
+
+    /// Computes the concave hull of the provided point cloud, returning each vertex with both of its indices
+    ///
+    /// This is identical to [`concave_hull`], except that each vertex also records its position in the hull,
+    /// which saves enumerating the hull when cross-referencing it with the point cloud.
+    /// The `k`th vertex always has an `order` of `k`.
+    pub fn concave_hull_vertices(points: &[Point], concavity: f64) -> Vec<HullVertex> {
+        concave_hull(points, concavity)
+            .into_iter()
+            .enumerate()
+            .map(|(order, (original, point))| HullVertex {
+                original,
+                order,
+                point,
+            })
+            .collect()
+    }
+
+    /// Computes the concave hull of the provided point cloud, along with a triangulation of its interior
+    ///
+    /// This bundles together everything needed to draw the hull, filled and outlined, in one call.
+    /// The outline is exactly the hull returned by [`concave_hull`], and the triangles exactly cover it,
+    /// with `n - 2` triangles for a hull of `n` vertices.
+    /// Hulls with fewer than three vertices have no triangles.
+    ///
+    /// Triangulation is O(n³) in the number of hull vertices in the worst case, though it's usually much faster.
+    pub fn hull_mesh(points: &[Point], concavity: f64) -> HullMesh {
+        let (outline, vertices): (Vec<usize>, Vec<Point>) =
+            concave_hull(points, concavity).into_iter().unzip();
+        let triangles = ear_clip(&vertices);
+
+        HullMesh {
+            vertices,
+            triangles,
+            outline,
+        }
+    }
+
+    /// Approximates the medial axis of a hull, for analyzing its shape
+    ///
+    /// The medial axis (or skeleton) is the set of points inside the hull with more than one nearest point on its boundary,
+    /// which traces out a spine along the middle of each part of the hull, with branches into its corners.
+    ///
+    /// This is only an approximation: the boundary is resampled with at most `sampling` between samples,
+    /// and the skeleton is the part of the samples' Voronoi diagram inside the hull, found from their Delaunay triangulation.
+    /// It converges to the true medial axis as `sampling` shrinks, but the triangulation is O(m²) in the number of samples,
+    /// so `sampling` should only be small enough to resolve the narrowest parts of the hull.
+    /// Segments with an end outside of the hull, and the very tips of branches into corners, are pruned.
+    ///
+    /// Returns the skeleton as unordered line segments, sorted by their coordinates so that the output is deterministic.
+    /// Hulls with fewer than three points, or a `sampling` which isn't positive, have no skeleton.
+    #[cfg(feature = "delaunay")]
+    pub fn hull_skeleton(hull: &[(usize, Point)], sampling: f64) -> Vec<(Point, Point)> {
+        if is_empty_hull(hull) {
+            return Vec::new();
+        }
+
+        crate::skeleton::hull_skeleton(hull, sampling)
+    }
+
+    /// Thickens the boundary of a hull returned by [`concave_hull`] into a band `width` wide, for drawing it with emphasis
+    ///
+    /// Returns the outer and inner rings of the band, which are the hull offset outwards and inwards by half of `width`,
+    /// with mitred corners. The band is the region between them, centred on the hull's boundary.
+    /// Both rings have one point per hull vertex, in the same order as the hull.
+    ///
+    /// Where the hull is too narrow for the full offset (like a tight concavity, for the outer ring, or a thin spike, for the inner ring),
+    /// the offset is clamped at each corner so that the ring doesn't fold over itself, and the band is thinner there instead.
+    /// Very sharp corners have their mitres cut off at twice `width`.
+    /// Hulls with fewer than three points have no band.
+    pub fn hull_band(hull: &[(usize, Point)], width: f64) -> (Vec<Point>, Vec<Point>) {
+        if is_empty_hull(hull) || hull.len() < 3 {
+            return (Vec::new(), Vec::new());
+        }
+
+        let ring: Vec<Point> = hull.iter().map(|(_, p)| *p).collect();
+        (
+            crate::offset::offset_ring(&ring, width / 2.),
+            crate::offset::offset_ring(&ring, -width / 2.),
+        )
+    }
+
+    /// Checks that a hull (possibly from another source) is a valid concave hull of the provided point cloud
+    ///
+    /// A valid hull:
+    /// - Only has vertices which are points in the point cloud, each paired with its index, and each appearing once
+    /// - Is a simple polygon: none of its edges intersect, except for neighbouring edges at their shared endpoint
+    /// - Winds counter-clockwise
+    /// - Contains every point in the point cloud, either inside of it or on its boundary
+    ///
+    /// Every hull returned by [`concave_hull`] is valid (as long as the point cloud has no repeat points).
+    /// Hulls with fewer than three vertices are valid if every point in the cloud is one of their vertices, or lies on them.
+    /// If the hull is invalid, the first failed check is returned.
+    pub fn validate_hull(
+        points: &[Point],
+        hull: &[(usize, Point)],
+    ) -> Result<(), HullValidationError> {
+        crate::validate::validate_hull(points, hull)
+    }
+
+    /// Computes the perimeter of a hull returned by [`concave_hull`]
+    ///
+    /// The hull is treated as a closed ring, so the edge from the last point back to the first is included.
+    /// Empty hulls have a perimeter of zero.
+    pub fn perimeter(hull: &[(usize, Point)]) -> f64 {
+        if is_empty_hull(hull) {
+            return 0.;
+        }
+
+        metrics::perimeter(hull.iter().map(|(_, p)| *p))
+    }
+
+    /// Computes the area enclosed by a hull returned by [`concave_hull`]
+    ///
+    /// The hull is treated as a closed ring, so the edge from the last point back to the first is included.
+    /// Hulls with fewer than three points have an area of zero.
+    pub fn area(hull: &[(usize, Point)]) -> f64 {
+        if is_empty_hull(hull) {
+            return 0.;
+        }
+
+        metrics::signed_area(hull.iter().map(|(_, p)| *p)).abs()
+    }
+
+    /// Computes the area scooped out of the convex hull by the concave hull
+    ///
+    /// This is the area which is inside of the convex hull of the point cloud, but outside of its concave hull:
+    /// the total area of the notches dug into the shape.
+    /// It is zero when the concave hull is convex (such as when `concavity` is infinite),
+    /// and grows as the hull digs deeper into the point cloud.
+    ///
+    /// See [`concave_hull`] for details on the inputs.
+    pub fn concavity_area(points: &[Point], concavity: f64) -> f64 {
+        let convex = convex_hull_idx(points);
+        let hull = concave_hull_inner(points, &ConcaveHullBuilder::new(concavity), &convex).hull;
+        let convex_area = metrics::signed_area(convex.iter().map(|&id| points[id])).abs();
+
+        // The concave hull is inside of the convex hull, so this is never negative (barring floating point error)
+        (convex_area - area(&hull)).max(0.)
+    }
+
+    /// Finds the reflex corners of a hull returned by [`concave_hull`]
+    ///
+    /// Reflex corners have an interior angle greater than 180 degrees, and are the defining feature of concavity:
+    /// a hull is convex exactly when it has no reflex corners.
+    /// Straight corners (with an interior angle of exactly 180 degrees) are not reflex.
+    ///
+    /// Returns the original index of each reflex corner, in the same order as `hull`.
+    pub fn reflex_vertices(hull: &[(usize, Point)]) -> Vec<usize> {
+        if is_empty_hull(hull) {
+            return Vec::new();
+        }
+
+        metrics::reflex_vertices(hull)
+    }
+
+    /// Computes the concave hull of the provided point cloud, with the discrete curvature at each vertex
+    ///
+    /// The curvature is the signed turning angle at the vertex, in radians: the angle between the edge coming into it
+    /// and the edge going out of it, walking the hull in its usual counter-clockwise order.
+    /// It is zero where the hull runs straight, positive at convex corners, and negative at reflex corners,
+    /// and is at most π in either direction, so sharp corners have large magnitudes.
+    /// The curvatures of a hull always sum to 2π (barring floating point error).
+    ///
+    /// Otherwise, this is identical to [`concave_hull`].
+    /// Degenerate hulls, with fewer than three points, have zero curvature everywhere.
+    pub fn hull_with_curvature(points: &[Point], concavity: f64) -> Vec<(usize, Point, f64)> {
+        let hull = concave_hull(points, concavity);
+        let ring: Vec<Point> = hull.iter().map(|(_, p)| *p).collect();
+
+        hull.into_iter()
+            .zip(metrics::turning_angles(&ring))
+            .map(|((id, p), angle)| (id, p, angle))
+            .collect()
+    }
+
+    /// Removes tiny reflex corners from a hull returned by [`concave_hull`], which are usually numerical noise
+    ///
+    /// Nearly straight runs of points can zigzag very slightly, leaving reflex corners which only turn by a fraction of a degree.
+    /// These aren't real concave features, but they still count towards [`reflex_vertices`] and [`hull_with_curvature`].
+    /// This drops every reflex corner that turns by less than `max_micro_angle` (in radians), and joins its neighbours directly,
+    /// so that the sharper reflex corners of genuine notches are left alone.
+    /// Removing a corner changes the turns of its neighbours, so this repeats until there are no tiny reflex corners left.
+    ///
+    /// Removing a reflex corner only adds area to the hull, so every point in the cloud stays inside of it.
+    /// Convex corners are never removed, no matter how sharp or shallow.
+    /// This is the opposite of [`ConcaveHullBuilder::max_edge_ratio`], which removes sharp convex spikes out to outliers.
+    ///
+    /// Hulls with three or fewer points are returned as-is, and a triangle is always left behind.
+    pub fn repair_micro_reflex(
+        hull: &[(usize, Point)],
+        max_micro_angle: f64,
+    ) -> Vec<(usize, Point)> {
+        if is_empty_hull(hull) {
+            return Vec::new();
+        }
+
+        remove_micro_reflex(hull.to_vec(), max_micro_angle)
+    }
+
+    /// Measures how "crinkly" a hull returned by [`concave_hull`] is
+    ///
+    /// This is the [`perimeter`] of the hull, divided by the perimeter of its convex hull.
+    /// The convex hull is used for normalization (rather than, say, the bounding box),
+    /// since it is the shortest ring that encloses the point cloud.
+    /// This means that the result is exactly `1` for a convex hull, and grows as the hull digs deeper into the point cloud.
+    /// Because it is a ratio, it can be compared across datasets of different scales.
+    ///
+    /// Hulls with fewer than three points have a complexity of `1`.
+    pub fn boundary_complexity(hull: &[(usize, Point)]) -> f64 {
+        if is_empty_hull(hull) || hull.len() < 3 {
+            return 1.;
+        }
+
+        let points: Vec<Point> = hull.iter().map(|(_, p)| *p).collect();
+        let convex = parry2d::transformation::convex_hull(&points);
+
+        metrics::boundary_complexity(hull, &convex)
+    }
+
+    /// Finds the minimum-area oriented bounding box of a hull returned by [`concave_hull`]
+    ///
+    /// Unlike an axis-aligned bounding box, an oriented bounding box can rotate to fit the hull as tightly as possible,
+    /// which is useful for aligning shapes with each other, or for cheap culling.
+    /// The box only depends on the convex hull of the hull's points, so the concavity used to compute the hull doesn't matter.
+    ///
+    /// Returns the center of the box, its two axes, and its half-extent along each axis.
+    /// The axes are perpendicular unit vectors, and the first axis is parallel to one of the edges of the convex hull.
+    /// A single point gets a box with zero extent, aligned with the coordinate axes, and an empty hull gets that same box at the origin.
+    pub fn hull_obb(hull: &[(usize, Point)]) -> (Point, [Vector; 2], [f64; 2]) {
+        let points: Vec<Point> = hull.iter().map(|(_, p)| *p).collect();
+        if is_empty_hull(hull) || points.len() < 3 {
+            // Too few points for parry, but they are their own convex hull anyway
+            return metrics::oriented_bounding_box(&points);
+        }
+
+        let convex = parry2d::transformation::convex_hull(&points);
+        metrics::oriented_bounding_box(&convex)
+    }
+
+    /// Finds the smallest circle enclosing a hull returned by [`concave_hull`], for culling and broad-phase tests
+    ///
+    /// Like [`hull_obb`], the circle only depends on the convex hull of the hull's points,
+    /// so only the convex hull's vertices are considered, and the circle is found with Welzl's algorithm.
+    /// The circle passes through two or three of those vertices, and is O(h) to find on average.
+    ///
+    /// Returns the center and radius of the circle.
+    /// A single point gets a circle with zero radius, and an empty hull gets that same circle at the origin.
+    pub fn hull_bounding_circle(hull: &[(usize, Point)]) -> (Point, f64) {
+        let points: Vec<Point> = hull.iter().map(|(_, p)| *p).collect();
+        if is_empty_hull(hull) || points.len() < 3 {
+            // Too few points for parry, but they are their own convex hull anyway
+            return metrics::bounding_circle(&points);
+        }
+
+        let convex = parry2d::transformation::convex_hull(&points);
+        metrics::bounding_circle(&convex)
+    }
+
+    /// Finds the axis-aligned bounding box of a hull returned by [`concave_hull`]
+    ///
+    /// The hull always includes every point of the convex hull, so this is also the bounding box of the whole point cloud
+    /// (unless points were left out, such as by [`ConcaveHullBuilder::max_edge_ratio`]).
+    /// Hulls are usually much smaller than their point clouds, so this is cheaper than bounding the cloud directly.
+    ///
+    /// An empty hull gets parry's invalid bounding box, with `mins` above `maxs`, which contains nothing.
+    pub fn hull_aabb(hull: &[(usize, Point)]) -> parry2d::bounding_volume::Aabb {
+        if is_empty_hull(hull) {
+            return parry2d::bounding_volume::Aabb::new_invalid();
+        }
+
+        parry2d::bounding_volume::details::local_point_cloud_aabb(hull.iter().map(|(_, p)| p))
+    }
+
+    /// Rasterizes a hull returned by [`concave_hull`] into a filled, row-major boolean mask
+    ///
+    /// `transform` maps each hull point into mask coordinates, where the cell in column `x` and row `y`
+    /// covers the unit square from `(x, y)` to `(x + 1, y + 1)`, and is at index `y * width + x` in the mask.
+    /// For example, `|p| Point::new((p.x - min_x) / cell_size, (p.y - min_y) / cell_size)`
+    /// lays a grid of `cell_size` cells over the point cloud, starting from `(min_x, min_y)`.
+    ///
+    /// A cell is `true` if its center is inside of the hull.
+    /// Parts of the hull outside of the mask are ignored, and hulls with fewer than three points fill nothing.
+    /// Concave hulls are handled correctly, since each row is scan-filled between the points where the hull crosses it.
+    pub fn hull_mask(
+        hull: &[(usize, Point)],
+        width: usize,
+        height: usize,
+        transform: impl Fn(&Point) -> Point,
+    ) -> Vec<bool> {
+        if is_empty_hull(hull) {
+            return vec![false; width * height];
+        }
+
+        let ring: Vec<Point> = hull.iter().map(|(_, p)| transform(p)).collect();
+        raster::scan_fill(&ring, width, height)
+    }
+
+    /// Computes the intersection over union between a hull and a known true boundary, as a measure of the hull's accuracy
+    ///
+    /// This is the area covered by both the hull and `truth`, divided by the area covered by either of them.
+    /// It is one when the hull matches `truth` exactly, and zero when they don't overlap at all,
+    /// which makes it the standard way to score shape reconstruction.
+    /// If neither has any area (including when the hull is empty), it is zero.
+    ///
+    /// `truth` must be a simple polygon, and can wind either way.
+    /// The overlap is computed exactly, by splitting `truth` into triangles and clipping the hull against each of them,
+    /// which is O(h·t) in the number of hull and truth points.
+    pub fn iou_with(hull: &[(usize, Point)], truth: &[Point]) -> f64 {
+        if is_empty_hull(hull) {
+            return 0.;
+        }
+
+        let hull: Vec<Point> = hull.iter().map(|(_, p)| *p).collect();
+        clip::intersection_over_union(&hull, truth).0
+    }
+
+    /// Computes the area covered by exactly one of a hull and a known true boundary
+    ///
+    /// This is the area of the union minus the area of the overlap: the total area that the hull gets wrong,
+    /// either by covering area outside of `truth`, or by missing area inside of it.
+    /// See [`iou_with`] for the requirements on `truth`.
+    /// An empty hull misses all of `truth`, so its symmetric difference is the area of `truth`.
+    pub fn symmetric_difference_area(hull: &[(usize, Point)], truth: &[Point]) -> f64 {
+        if is_empty_hull(hull) {
+            return metrics::signed_area(truth.iter().copied()).abs();
+        }
+
+        let hull: Vec<Point> = hull.iter().map(|(_, p)| *p).collect();
+        clip::intersection_over_union(&hull, truth).1
+    }
+
+    /// Computes the symmetric Hausdorff distance between two hulls returned by [`concave_hull`]
+    ///
+    /// This is the furthest that any vertex of either hull is from the boundary of the other hull.
+    /// It is zero when the hulls trace the same shape, and grows with the largest local disagreement between them,
+    /// which makes it a good way to compare hulls computed with different options.
+    /// Hulls which start from different points, but are otherwise the same, have a distance of zero.
+    ///
+    /// If either hull is empty, the distance is zero.
+    pub fn hausdorff_distance(a: &[(usize, Point)], b: &[(usize, Point)]) -> f64 {
+        if is_empty_hull(a) || is_empty_hull(b) {
+            return 0.;
+        }
+
+        let a: Vec<Point> = a.iter().map(|(_, p)| *p).collect();
+        let b: Vec<Point> = b.iter().map(|(_, p)| *p).collect();
+
+        metrics::hausdorff_distance(&a, &b)
+    }
+
+    /// Measures how stable the concave hull of the provided point cloud is under small random perturbations
+    ///
+    /// Each of the `trials` offsets every point by a random amount, up to `jitter` along each axis,
+    /// and computes the concave hull of the perturbed points.
+    /// The result is the mean [`hausdorff_distance`] between those hulls and the hull of the unperturbed points.
+    /// Hulls are compared using the original positions of their points, so the jitter itself doesn't count,
+    /// only changes to which points end up on the hull.
+    /// A result of zero means that the jitter never changed the hull at all.
+    ///
+    /// The perturbations are generated from `seed`, so the same inputs always give the same result.
+    /// See [`concave_hull`] for details on the other inputs.
+    /// Returns zero if there are no trials.
+    pub fn hull_stability(
+        points: &[Point],
+        concavity: f64,
+        jitter: f64,
+        trials: usize,
+        seed: u64,
+    ) -> f64 {
+        crate::stability::hull_stability(points, jitter, trials, seed, |points| {
+            concave_hull(points, concavity)
+        })
+    }
+
+    /// Converts a hull returned by [`concave_hull`] to a [`geo_types::Polygon`]
+    ///
+    /// The hull becomes the exterior ring of the polygon (which `geo-types` closes by repeating the first point),
+    /// and the polygon has no interior rings.
+    /// The indices of the hull points are discarded, and an empty hull becomes an empty polygon.
+    #[cfg(feature = "geo-types")]
+    pub fn hull_to_polygon(hull: &[(usize, Point)]) -> geo_types::Polygon<f64> {
+        if is_empty_hull(hull) {
+            return geo_types::Polygon::new(geo_types::LineString::new(Vec::new()), Vec::new());
+        }
+
+        let exterior: Vec<geo_types::Coord<f64>> = hull
+            .iter()
+            .map(|(_, p)| geo_types::coord! { x: p.x, y: p.y })
+            .collect();
+
+        geo_types::Polygon::new(exterior.into(), Vec::new())
+    }
+
+    /// Converts the exterior ring of a [`geo_types::Polygon`] to a list of points
+    ///
+    /// This is the inverse of [`hull_to_polygon`]: the closing point is dropped,
+    /// so the points are in the same format as the points of a hull returned by [`concave_hull`].
+    /// Interior rings are ignored.
+    #[cfg(feature = "geo-types")]
+    pub fn polygon_to_points(polygon: &geo_types::Polygon<f64>) -> Vec<Point> {
+        let exterior = polygon.exterior();
+        let mut points: Vec<Point> = exterior.coords().map(|c| Point::new(c.x, c.y)).collect();
+        if exterior.is_closed() {
+            points.pop();
+        }
+
+        points
+    }
+
+    /// Writes a hull returned by [`concave_hull`] to a polygon shapefile, for use in GIS tools
+    ///
+    /// Three files are written next to `path`: the shapes (`.shp`), their index (`.shx`), and the attribute table (`.dbf`).
+    /// The extension of `path` is replaced for each file, so passing either `hull.shp` or `hull` writes all three.
+    ///
+    /// The hull is written as a single polygon, closed by repeating its first point at the end.
+    /// Shapefiles wind exterior rings clockwise (the opposite of GeoJSON, and of the hulls returned by [`concave_hull`]),
+    /// so the ring is reversed as needed.
+    /// The attribute table has a single numeric `ID` field, which is zero for the hull.
+    ///
+    /// An empty hull is written as a single null shape, which GIS tools read as a record without any geometry.
+    /// Hulls with one or two points aren't polygons, so they are rejected with [`std::io::ErrorKind::InvalidInput`].
+    #[cfg(feature = "shapefile")]
+    pub fn write_hull_shapefile(
+        path: impl AsRef<std::path::Path>,
+        hull: &[(usize, Point)],
+    ) -> std::io::Result<()> {
+        crate::shapefile::write_hull_shapefile(path.as_ref(), hull)
+    }
+
+    /// Lazily reads a point cloud from CSV data
+    ///
+    /// Records are read and parsed one at a time, so very large files can be streamed
+    /// (or filtered, or stopped early) without holding the whole file in memory.
+    /// Each item is either a point, or the reason that record could not be read.
+    /// Reading continues after a bad record, so collecting into a [`Result`] stops at the first error,
+    /// while filtering out the errors skips bad records.
+    ///
+    /// See [`CsvOptions`] for the supported layouts.
+    #[cfg(feature = "csv")]
+    pub fn read_points_csv<R: std::io::Read>(
+        reader: R,
+        options: &CsvOptions,
+    ) -> impl Iterator<Item = Result<Point, CsvPointError>> + use<R> {
+        crate::points_csv::read_points_csv(reader, options)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::f32::*;
+
+    mod small_clouds {
+        use super::*;
+
+        /// An array of points in a numpad grid, in numpad order
+        ///
+        /// 7 8 9
+        /// 4 5 6
+        /// 1 2 3
+        /// 0
+        const POINTS: [Point; 10] = [
+            Point::new(0., 0.),
+            Point::new(0., 1.),
+            Point::new(1., 1.),
+            Point::new(2., 1.),
+            Point::new(0., 2.),
+            Point::new(1., 2.),
+            Point::new(2., 2.),
+            Point::new(0., 3.),
+            Point::new(1., 3.),
+            Point::new(2., 3.),
+        ];
+
+        /// Tiny clouds keep their edges in a list rather than a heap, which must not change the hull
+        #[test]
+        fn small_queue_matches_heap() {
+            for len in 4..crate::concave::SMALL_CLOUD_POINTS {
+                for start in 0..=POINTS.len() - len {
+                    let points = &POINTS[start..start + len];
+                    for concavity in [0., 1., 1.5, f32::INFINITY] {
+                        // The small cloud queue only applies with stable ordering
+                        let builder = ConcaveHullBuilder::new(concavity).stable_ordering(true);
+                        assert_eq!(
+                            builder.compute(points),
+                            builder.clone().small_cloud_queue(false).compute(points)
+                        );
+                    }
+                }
+            }
+        }
+
+        #[test]
+        #[cfg(feature = "half")]
+        fn half_precision() {
+            use half::f16;
+
+            let halves: Vec<[f16; 2]> = POINTS
+                .iter()
+                .map(|p| [f16::from_f32(p.x), f16::from_f32(p.y)])
+                .collect();
+
+            // Every coordinate is exact at f16 precision, so the hulls should match exactly
+            for concavity in [0., 1., 2., f32::INFINITY] {
+                assert_eq!(
+                    concave_hull_generic(&halves, concavity),
+                    concave_hull(&POINTS, concavity)
+                );
+            }
+        }
+
+        #[test]
+        fn zero_points() {
+            let hull = concave_hull(&POINTS[0..0], 10.);
+            assert_eq!(hull, Vec::new());
+        }
+
+        #[test]
+        fn one_point() {
+            let hull = concave_hull(&POINTS[0..1], 10.);
+            assert_eq!(hull, Vec::from([(0, POINTS[0])]));
+        }
+
+        #[test]
+        fn two_points() {
+            let hull = concave_hull(&POINTS[0..2], 10.);
+            assert_eq!(hull, Vec::from([(0, POINTS[0]), (1, POINTS[1])]));
+        }
+
+        #[test]
+        fn zero_points_closed() {
+            let hull = ConcaveHullBuilder::new(10.)
+                .close_degenerate(true)
+                .compute(&POINTS[0..0]);
+            assert_eq!(hull, Vec::new());
+        }
+
+        #[test]
+        fn one_point_closed() {
+            let hull = ConcaveHullBuilder::new(10.)
+                .close_degenerate(true)
+                .compute(&POINTS[0..1]);
+            assert_eq!(hull, Vec::from([(0, POINTS[0]), (0, POINTS[0])]));
+        }
+
+        #[test]
+        fn anytime_degenerate() {
+            let points = [Point::new(0., 0.), Point::new(1., 0.)];
+            let hulls: Vec<_> = concave_hull_anytime(&points, 0.).collect();
+
+            assert_eq!(hulls, Vec::from([concave_hull(&points, 0.)]));
+        }
+
+        #[test]
+        fn two_points_closed() {
+            let hull = ConcaveHullBuilder::new(10.)
+                .close_degenerate(true)
+                .compute(&POINTS[0..2]);
+            assert_eq!(
+                hull,
+                Vec::from([(0, POINTS[0]), (1, POINTS[1]), (0, POINTS[0])])
+            );
+        }
+
+        #[test]
+        fn three_points() {
+            // Counter-clockwise, starting from the bottom left, regardless of the order that parry finds them in
+            let hull = concave_hull(&POINTS[0..3], 10.);
+            assert_eq!(
+                hull,
+                Vec::from([(0, POINTS[0]), (2, POINTS[2]), (1, POINTS[1]),])
+            );
+
+            // Reversing the cloud doesn't change the hull, only the indices
+            let reversed = [POINTS[2], POINTS[1], POINTS[0]];
+            assert_eq!(
+                concave_hull(&reversed, 10.),
+                Vec::from([(2, POINTS[0]), (0, POINTS[2]), (1, POINTS[1])])
+            );
+        }
+
+        #[test]
+        fn square() {
+            let hull = concave_hull(&[POINTS[1], POINTS[2], POINTS[4], POINTS[5]], 10.);
+            assert_eq!(
+                hull,
+                Vec::from([
+                    (2, POINTS[4]),
+                    (0, POINTS[1]),
+                    (1, POINTS[2]),
+                    (3, POINTS[5]),
+                ])
+            );
+        }
+
+        #[test]
+        fn square_stable() {
+            // Ties between edges are broken by their coordinates,
+            // so the edge from the bottom left is always finalized last, and the hull is sorted from there
+            let hull = ConcaveHullBuilder::new(10.)
+                .stable_ordering(true)
+                .compute(&[POINTS[1], POINTS[2], POINTS[4], POINTS[5]]);
+            assert_eq!(
+                hull,
+                Vec::from([
+                    (0, POINTS[1]),
+                    (1, POINTS[2]),
+                    (3, POINTS[5]),
+                    (2, POINTS[4]),
+                ])
+            );
+        }
+    }
+
+    mod bounding_box {
+        use super::*;
+
+        #[test]
+        fn square_circle() {
+            // A 4x4 square, with some points inside
+            let corners = [
+                Point::new(1., 1.),
+                Point::new(5., 1.),
+                Point::new(5., 5.),
+                Point::new(1., 5.),
+            ];
+            let mut points = corners.to_vec();
+            points.extend([Point::new(3., 3.), Point::new(2., 4.), Point::new(4.5, 1.5)]);
+
+            let (center, radius) = hull_bounding_circle(&concave_hull(&points, 1.));
+            assert!((center - Point::new(3., 3.)).norm() < 1e-5);
+            assert!((radius - 8_f32.sqrt()).abs() < 1e-5);
+
+            // The circle passes through every corner
+            for corner in corners {
+                assert!(((corner - center).norm() - radius).abs() < 1e-5);
+            }
+
+            // Single points get a circle with no radius
+            assert_eq!(
+                hull_bounding_circle(&[(0, Point::new(2., 3.))]),
+                (Point::new(2., 3.), 0.)
+            );
+        }
+
+        #[test]
+        fn rotated_rectangle() {
+            // A 8 by 2 rectangle, rotated by 30 degrees around (5, 5), with some points inside
+            let angle = std::f32::consts::FRAC_PI_6;
+            let direction = Vector::new(angle.cos(), angle.sin());
+            let normal = Vector::new(-direction.y, direction.x);
+            let center = Point::new(5., 5.);
+            let points: Vec<Point> = [
+                (-4., -1.),
+                (4., -1.),
+                (4., 1.),
+                (-4., 1.),
+                (0., 0.),
+                (2., 0.5),
+                (-3., -0.5),
+            ]
+            .into_iter()
+            .map(|(u, v)| center + direction * u + normal * v)
+            .collect();
+
+            let (obb_center, axes, half_extents) = hull_obb(&concave_hull(&points, 1.));
+            assert!((obb_center - center).norm() < 1e-4);
+
+            // The long axis of the box must line up with the long side of the rectangle
+            let long = if half_extents[0] > half_extents[1] {
+                0
+            } else {
+                1
+            };
+            assert!((half_extents[long] - 4.).abs() < 1e-4);
+            assert!((half_extents[1 - long] - 1.).abs() < 1e-4);
+            assert!((axes[long].dot(&direction).abs() - 1.).abs() < 1e-4);
+            assert!(axes[0].dot(&axes[1]).abs() < 1e-4);
+        }
+    }
+
+    mod large {
+        use std::time::{Duration, Instant};
+
+        use super::*;
+        use crate::{
+            concave::concave_hull_queued_inner,
+            queue::{DefaultQueue, LinearQueue},
+            rng::SplitMix64,
+        };
+
+        /// Generates `n` pseudo-random points in a 1000x1000 square, using a fixed seed
+        fn random_points(n: usize) -> Vec<Point> {
+            let mut rng = SplitMix64::new(0x5EED);
+            let mut next = || rng.next_f64() as f32 * 1000.;
+
+            (0..n).map(|_| Point::new(next(), next())).collect()
+        }
+
+        /// Catches accidental quadratic regressions on large inputs
+        ///
+        /// This is slow, especially in debug builds, so it's ignored by default.
+        /// Run it with `cargo test --release -- --ignored`.
+        #[test]
+        #[ignore]
+        fn million_points() {
+            let points = random_points(1_000_000);
+
+            let start = Instant::now();
+            let hull = concave_hull(&points, 100.);
+            let elapsed = start.elapsed();
+
+            assert!(hull.len() >= 3);
+            assert!(
+                elapsed < Duration::from_secs(60),
+                "Took {elapsed:?} to hull a million points"
+            );
+        }
+
+        /// Large hulls check for intersections in parallel, which must not change the hull
+        #[test]
+        #[cfg(feature = "rayon")]
+        fn parallel_checks() {
+            // A concavity of zero digs as deep as possible, so about half of the points end up on the hull
+            let points = random_points(200);
+            let builder = ConcaveHullBuilder::new(0.);
+
+            let hull = builder.compute(&points);
+            assert!(hull.len() > crate::concave::PARALLEL_CHECK_EDGES);
+            assert_eq!(
+                hull,
+                builder.clone().parallel_checks(false).compute(&points)
+            );
+        }
+
+        #[test]
+        fn f64_metrics() {
+            // A 1000x500 rectangle, far enough from the origin that the f32 shoelace products lose most of their precision
+            let (x, y) = (1_000_000., 2_000_000.);
+            let points = [
+                Point::new(x, y),
+                Point::new(x + 1000., y),
+                Point::new(x + 1000., y + 500.),
+                Point::new(x, y + 500.),
+                Point::new(x + 250., y + 100.),
+                Point::new(x + 750., y + 400.),
+            ];
+
+            let (hull, area_f64, perimeter_f64) =
+                concave_hull_with_metrics_f64(&points, f32::INFINITY);
+            assert_eq!(hull, concave_hull(&points, f32::INFINITY));
+
+            let error_f32 = (area(&hull) as f64 - 500_000.).abs();
+            let error_f64 = (area_f64 - 500_000.).abs();
+            assert!(error_f64 < error_f32);
+            assert_eq!(area_f64, 500_000.);
+            assert_eq!(perimeter_f64, 3000.);
+        }
+
+        /// Queued edges are totally ordered, so any queue pops them in the same order as the heap
+        #[test]
+        fn other_queues_match() {
+            let points = random_points(500);
+            let convex = parry2d::transformation::convex_hull_idx(&points);
+
+            // Only stable ordering gives the queues a total order to agree on
+            for concavity in [0., 10., 40., f32::INFINITY] {
+                let options = ConcaveHullBuilder::new(concavity).stable_ordering(true);
+                assert_eq!(
+                    concave_hull_queued_inner::<f32, LinearQueue<f32>>(&points, &options, &convex)
+                        .hull,
+                    concave_hull_queued_inner::<f32, DefaultQueue<f32>>(&points, &options, &convex)
+                        .hull
+                );
+            }
+        }
+    }
+
+    mod stability {
+        use super::*;
+        use crate::rng::SplitMix64;
+
+        #[test]
+        fn hausdorff_rotation() {
+            let square = concave_hull(
+                &[
+                    Point::new(0., 0.),
+                    Point::new(2., 0.),
+                    Point::new(2., 2.),
+                    Point::new(0., 2.),
+                ],
+                f32::INFINITY,
+            );
+            let mut rotated = square.clone();
+            rotated.rotate_left(1);
+            assert_eq!(hausdorff_distance(&square, &rotated), 0.);
+
+            let notched = [
+                (0, Point::new(0., 0.)),
+                (1, Point::new(2., 0.)),
+                (4, Point::new(1., 1.5)),
+                (2, Point::new(2., 2.)),
+                (3, Point::new(0., 2.)),
+            ];
+            assert_eq!(hausdorff_distance(&square, &notched), 0.5);
+        }
+
+        #[test]
+        fn dense_more_stable() {
+            // A regular grid, with every point well away from its neighbours
+            let dense: Vec<Point> = (0..20)
+                .flat_map(|x| (0..20).map(move |y| Point::new(x as f32, y as f32)))
+                .collect();
+
+            // Fewer points over the same area, placed at random
+            let mut rng = SplitMix64::new(7);
+            let sparse: Vec<Point> = (0..40)
+                .map(|_| {
+                    let x = (rng.next_f64() * 19.) as f32;
+                    let y = (rng.next_f64() * 19.) as f32;
+                    Point::new(x, y)
+                })
+                .collect();
+
+            let dense_stability = hull_stability(&dense, 2., 0.2, 8, 1);
+            let sparse_stability = hull_stability(&sparse, 2., 0.2, 8, 1);
+            assert!(dense_stability < sparse_stability);
+
+            assert_eq!(hull_stability(&sparse, 2., 0.2, 0, 1), 0.);
+        }
+    }
+
+    mod edge_points {
+        use super::*;
+
+        /// A square, with points on (or very nearly on) two of its edges, and one in the middle
+        const POINTS: [Point; 7] = [
+            Point::new(0., 0.),
+            Point::new(4., 0.),
+            Point::new(4., 4.),
+            Point::new(0., 4.),
+            Point::new(2., 0.),
+            Point::new(3., 3.9999),
+            Point::new(2., 2.),
+        ];
+
+        /// Asserts that `id` is on the hull, between `prev` and `next`
+        fn assert_between(hull: &[(usize, Point)], prev: usize, id: usize, next: usize) {
+            let pos = hull.iter().position(|(i, _)| *i == id).unwrap();
+            assert_eq!(hull[(pos + hull.len() - 1) % hull.len()].0, prev);
+            assert_eq!(hull[(pos + 1) % hull.len()].0, next);
+        }
+
+        #[test]
+        fn left_on_edges_by_default() {
+            // The edges are too short to split, so only the corners are on the hull
+            assert_eq!(concave_hull(&POINTS, 10.).len(), 4);
+        }
+
+        #[test]
+        fn splits_edges() {
+            let hull = ConcaveHullBuilder::new(10.)
+                .include_edge_points(1e-3)
+                .compute(&POINTS);
+
+            assert_eq!(hull.len(), 6);
+            assert_between(&hull, 0, 4, 1);
+            assert_between(&hull, 2, 5, 3);
+        }
+    }
+
+    mod validation {
+        use super::*;
+
+        /// A square, with a point in the middle
+        const POINTS: [Point; 5] = [
+            Point::new(0., 0.),
+            Point::new(4., 0.),
+            Point::new(4., 4.),
+            Point::new(0., 4.),
+            Point::new(2., 2.),
+        ];
+
+        fn hull_of(ids: &[usize]) -> Vec<(usize, Point)> {
+            ids.iter().map(|&id| (id, POINTS[id])).collect()
+        }
+
+        #[test]
+        fn valid() {
+            assert_eq!(validate_hull(&POINTS, &hull_of(&[0, 1, 2, 3])), Ok(()));
+            assert_eq!(validate_hull(&POINTS, &concave_hull(&POINTS, 0.)), Ok(()));
+        }
+
+        #[test]
+        fn unknown_vertex() {
+            let mut hull = hull_of(&[0, 1, 2, 3]);
+            hull[3].1 = Point::new(1., 1.);
+
+            assert_eq!(
+                validate_hull(&POINTS, &hull),
+                Err(HullValidationError::UnknownVertex { position: 3 })
+            );
+        }
+
+        #[test]
+        fn reversed_winding() {
+            assert_eq!(
+                validate_hull(&POINTS, &hull_of(&[3, 2, 1, 0])),
+                Err(HullValidationError::NotCounterClockwise)
+            );
+        }
+
+        #[test]
+        fn missing_point() {
+            // Cuts the square in half along the diagonal, leaving the top left corner out
+            assert_eq!(
+                validate_hull(&POINTS, &hull_of(&[0, 1, 2, 4])),
+                Err(HullValidationError::PointOutside { index: 3 })
+            );
+        }
+
+        #[test]
+        fn self_intersecting() {
+            // A bowtie, which crosses itself in the middle
+            assert_eq!(
+                validate_hull(&POINTS, &hull_of(&[0, 1, 3, 2])),
+                Err(HullValidationError::SelfIntersecting {
+                    first: 1,
+                    second: 3
+                })
+            );
+        }
+    }
+
+    mod checked {
+        use super::*;
+
+        #[test]
+        fn zero_width() {
+            let points: Vec<Point> = (0..5).map(|y| Point::new(3., y as f32)).collect();
+
+            assert_eq!(
+                try_concave_hull(&points, 1.),
+                Err(ConcaveHullError::DegenerateExtent)
+            );
+        }
+
+        #[test]
+        fn normal_cloud() {
+            let points = [
+                Point::new(0., 0.),
+                Point::new(4., 0.),
+                Point::new(4., 3.),
+                Point::new(0., 3.),
+                Point::new(2., 1.),
+            ];
+
+            assert_eq!(try_concave_hull(&points, 1.), Ok(concave_hull(&points, 1.)));
+            assert_eq!(
+                ConcaveHullBuilder::new(1.)
+                    .max_aspect_ratio(1.2)
+                    .try_compute(&points),
+                Err(ConcaveHullError::DegenerateExtent)
+            );
+        }
+
+        #[test]
+        fn all_nan() {
+            let points = [Point::new(f32::NAN, f32::NAN); 10];
+
+            assert_eq!(try_concave_hull(&points, 1.), Ok(Vec::new()));
+        }
+
+        #[test]
+        fn one_finite_point() {
+            let mut points = [Point::new(f32::NAN, 1.); 10];
+            points[6] = Point::new(2., 3.);
+            points[2] = Point::new(f32::INFINITY, 0.);
+
+            assert_eq!(try_concave_hull(&points, 1.), Ok(vec![(6, points[6])]));
+
+            // Repeats of that point are still only a single point
+            points[8] = points[6];
+            assert_eq!(try_concave_hull(&points, 1.), Ok(vec![(6, points[6])]));
+        }
+
+        #[test]
+        fn skips_nan_points() {
+            let points = [
+                Point::new(0., 0.),
+                Point::new(f32::NAN, 2.),
+                Point::new(4., 0.),
+                Point::new(4., 3.),
+                Point::new(0., 3.),
+                Point::new(2., 1.),
+            ];
+            let finite: Vec<usize> = vec![0, 2, 3, 4, 5];
+
+            assert_eq!(
+                try_concave_hull(&points, 1.),
+                Ok(concave_hull_subset(&points, &finite, 1.))
+            );
+        }
+
+        #[test]
+        fn closed_ring() {
+            let open = [
+                Point::new(0., 0.),
+                Point::new(4., 0.),
+                Point::new(2., 1.),
+                Point::new(4., 3.),
+                Point::new(0., 3.),
+            ];
+            let mut closed = open.to_vec();
+            closed.push(open[0]);
+
+            let hull = try_concave_hull(&open, 1.).unwrap();
+            assert_eq!(hull.len(), 5);
+            assert_eq!(try_concave_hull(&closed, 1.), Ok(hull));
+        }
+    }
+
+    mod degenerate_candidates {
+        use super::*;
+
+        /// A square, with a repeat of its first corner, and a point inside
+        const POINTS: [Point; 6] = [
+            Point::new(0., 0.),
+            Point::new(4., 0.),
+            Point::new(4., 4.),
+            Point::new(0., 4.),
+            Point::new(0., 0.),
+            Point::new(2., 1.),
+        ];
+
+        #[test]
+        fn never_picks_repeat_points() {
+            // The repeated corner has a zero-length vector to one end of its edges,
+            // which would otherwise make it look like the best possible candidate
+            let hull = concave_hull(&POINTS, 0.);
+
+            for k in 0..hull.len() {
+                let next = (k + 1) % hull.len();
+                assert_ne!(hull[k].1, hull[next].1, "Found a zero-length edge");
+            }
+            assert!(hull.iter().any(|(id, _)| *id == 5));
+        }
+    }
+
+    mod degenerate_convex_hulls {
+        use super::*;
+
+        /// Checks that every entry point agrees on the hull, that it's valid, and returns its indices in sorted order
+        fn hull_ids(points: &[Point]) -> Vec<usize> {
+            let hull = concave_hull(points, 0.);
+            assert_eq!(validate_hull(points, &hull), Ok(()));
+            assert_eq!(
+                concave_hull_anytime(points, 0.).last().unwrap_or_default(),
+                hull
+            );
+            assert_eq!(concave_hull_unsorted(points, 0.).len(), hull.len());
+
+            let mut ids: Vec<usize> = hull.iter().map(|(id, _)| *id).collect();
+            ids.sort_unstable();
+            ids
+        }
+
+        #[test]
+        fn no_points() {
+            assert!(hull_ids(&[]).is_empty());
+        }
+
+        #[test]
+        fn one_repeated_point() {
+            let points = [Point::new(3., 2.); 5];
+            assert_eq!(hull_ids(&points), [0]);
+        }
+
+        #[test]
+        fn collinear() {
+            // Out of order along the line, so that the ends aren't first and last
+            let points = [
+                Point::new(1., 1.),
+                Point::new(3., 3.),
+                Point::new(0., 0.),
+                Point::new(4., 4.),
+                Point::new(2., 2.),
+            ];
+            assert_eq!(hull_ids(&points), [2, 3]);
+        }
+
+        #[test]
+        fn three_collinear() {
+            let points = [Point::new(0., 0.), Point::new(1., 2.), Point::new(2., 4.)];
+            assert_eq!(hull_ids(&points), [0, 2]);
+        }
+
+        #[test]
+        fn three_points() {
+            let points = [Point::new(0., 0.), Point::new(1., 2.), Point::new(2., 0.)];
+            assert_eq!(hull_ids(&points), [0, 1, 2]);
+        }
+
+        #[test]
+        fn near_collinear_epsilon() {
+            // The middle point is 0.001 off of the line, so the path through it turns by a sine of about 0.002
+            let points = [
+                Point::new(0., 0.),
+                Point::new(2., 0.),
+                Point::new(1., 0.001),
+            ];
+            assert_eq!(hull_ids(&points), [0, 1, 2]);
+
+            let loose = ConcaveHullBuilder::new(0.).geometric_epsilon(0.01);
+            let hull: Vec<usize> = loose.compute(&points).iter().map(|(id, _)| *id).collect();
+            assert_eq!(hull, [0, 1]);
+
+            // Larger clouds are degenerate too, once their convex hull is flat enough
+            let line: Vec<Point> = (0..10)
+                .map(|k| Point::new(k as f32, if k % 2 == 0 { 0. } else { 0.001 }))
+                .collect();
+            assert!(concave_hull(&line, 0.).len() > 2);
+            let hull: Vec<usize> = loose.compute(&line).iter().map(|(id, _)| *id).collect();
+            assert_eq!(hull, [0, 9]);
+        }
+    }
+
+    mod sliding {
+        use super::*;
+
+        #[test]
+        fn evicts_oldest() {
+            let mut window = SlidingHull::new(4, 10.);
+
+            // These far away points would dominate the hull if they were still in the window
+            assert_eq!(window.push(Point::new(100., 100.)), None);
+            assert_eq!(window.push(Point::new(-100., -100.)), None);
+
+            let square = [
+                Point::new(0., 0.),
+                Point::new(1., 0.),
+                Point::new(1., 1.),
+                Point::new(0., 1.),
+            ];
+            assert_eq!(window.push(square[0]), None);
+            assert_eq!(window.push(square[1]), None);
+            assert_eq!(window.push(square[2]), Some(Point::new(100., 100.)));
+            assert_eq!(window.push(square[3]), Some(Point::new(-100., -100.)));
+
+            assert_eq!(window.len(), 4);
+            assert!(window.points().eq(square.iter()));
+            assert_eq!(window.hull(), concave_hull(&square, 10.));
+        }
+    }
+
+    mod paths {
+        use super::*;
+
+        /// A C shape, made of points along a semicircle, in order from top to bottom
+        const POINTS: [Point; 13] = [
+            Point::new(0., 10.),
+            Point::new(-2.6, 9.7),
+            Point::new(-5., 8.7),
+            Point::new(-7.1, 7.1),
+            Point::new(-8.7, 5.),
+            Point::new(-9.7, 2.6),
+            Point::new(-10., 0.),
+            Point::new(-9.7, -2.6),
+            Point::new(-8.7, -5.),
+            Point::new(-7.1, -7.1),
+            Point::new(-5., -8.7),
+            Point::new(-2.6, -9.7),
+            Point::new(0., -10.),
+        ];
+
+        #[test]
+        fn follows_curve() {
+            let path = concave_path(&POINTS, 3., 0, 12).unwrap();
+            let expected: Vec<_> = POINTS.iter().copied().enumerate().collect();
+
+            assert_eq!(path, expected);
+        }
+
+        #[test]
+        fn loose_path() {
+            let path = concave_path(&POINTS, 10., 0, 12).unwrap();
+            let ids: Vec<usize> = path.iter().map(|(id, _)| *id).collect();
+
+            assert_eq!(ids, [0, 3, 6, 9, 12]);
+        }
+
+        #[test]
+        fn reversed_endpoints() {
+            let path = concave_path(&POINTS, 3., 12, 0).unwrap();
+            let expected: Vec<_> = POINTS.iter().copied().enumerate().rev().collect();
+
+            assert_eq!(path, expected);
+        }
+
+        #[test]
+        fn invalid_endpoints() {
+            assert_eq!(concave_path(&POINTS, 3., 4, 4), None);
+            assert_eq!(concave_path(&POINTS, 3., 0, POINTS.len()), None);
+            assert_eq!(concave_path(&[], 3., 0, 1), None);
+        }
+    }
+
+    mod notches {
+        use super::*;
+
+        /// A 12x10 rectangle, with a point below the top edge to form a big notch,
+        /// and a point just above the bottom edge to form a tiny one
+        const POINTS: [Point; 6] = [
+            Point::new(0., 0.),
+            Point::new(12., 0.),
+            Point::new(12., 10.),
+            Point::new(0., 10.),
+            Point::new(6., 4.),
+            Point::new(6., 0.5),
+        ];
+        const BIG: usize = 4;
+        const TINY: usize = 5;
+
+        #[test]
+        fn keeps_all_notches_by_default() {
+            let hull = concave_hull(&POINTS, 9.);
+            let ids: Vec<usize> = hull.iter().map(|(id, _)| *id).collect();
+
+            assert!(ids.contains(&BIG));
+            assert!(ids.contains(&TINY));
+        }
+
+        #[test]
+        fn fills_tiny_notch() {
+            // The big notch has an area of 36, and the tiny one has an area of 3
+            let hull = ConcaveHullBuilder::new(9.)
+                .min_notch_area(10.)
+                .compute(&POINTS);
+            let ids: Vec<usize> = hull.iter().map(|(id, _)| *id).collect();
+
+            assert!(ids.contains(&BIG));
+            assert!(!ids.contains(&TINY));
+            assert_eq!(hull.len(), 5);
+        }
+
+        #[test]
+        fn min_feature_smooths_shallow_notch() {
+            // The big notch is 6 deep, and the tiny one is only 0.5 deep
+            let hull = ConcaveHullBuilder::new(9.).min_feature(1.).compute(&POINTS);
+            let ids: Vec<usize> = hull.iter().map(|(id, _)| *id).collect();
+
+            assert!(ids.contains(&BIG));
+            assert!(!ids.contains(&TINY));
+            assert_eq!(hull.len(), 5);
+
+            // Once neither notch is deep enough, the hull stays convex
+            let hull = ConcaveHullBuilder::new(9.).min_feature(7.).compute(&POINTS);
+            assert_eq!(hull.len(), 4);
+        }
+
+        #[test]
+        fn classifies_edges() {
+            let hull = concave_hull_classified(&POINTS, 9.);
+            let concave: Vec<usize> = hull
+                .iter()
+                .filter(|(_, _, kind)| *kind == EdgeKind::Concave)
+                .map(|(id, _, _)| *id)
+                .collect();
+
+            // Both edges into and out of each notch are concave, and the two sides are still convex
+            assert_eq!(hull.len(), 6);
+            assert_eq!(concave.len(), 4);
+            assert!(concave.contains(&BIG) && concave.contains(&TINY));
+
+            let hull = concave_hull_classified(&POINTS, f32::INFINITY);
+            assert_eq!(hull.len(), 4);
+            assert!(hull.iter().all(|(_, _, kind)| *kind == EdgeKind::Convex));
+        }
+
+        #[test]
+        fn custom_distance() {
+            // Only measuring vertical distance makes the top and bottom edges too short to split,
+            // while the sides (which weren't split before) become long enough
+            let hull = ConcaveHullBuilder::new(9.)
+                .distance(|a, b| (b.y - a.y).abs())
+                .compute(&POINTS);
+            let ids: Vec<usize> = hull.iter().map(|(id, _)| *id).collect();
+
+            assert!(ids.contains(&BIG));
+            assert!(!ids.contains(&TINY));
+            assert_eq!(hull.len(), 5);
+        }
+
+        #[test]
+        fn triangulates_notches() {
+            let triangles = pocket_triangles(&POINTS, 9.);
+            let total: f32 = triangles.iter().map(|triangle| area(triangle)).sum();
+
+            // One triangle for each notch
+            assert_eq!(triangles.len(), 2);
+            assert_eq!(total, 39.);
+        }
+
+        #[test]
+        fn splits_pockets() {
+            let pockets = concave_hull_pockets(&POINTS, 9.);
+            let mut middles: Vec<usize> = pockets.iter().map(|pocket| pocket[1].0).collect();
+            middles.sort_unstable();
+
+            // Each notch is a convex hull edge, bent in at a single point
+            assert_eq!(pockets.len(), 2);
+            assert!(pockets.iter().all(|pocket| pocket.len() == 3));
+            assert_eq!(middles, [BIG, TINY]);
+            assert!(concave_hull_pockets(&POINTS, f32::INFINITY).is_empty());
+        }
+
+        #[test]
+        fn pockets_of_existing_hull() {
+            let hull = concave_hull(&POINTS, 9.);
+
+            assert_eq!(
+                hull_pockets(&POINTS, &hull),
+                concave_hull_pockets(&POINTS, 9.)
+            );
+        }
+    }
+
+    mod axis_bias {
+        use super::*;
+        use crate::rng::SplitMix64;
+
+        /// A 10x10 grid with a notch cut out of the top, with each point jittered by up to a quarter of the spacing
+        fn jittered_floorplan() -> Vec<Point> {
+            let mut rng = SplitMix64::new(0x5EED);
+            let mut jitter = || rng.next_f64() as f32 * 0.5 - 0.25;
+
+            let mut points = Vec::new();
+            for x in 0..10 {
+                for y in 0..10 {
+                    if (3..=6).contains(&x) && y >= 5 {
+                        continue;
+                    }
+                    let (dx, dy) = (jitter(), jitter());
+                    points.push(Point::new(x as f32 + dx, y as f32 + dy));
+                }
+            }
+
+            points
+        }
+
+        /// Counts the edges of the hull within 10 degrees of horizontal or vertical
+        fn axis_aligned_edges(hull: &[(usize, Point)]) -> usize {
+            // |sin 2θ| is small near either axis
+            let limit = 20f32.to_radians().sin();
+            (0..hull.len())
+                .map(|k| hull[(k + 1) % hull.len()].1 - hull[k].1)
+                .filter(|v| (v.x * v.y).abs() * 2. / v.norm_squared() < limit)
+                .count()
+        }
+
+        #[test]
+        fn more_axis_aligned_edges() {
+            let points = jittered_floorplan();
+
+            let plain = concave_hull(&points, 2.);
+            let biased = ConcaveHullBuilder::new(2.).axis_bias(1.).compute(&points);
+
+            assert!(axis_aligned_edges(&biased) > axis_aligned_edges(&plain));
+            assert!(validate_hull(&points, &biased).is_ok());
+        }
+    }
+
+    mod next_best {
+        use super::*;
+
+        /// A lopsided quadrilateral, with one point just inside of its bottom left corner
+        ///
+        /// The right edge is split first, since it's the longest,
+        /// but its best point is the top left corner, which is already on the hull.
+        const POINTS: [Point; 5] = [
+            Point::new(2.5, 6.),
+            Point::new(9.5, 10.),
+            Point::new(10., 0.),
+            Point::new(2., 2.5),
+            Point::new(1.5, 2.5),
+        ];
+        const INSIDE: usize = 3;
+
+        #[test]
+        fn digs_past_boundary_points() {
+            let plain = concave_hull(&POINTS, 4.);
+            let next_best = ConcaveHullBuilder::new(4.)
+                .next_best_on_boundary(true)
+                .compute(&POINTS);
+            assert_eq!(validate_hull(&POINTS, &plain), Ok(()));
+            assert_eq!(validate_hull(&POINTS, &next_best), Ok(()));
+
+            // Without the option, the right edge is kept, so the inside point can only be reached from the bottom edge
+            // With it, the right edge is split with the inside point, cutting deep into the cloud
+            let position = next_best.iter().position(|(id, _)| *id == INSIDE).unwrap();
+            let neighbours = [
+                next_best[(position + next_best.len() - 1) % next_best.len()].0,
+                next_best[(position + 1) % next_best.len()].0,
+            ];
+            assert_eq!(neighbours, [2, 1]);
+            assert!(area(&next_best) < area(&plain) / 2.);
+        }
+    }
+
+    mod obstacles {
+        use super::*;
+
+        /// A 12x10 box (with a shallow point under the bottom, so that only the top edge is long enough to split),
+        /// and two points for the top edge to be split with
+        const POINTS: [Point; 7] = [
+            Point::new(0., 0.),
+            Point::new(6., -1.),
+            Point::new(12., 0.),
+            Point::new(12., 10.),
+            Point::new(0., 10.),
+            Point::new(4., 6.),
+            Point::new(9., 5.),
+        ];
+        const BEST: usize = 5;
+        const DETOUR: usize = 6;
+
+        /// A small lake straddling the top edge, across the path from the top right corner to the best point
+        const LAKE: [Point; 4] = [
+            Point::new(9.5, 8.),
+            Point::new(10.5, 8.),
+            Point::new(10.5, 11.),
+            Point::new(9.5, 11.),
+        ];
+
+        #[test]
+        fn detours_around_obstacle() {
+            let ids = |hull: &[(usize, Point)]| hull.iter().map(|(id, _)| *id).collect::<Vec<_>>();
+
+            let hull = concave_hull(&POINTS, 11.);
+            assert!(ids(&hull).contains(&BEST));
+            assert!(!ids(&hull).contains(&DETOUR));
+
+            let hull = ConcaveHullBuilder::new(11.)
+                .obstacles(&[&LAKE])
+                .compute(&POINTS);
+            assert!(!ids(&hull).contains(&BEST));
+            assert!(ids(&hull).contains(&DETOUR));
+            assert_eq!(hull.len(), 6);
+
+            // The hull goes under the lake, leaving it outside, and every point is still inside
+            let indexed = IndexedHull::new(hull.clone());
+            assert!(LAKE.iter().all(|p| !indexed.contains(p)));
+            assert!(validate_hull(&POINTS, &hull).is_ok());
+        }
+    }
+
+    mod pinches {
+        use super::*;
+
+        /// Two triangles, meeting at the origin
+        fn figure_eight() -> Vec<(usize, Point)> {
+            [
+                (-2., -1.),
+                (0., 0.),
+                (2., -1.),
+                (2., 1.),
+                (0., 0.),
+                (-2., 1.),
+            ]
+            .into_iter()
+            .enumerate()
+            .map(|(id, (x, y))| (id, Point::new(x, y)))
+            .collect()
+        }
+
+        #[test]
+        fn repeated_vertex() {
+            let hull = figure_eight();
+            assert_eq!(find_pinch_points(&hull), [1, 4]);
+
+            let mut rings = split_pinch_points(&hull);
+            rings.sort_by_key(|ring| ring[1].0);
+            let ids: Vec<Vec<usize>> = rings
+                .iter()
+                .map(|ring| ring.iter().map(|(id, _)| *id).collect())
+                .collect();
+            assert_eq!(ids, [vec![1, 2, 3], vec![4, 5, 0]]);
+            assert!(rings.iter().all(|ring| find_pinch_points(ring).is_empty()));
+        }
+
+        #[test]
+        fn vertex_on_edge() {
+            // The notch reaches all the way down to the middle of the bottom edge
+            let hull: Vec<(usize, Point)> = [(0., 0.), (4., 0.), (4., 4.), (2., 0.), (0., 4.)]
+                .into_iter()
+                .enumerate()
+                .map(|(id, (x, y))| (id, Point::new(x, y)))
+                .collect();
+            assert_eq!(find_pinch_points(&hull), [3]);
+
+            let mut rings = split_pinch_points(&hull);
+            rings.sort_by_key(|ring| ring[1].0);
+            let ids: Vec<Vec<usize>> = rings
+                .iter()
+                .map(|ring| ring.iter().map(|(id, _)| *id).collect())
+                .collect();
+            assert_eq!(ids, [vec![3, 1, 2], vec![3, 4, 0]]);
+        }
+
+        #[test]
+        fn gift_opening_never_pinches() {
+            // The cloud has two copies of the waist, but the hull only passes through one of them
+            let points: Vec<Point> = figure_eight().into_iter().map(|(_, p)| p).collect();
+            let hull = concave_hull(&points, 0.);
+
+            assert!(find_pinch_points(&hull).is_empty());
+            assert_eq!(split_pinch_points(&hull), [hull]);
+        }
+    }
+
+    mod skeleton {
+        use super::*;
+
+        #[test]
+        #[cfg(feature = "delaunay")]
+        fn rectangle_spine() {
+            // A 10x2 rectangle, whose medial axis is a spine from (1, 1) to (9, 1), branching into each corner
+            let points = [
+                Point::new(0., 0.),
+                Point::new(10., 0.),
+                Point::new(10., 2.),
+                Point::new(0., 2.),
+                Point::new(5., 1.),
+            ];
+            let hull = concave_hull(&points, f32::INFINITY);
+            let skeleton = hull_skeleton(&hull, 0.5);
+
+            let on_spine = |p: &Point| (p.y - 1.).abs() < 1e-4;
+            let spine: f32 = skeleton
+                .iter()
+                .filter(|(a, b)| on_spine(a) && on_spine(b))
+                .map(|(a, b)| (b - a).norm())
+                .sum();
+            assert!((spine - 8.).abs() < 1e-3);
+
+            // Everything else is a branch into a corner, so it stays near the ends
+            for (a, b) in &skeleton {
+                for p in [a, b] {
+                    assert!(p.x > 0. && p.x < 10. && p.y > 0. && p.y < 2.);
+                    assert!(on_spine(p) || p.x <= 1. || p.x >= 9.);
+                }
+            }
+        }
+    }
+
+    mod band {
+        use super::*;
+
+        #[test]
+        fn square_is_concentric() {
+            let points = [
+                Point::new(0., 0.),
+                Point::new(10., 0.),
+                Point::new(10., 10.),
+                Point::new(0., 10.),
+            ];
+            let hull = concave_hull(&points, f32::INFINITY);
+            let (outer, inner) = hull_band(&hull, 2.);
+            assert_eq!((outer.len(), inner.len()), (4, 4));
+
+            // Both rings are squares around the middle of the hull, one unit either side of its edges
+            let centre = Point::new(5., 5.);
+            for (ring, half_side) in [(&outer, 6.), (&inner, 4.)] {
+                let centroid = ring.iter().map(|p| p.coords).sum::<Vector>() / 4.;
+                assert!((centroid - centre.coords).norm() < 1e-5);
+                for p in ring {
+                    assert!(((p.x - 5.).abs() - half_side).abs() < 1e-5);
+                    assert!(((p.y - 5.).abs() - half_side).abs() < 1e-5);
+                }
+            }
+        }
+
+        #[test]
+        fn inner_ring_clamped() {
+            // A strip only one unit thick, so the inner ring can't be offset by the full two units
+            let points = [
+                Point::new(0., 0.),
+                Point::new(10., 0.),
+                Point::new(10., 1.),
+                Point::new(0., 1.),
+            ];
+            let hull = concave_hull(&points, f32::INFINITY);
+            let (_, inner) = hull_band(&hull, 4.);
+
+            // Instead, it collapses onto the middle of the strip, rather than crossing over to the other side
+            for p in &inner {
+                assert!((p.y - 0.5).abs() < 1e-5);
+                assert!(p.x >= 0. && p.x <= 10.);
+            }
+        }
+    }
+
+    mod exact_math {
+        use super::*;
+        use crate::exact::concave_hull_exact_math;
+
+        #[test]
+        fn integer_points() {
+            // A triangle with a point just above the middle of its base, which the base digs in to
+            let points: [nalgebra::Point2<i64>; 4] = [
+                nalgebra::Point2::new(0, 0),
+                nalgebra::Point2::new(10, 0),
+                nalgebra::Point2::new(5, 8),
+                nalgebra::Point2::new(5, 1),
+            ];
+
+            let hull = concave_hull_exact_math(&points, 3);
+            let ids: Vec<usize> = hull.iter().map(|(id, _)| *id).collect();
+            assert_eq!(ids, [0, 3, 1, 2]);
+            assert!(hull.iter().all(|&(id, p)| points[id] == p));
+
+            // A concavity longer than every edge leaves the convex hull as it is
+            let hull = concave_hull_exact_math(&points, 20);
+            let ids: Vec<usize> = hull.iter().map(|(id, _)| *id).collect();
+            assert_eq!(ids, [0, 1, 2]);
+        }
+
+        #[test]
+        fn integer_lines() {
+            let line: Vec<nalgebra::Point2<i64>> =
+                (0..5).map(|k| nalgebra::Point2::new(k, 2 * k)).collect();
+            let ids: Vec<usize> = concave_hull_exact_math(&line, 0)
+                .iter()
+                .map(|(id, _)| *id)
+                .collect();
+            assert_eq!(ids, [0, 4]);
+
+            let same = [nalgebra::Point2::new(3_i64, 3); 3];
+            assert_eq!(concave_hull_exact_math(&same, 0), [(0, same[0])]);
+            assert!(concave_hull_exact_math::<i64>(&[], 0).is_empty());
+        }
+
+        #[test]
+        fn float_hull_is_valid() {
+            // The same process works on floats, and always gives a valid hull
+            let points: Vec<Point> = (0..15)
+                .flat_map(|x| (0..15).map(move |y| (x, y)))
+                .filter(|&(x, y)| !(5..10).contains(&x) || y < 5)
+                .map(|(x, y)| Point::new(x as f32, y as f32 + (x % 3) as f32 * 0.1))
+                .collect();
+
+            for concavity in [1.5, 3., 10., f32::INFINITY] {
+                let hull = concave_hull_exact_math(&points, concavity);
+                assert_eq!(validate_hull(&points, &hull), Ok(()));
+            }
+        }
+    }
+
+    mod empty_hulls {
+        use super::*;
+
+        #[test]
+        fn every_helper() {
+            let empty: &[(usize, Point)] = &[];
+            let square = [
+                Point::new(0., 0.),
+                Point::new(2., 0.),
+                Point::new(2., 2.),
+                Point::new(0., 2.),
+            ];
+
+            // Each helper which takes a hull, and whether it gave its empty result for an empty hull
+            type GaveEmpty<'a> = &'a dyn Fn(&[(usize, Point)]) -> bool;
+            let helpers: [(&str, GaveEmpty); 19] = [
+                ("perimeter", &|hull| perimeter(hull) == 0.),
+                ("area", &|hull| area(hull) == 0.),
+                ("reflex_vertices", &|hull| reflex_vertices(hull).is_empty()),
+                ("find_pinch_points", &|hull| {
+                    find_pinch_points(hull).is_empty()
+                }),
+                ("split_pinch_points", &|hull| {
+                    split_pinch_points(hull).is_empty()
+                }),
+                ("simplify_to_count", &|hull| {
+                    simplify_to_count(hull, 3).is_empty()
+                }),
+                ("repair_micro_reflex", &|hull| {
+                    repair_micro_reflex(hull, 0.1).is_empty()
+                }),
+                ("boundary_complexity", &|hull| {
+                    boundary_complexity(hull) == 1.
+                }),
+                ("hull_obb", &|hull| {
+                    hull_obb(hull) == (Point::origin(), [Vector::x(), Vector::y()], [0.; 2])
+                }),
+                ("hull_bounding_circle", &|hull| {
+                    hull_bounding_circle(hull) == (Point::origin(), 0.)
+                }),
+                ("hull_aabb", &|hull| {
+                    !hull_aabb(hull).contains_local_point(&Point::origin())
+                }),
+                ("hull_mask", &|hull| {
+                    hull_mask(hull, 4, 4, |p| *p).iter().all(|&cell| !cell)
+                }),
+                ("iou_with", &|hull| iou_with(hull, &square) == 0.),
+                ("symmetric_difference_area", &|hull| {
+                    symmetric_difference_area(hull, &square) == 4.
+                }),
+                ("hausdorff_distance", &|hull| {
+                    hausdorff_distance(hull, &concave_hull(&square, 1.)) == 0.
+                }),
+                ("hull_skeleton", &|hull| hull_skeleton(hull, 0.5).is_empty()),
+                ("hull_band", &|hull| {
+                    hull_band(hull, 1.) == (Vec::new(), Vec::new())
+                }),
+                ("IndexedHull::contains", &|hull| {
+                    !IndexedHull::new(hull.to_vec()).contains(&Point::origin())
+                }),
+                ("IndexedHull::nearest_edge", &|hull| {
+                    IndexedHull::new(hull.to_vec())
+                        .nearest_edge(&Point::origin())
+                        .is_none()
+                }),
+            ];
+
+            for (name, handles_empty) in helpers {
+                assert!(handles_empty(empty), "{name} mishandled an empty hull");
+            }
+        }
+
+        #[test]
+        #[cfg(feature = "shapefile")]
+        fn empty_shapefile() {
+            let dir =
+                std::env::temp_dir().join(format!("concave_hull_empty_shp_{}", std::process::id()));
+            std::fs::create_dir_all(&dir).unwrap();
+            write_hull_shapefile(dir.join("empty"), &[]).unwrap();
+
+            // The file header, then a record header and a null shape
+            let shp = std::fs::read(dir.join("empty.shp")).unwrap();
+            assert_eq!(shp.len(), 100 + 8 + 4);
+            assert_eq!(i32::from_le_bytes(shp[108..112].try_into().unwrap()), 0);
+
+            std::fs::remove_dir_all(dir).unwrap();
+        }
+    }
+
+    mod labels {
+        use super::*;
+
+        #[test]
+        fn interleaved_labels() {
+            // A square with a point in the middle, and a triangle with a point inside, off to the right
+            let square = [
+                Point::new(0., 0.),
+                Point::new(4., 0.),
+                Point::new(4., 4.),
+                Point::new(0., 4.),
+                Point::new(2., 2.),
+            ];
+            let triangle = [
+                Point::new(10., 0.),
+                Point::new(14., 0.),
+                Point::new(12., 4.),
+                Point::new(12., 1.),
+            ];
+
+            // Alternate between the shapes, so that neither is contiguous in the cloud
+            let mut points = Vec::new();
+            let mut labels = Vec::new();
+            for (k, p) in square.iter().enumerate() {
+                points.push(*p);
+                labels.push(7);
+                if let Some(p) = triangle.get(k) {
+                    points.push(*p);
+                    labels.push(3);
+                }
+            }
+
+            let hulls = concave_hull_by_label(&points, &labels, 10.);
+            assert_eq!(hulls.len(), 2);
+            for (label, shape) in [(7, &square[..]), (3, &triangle[..])] {
+                let hull = &hulls[&label];
+                assert!(
+                    hull.iter()
+                        .all(|&(id, p)| labels[id] == label && points[id] == p)
+                );
+
+                let expected: Vec<Point> =
+                    concave_hull(shape, 10.).iter().map(|(_, p)| *p).collect();
+                let actual: Vec<Point> = hull.iter().map(|(_, p)| *p).collect();
+                assert_eq!(actual, expected);
+            }
+        }
+
+        #[test]
+        fn small_clusters_dropped() {
+            // Two 4x4 grids, and a speck of two points between them
+            // With a concavity of the grid spacing, edges between neighbouring points are never split into the grids,
+            // so each grid hull keeps exactly its 12 border points
+            let mut points = Vec::new();
+            let mut labels = Vec::new();
+            for (label, offset) in [(0, 0.), (1, 20.)] {
+                for x in 0..4 {
+                    for y in 0..4 {
+                        points.push(Point::new(offset + x as f32, y as f32));
+                        labels.push(label);
+                    }
+                }
+            }
+            points.extend([Point::new(10., 1.), Point::new(10.5, 1.5)]);
+            labels.extend([2, 2]);
+
+            let hulls = concave_hull_by_label_min_size(&points, &labels, 1., 3);
+            let mut kept: Vec<u32> = hulls.keys().copied().collect();
+            kept.sort();
+            assert_eq!(kept, [0, 1]);
+            for label in [0, 1] {
+                assert_eq!(hulls[&label].len(), 12);
+            }
+
+            // Without a minimum size, the speck gets its own (degenerate) hull
+            let hulls = concave_hull_by_label(&points, &labels, 1.);
+            assert_eq!(hulls[&2].len(), 2);
+        }
+    }
+
+    #[cfg(feature = "image")]
+    mod mask_input {
+        use super::*;
+
+        #[test]
+        fn l_shaped_mask() {
+            // An L along the left column and the bottom row of a 4x4 image, with a dim pixel under the threshold
+            let mut mask = image::GrayImage::new(4, 4);
+            for k in 0..4 {
+                mask.put_pixel(0, k, image::Luma([255]));
+                mask.put_pixel(k, 3, image::Luma([200]));
+            }
+            mask.put_pixel(2, 1, image::Luma([100]));
+
+            let hull = concave_hull_from_mask(&mask, 127, 0.);
+
+            // Start from the top left pixel, which is the first foreground pixel
+            let start = hull.iter().position(|&(id, _)| id == 0).unwrap();
+            let mut points: Vec<Point> = hull.iter().map(|(_, p)| *p).collect();
+            points.rotate_left(start);
+            assert_eq!(
+                points,
+                [
+                    Point::new(0.5, 0.5),
+                    Point::new(3.5, 3.5),
+                    Point::new(2.5, 3.5),
+                    Point::new(1.5, 3.5),
+                    Point::new(0.5, 3.5),
+                    Point::new(0.5, 2.5),
+                    Point::new(0.5, 1.5),
+                ]
+            );
+
+            // Everything is under a high enough threshold
+            assert!(concave_hull_from_mask(&mask, 255, 0.).is_empty());
+        }
+    }
+
+    mod density_field {
+        use super::*;
+
+        #[test]
+        fn thin_cloud_grid_stays_small() {
+            // A long, very thin strip, whose area alone would call for tiny cells
+            let points: Vec<Point> = (0..1000)
+                .map(|k| Point::new(k as f32, (k % 2) as f32 * 0.001))
+                .collect();
+            let field = DensityField::new(&points);
+
+            assert!(field.counts.len() <= 2 * points.len());
+            assert_eq!(field.counts.iter().sum::<usize>(), points.len());
+        }
+    }
+
+    mod combined_hulls {
+        use super::*;
+
+        #[test]
+        fn overlapping_squares() {
+            let square = |x: f32, y: f32| {
+                Vec::from([
+                    Point::new(x, y),
+                    Point::new(x + 4., y),
+                    Point::new(x + 4., y + 4.),
+                    Point::new(x, y + 4.),
+                ])
+            };
+            let hulls = [square(0., 0.), square(2., 2.)];
+            let sorted = |mut points: Vec<Point>| {
+                points.sort_by(|a, b| a.x.total_cmp(&b.x).then(a.y.total_cmp(&b.y)));
+                points
+            };
+
+            // The convex hull cuts across the corners where the squares meet, leaving out the corners inside the other square
+            let outer = hull_of_hulls(&hulls, f32::INFINITY);
+            assert_eq!(
+                sorted(outer),
+                [
+                    Point::new(0., 0.),
+                    Point::new(0., 4.),
+                    Point::new(2., 6.),
+                    Point::new(4., 0.),
+                    Point::new(6., 2.),
+                    Point::new(6., 6.),
+                ]
+            );
+
+            // Digging in reaches those inner corners, but never the places where the squares' edges cross
+            let tight = hull_of_hulls(&hulls, 3.);
+            assert_eq!(sorted(tight), sorted(hulls.concat()));
+        }
+    }
+
+    mod clipping {
+        use super::*;
+
+        #[test]
+        fn smaller_box() {
+            // An 11x11 grid, from 0 to 10, clipped to the box from 2 to 8
+            let points: Vec<Point> = (0..11)
+                .flat_map(|x| (0..11).map(move |y| Point::new(x as f32, y as f32)))
+                .collect();
+            let clip = [
+                Point::new(2., 2.),
+                Point::new(8., 2.),
+                Point::new(8., 8.),
+                Point::new(2., 8.),
+            ];
+
+            let clipped = concave_hull_clipped(&points, 2., &clip);
+            let ring: Vec<(usize, Point)> = clipped.iter().map(|p| (0, *p)).collect();
+            assert_eq!(area(&ring), 36.);
+            for p in &clipped {
+                assert!((2. ..=8.).contains(&p.x) && (2. ..=8.).contains(&p.y));
+            }
+
+            // The clip polygon can wind either way
+            let reversed: Vec<Point> = clip.iter().rev().copied().collect();
+            let ring: Vec<(usize, Point)> = concave_hull_clipped(&points, 2., &reversed)
+                .into_iter()
+                .map(|p| (0, p))
+                .collect();
+            assert_eq!(area(&ring), 36.);
+
+            // A clip polygon off to the side leaves nothing
+            let far: Vec<Point> = clip.iter().map(|p| p + Vector::new(20., 0.)).collect();
+            assert!(concave_hull_clipped(&points, 2., &far).is_empty());
+        }
+    }
+
+    mod mask {
+        use super::*;
+
+        #[test]
+        fn square() {
+            let points = [
+                Point::new(1., 1.),
+                Point::new(5., 1.),
+                Point::new(5., 5.),
+                Point::new(1., 5.),
+                Point::new(3., 3.),
+            ];
+            let hull = concave_hull(&points, f32::INFINITY);
+
+            // A 4x4 square, offset by one cell from the corner of a 6x6 mask
+            let mask = hull_mask(&hull, 6, 6, |p| *p);
+            assert_eq!(mask.iter().filter(|&&cell| cell).count(), 16);
+            assert!(!mask[0]);
+            assert!(mask[6 + 1]);
+            assert!(!mask[5 * 6 + 5]);
+
+            // Halving the coordinates shrinks it to a 2x2 square, and clipping it to a 1x1 mask leaves a single cell
+            let mask = hull_mask(&hull, 4, 4, |p| Point::new(p.x / 2., p.y / 2.));
+            assert_eq!(mask.iter().filter(|&&cell| cell).count(), 4);
+            assert_eq!(hull_mask(&hull, 1, 1, |p| *p), [false]);
+            assert_eq!(hull_mask(&hull, 2, 2, |p| *p), [false, false, false, true]);
+        }
+
+        #[test]
+        fn notch() {
+            // A 12x10 rectangle, with a notch dug into its top edge down to (6, 4)
+            let points = [
+                Point::new(0., 0.),
+                Point::new(12., 0.),
+                Point::new(12., 10.),
+                Point::new(0., 10.),
+                Point::new(6., 4.),
+            ];
+            // The top and bottom edges are equally long, so stable ordering is needed to pin down which is split first
+            let hull = ConcaveHullBuilder::new(0.)
+                .stable_ordering(true)
+                .compute(&points);
+            let mask = hull_mask(&hull, 12, 10, |p| *p);
+
+            // The notch cuts 36 cells out of the rectangle's 120, matching its area
+            assert_eq!(mask.iter().filter(|&&cell| cell).count(), 120 - 36);
+            assert!(mask[2 * 12 + 6]);
+            assert!(!mask[9 * 12 + 6]);
+        }
+    }
+
+    mod permutations {
+        use super::*;
+
+        /// A 6x6 grid with a notch cut out of the top, so that it is full of ties
+        fn notched_grid() -> Vec<Point> {
+            (0..6)
+                .flat_map(|x| (0..6).map(move |y| (x, y)))
+                .filter(|&(x, y)| !((x == 2 || x == 3) && y >= 3))
+                .map(|(x, y)| Point::new(x as f32, y as f32))
+                .collect()
+        }
+
+        #[test]
+        fn symmetric_grid() {
+            let points = notched_grid();
+            let n = points.len();
+            let permutations: [Vec<usize>; 3] = [
+                (0..n).rev().collect(),
+                (0..n).map(|k| (k + 7) % n).collect(),
+                (0..n).map(|k| (k * 7) % n).collect(),
+            ];
+
+            for concavity in [0., 1.5] {
+                let builder = ConcaveHullBuilder::new(concavity).stable_ordering(true);
+                let expected: Vec<Point> = builder
+                    .compute(&points)
+                    .into_iter()
+                    .map(|(_, p)| p)
+                    .collect();
+
+                for permutation in &permutations {
+                    let shuffled: Vec<Point> = permutation.iter().map(|&k| points[k]).collect();
+                    let hull = builder.compute(&shuffled);
+
+                    // Same shape, from the same starting point, with indices into the shuffled cloud
+                    let coordinates: Vec<Point> = hull.iter().map(|(_, p)| *p).collect();
+                    assert_eq!(coordinates, expected);
+                    for (id, p) in &hull {
+                        assert_eq!(shuffled[*id], *p);
+                    }
+                }
+            }
+        }
+    }
+
+    mod curvature {
+        use std::f32::consts::{FRAC_PI_2, TAU};
+
+        use super::*;
+
+        #[test]
+        fn straight_runs_and_corners() {
+            // A 5x5 grid, whose hull is the square around it, with every edge point along the way
+            // (a concavity of the grid spacing splits the sides down to single steps, but never digs into the grid)
+            let points: Vec<Point> = (0..5)
+                .flat_map(|x| (0..5).map(move |y| Point::new(x as f32, y as f32)))
+                .collect();
+            let hull = hull_with_curvature(&points, 1.);
+            assert_eq!(hull.len(), 16);
+
+            for (_, p, angle) in &hull {
+                let corner = (p.x == 0. || p.x == 4.) && (p.y == 0. || p.y == 4.);
+                if corner {
+                    assert!((angle - FRAC_PI_2).abs() < 1e-6);
+                } else {
+                    assert!(angle.abs() < 1e-6);
+                }
+            }
+        }
+
+        #[test]
+        fn micro_reflex_repair() {
+            // A 20x10 rectangle, with a 1 degree dent in the bottom edge, and a 30 degree notch in the top edge
+            let dent = 10. * 0.5_f32.to_radians().tan();
+            let notch = 10. * 15_f32.to_radians().tan();
+            let hull: Vec<(usize, Point)> = [
+                Point::new(0., 0.),
+                Point::new(10., dent),
+                Point::new(20., 0.),
+                Point::new(20., 10.),
+                Point::new(10., 10. - notch),
+                Point::new(0., 10.),
+            ]
+            .into_iter()
+            .enumerate()
+            .collect();
+            assert_eq!(reflex_vertices(&hull), [1, 4]);
+
+            let repaired = repair_micro_reflex(&hull, 2_f32.to_radians());
+            let ids: Vec<usize> = repaired.iter().map(|(id, _)| *id).collect();
+            assert_eq!(ids, [0, 2, 3, 4, 5]);
+            assert_eq!(reflex_vertices(&repaired), [4]);
+
+            // A large enough threshold takes out the notch too
+            let repaired = repair_micro_reflex(&hull, 45_f32.to_radians());
+            assert!(reflex_vertices(&repaired).is_empty());
+        }
+
+        #[test]
+        fn reflex_corners_turn_right() {
+            // A rectangle with a notch dug into its top edge
+            let points = [
+                Point::new(0., 0.),
+                Point::new(12., 0.),
+                Point::new(12., 10.),
+                Point::new(0., 10.),
+                Point::new(6., 4.),
+            ];
+            let hull = hull_with_curvature(&points, 0.);
+            let reflex: Vec<usize> = hull
+                .iter()
+                .filter(|(.., angle)| *angle < 0.)
+                .map(|(id, ..)| *id)
+                .collect();
+            let total: f32 = hull.iter().map(|(.., angle)| angle).sum();
+
+            assert_eq!(reflex, [4]);
+            assert_eq!(reflex, reflex_vertices(&concave_hull(&points, 0.)));
+            assert!((total - TAU).abs() < 1e-4);
+        }
+    }
+
+    mod wrapping {
+        use super::*;
+
+        #[test]
+        fn antimeridian() {
+            // A 20 by 8 patch of longitudes and latitudes, straddling the seam at ±180
+            let points: Vec<Point> = [170., 174., 178., -178., -174., -170.]
+                .into_iter()
+                .flat_map(|x| [0., 4., 8.].map(|y| Point::new(x, y)))
+                .collect();
+
+            // Without wrapping, the hull goes the long way around
+            assert!(area(&concave_hull(&points, 5.)) > 2000.);
+
+            let wrap = Wrap {
+                x_period: Some(360.),
+                y_period: None,
+            };
+            let hull = ConcaveHullBuilder::new(5.).wrap(wrap).compute(&points);
+            assert_eq!(area(&hull), 160.);
+            for (id, p) in &hull {
+                // Points are only ever moved by a whole period, and the first point stays put
+                let shift = p.x - points[*id].x;
+                assert!(shift == 0. || shift == 360.);
+                assert_eq!(p.y, points[*id].y);
+                assert!((170. ..=190.).contains(&p.x));
+            }
+        }
+    }
+
+    mod outliers {
+        use super::*;
+
+        /// An 11x11 grid, with one point far off to the right
+        fn grid_with_outlier() -> Vec<Point> {
+            let mut points: Vec<Point> = (0..11)
+                .flat_map(|x| (0..11).map(move |y| Point::new(x as f32, y as f32)))
+                .collect();
+            points.push(Point::new(50., 5.));
+            points
+        }
+        const OUTLIER: usize = 121;
+
+        #[test]
+        fn spikes_out_by_default() {
+            let hull = concave_hull(&grid_with_outlier(), 2.);
+
+            assert!(hull.iter().any(|(id, _)| *id == OUTLIER));
+        }
+
+        #[test]
+        fn excludes_outlier() {
+            let points = grid_with_outlier();
+            let hull = ConcaveHullBuilder::new(2.)
+                .max_edge_ratio(10.)
+                .compute(&points);
+
+            assert!(hull.iter().all(|(id, _)| *id != OUTLIER));
+            // The indices still refer to the original points
+            for (id, p) in &hull {
+                assert_eq!(points[*id], *p);
+            }
+            assert_eq!(area(&hull), 100.);
+        }
+    }
+
+    mod accuracy {
+        use std::fs::File;
+
+        use csv::ReaderBuilder;
+
+        use super::*;
+
+        fn load_polygon() -> Vec<Point> {
+            let f = File::open("./test_data/polygon.csv").unwrap();
+
+            let mut reader = ReaderBuilder::new().has_headers(false).from_reader(f);
+
+            reader
+                .records()
+                .map(|r| {
+                    let r = r.unwrap();
+                    Point::new(r[0].parse().unwrap(), r[1].parse().unwrap())
+                })
+                .collect()
+        }
+
+        /// A rough, hand-drawn outline of the star that the polygon dataset traces out
+        const TRUTH: [Point; 16] = [
+            Point::new(160., 400.),
+            Point::new(175., 405.),
+            Point::new(188., 413.),
+            Point::new(178., 431.),
+            Point::new(192., 442.),
+            Point::new(175., 444.),
+            Point::new(182., 466.),
+            Point::new(160., 471.),
+            Point::new(157., 448.),
+            Point::new(139., 466.),
+            Point::new(151., 442.),
+            Point::new(126., 447.),
+            Point::new(134., 430.),
+            Point::new(155., 425.),
+            Point::new(141., 408.),
+            Point::new(160., 428.),
+        ];
+
+        #[test]
+        fn tighter_hull_is_more_accurate() {
+            let points = load_polygon();
+            let convex = iou_with(&concave_hull(&points, f32::INFINITY), &TRUTH);
+            let loose = iou_with(&concave_hull(&points, 40.), &TRUTH);
+            let tight = iou_with(&concave_hull(&points, 20.), &TRUTH);
+
+            assert!(convex < loose);
+            assert!(loose < tight);
+            assert!(tight > 0.85 && tight < 1.);
+        }
+
+        #[test]
+        fn identical_and_disjoint() {
+            let truth: Vec<(usize, Point)> = TRUTH.iter().copied().enumerate().collect();
+            assert!((iou_with(&truth, &TRUTH) - 1.).abs() < 1e-3);
+            assert!(symmetric_difference_area(&truth, &TRUTH).abs() < 1.);
+
+            // The same shape, moved well clear of the original
+            let moved: Vec<(usize, Point)> = TRUTH
+                .iter()
+                .map(|p| Point::new(p.x + 100., p.y))
+                .enumerate()
+                .collect();
+            assert_eq!(iou_with(&moved, &TRUTH), 0.);
+            let difference = symmetric_difference_area(&moved, &TRUTH);
+            assert!((difference - 2. * area(&truth)).abs() < 1.);
+        }
+    }
+
+    mod question_mark {
+        use std::fs::File;
+
+        use csv::ReaderBuilder;
+
+        use super::*;
+        use crate::{edge::Edge, segment_intersect::edges_intersect};
+
+        fn load_question_mark() -> Vec<Point> {
+            let f = File::open("./test_data/question_mark.csv").unwrap();
+
+            let mut reader = ReaderBuilder::new().has_headers(false).from_reader(f);
+
+            reader
+                .records()
+                .map(|r| {
+                    let r = r.unwrap();
+                    let x = r[0].parse().unwrap();
+                    let y = r[1].parse().unwrap();
+
+                    Point::new(x, y)
+                })
+                .collect()
+        }
+
+        #[test]
+        fn simplify_to_ten() {
+            let points = load_question_mark();
+            let hull = concave_hull(&points, 40.);
+            assert!(hull.len() > 10);
+
+            let simplified = simplify_to_count(&hull, 10);
+            assert_eq!(simplified.len(), 10);
+
+            // The kept vertices are a subsequence of the hull
+            let mut rest = hull.iter();
+            assert!(simplified.iter().all(|v| rest.any(|h| h == v)));
+
+            let error = (area(&simplified) - area(&hull)).abs() / area(&hull);
+            assert!(error < 0.05, "Area changed by {:.1}%", error * 100.);
+
+            assert_eq!(simplify_to_count(&hull, hull.len()), hull);
+        }
+
+        #[test]
+        fn pocket_triangle_area() {
+            let points = load_question_mark();
+            let convex = concave_hull(&points, f32::INFINITY);
+            let concave = concave_hull(&points, 40.);
+
+            let total: f32 = pocket_triangles(&points, 40.)
+                .iter()
+                .map(|triangle| area(triangle))
+                .sum();
+
+            let expected = area(&convex) - area(&concave);
+            assert!((total - expected).abs() < expected * 1e-4);
+        }
+
+        #[test]
+        fn difference_area() {
+            let points = load_question_mark();
+            let region_area = |regions: Vec<Vec<Point>>| -> f32 {
+                regions
+                    .iter()
+                    .map(|region| crate::metrics::signed_area(region.iter().copied()).abs())
+                    .sum()
+            };
+
+            let regions = hull_difference(&points, 40., 0.);
+            assert_eq!(regions.len(), 26);
+            assert!(regions.iter().all(|region| region.len() >= 3));
+
+            let total = region_area(regions);
+            let expected = area(&concave_hull(&points, 40.)) - area(&concave_hull(&points, 0.));
+            assert!((total - expected).abs() < expected * 1e-4);
+            let swapped = region_area(hull_difference(&points, 0., 40.));
+            assert!((swapped - total).abs() < expected * 1e-4);
+
+            assert!(hull_difference(&points, 40., 40.).is_empty());
+        }
+
+        #[test]
+        fn reflex_corners() {
+            let points = load_question_mark();
+
+            assert!(reflex_vertices(&concave_hull(&points, f32::INFINITY)).is_empty());
+            assert_eq!(reflex_vertices(&concave_hull(&points, 0.)).len(), 40);
+        }
+
+        /// Asserts that no two edges of the hull intersect, other than neighbours at their shared endpoint
+        fn assert_simple(hull: &[(usize, Point)]) {
+            let points: Vec<Point> = hull.iter().map(|(_, p)| *p).collect();
+            let edges: Vec<Edge<f32>> = (0..points.len())
+                .map(|k| Edge::new(k, (k + 1) % points.len(), &points))
+                .collect();
+
+            for (k, e1) in edges.iter().enumerate() {
+                for e2 in &edges[k + 1..] {
+                    assert!(!edges_intersect(e1, e2), "{e1:?} intersects {e2:?}");
+                }
+            }
+        }
+
+        #[test]
+        fn default_priority() {
+            let points = load_question_mark();
+            let hull = ConcaveHullBuilder::new(40.)
+                .edge_priority(|a, b| (b - a).norm_squared())
+                .compute(&points);
+
+            assert_eq!(hull, concave_hull(&points, 40.));
+        }
+
+        #[test]
+        fn custom_priority() {
+            let points = load_question_mark();
+            // Split the highest edges first
+            let hull = ConcaveHullBuilder::new(40.)
+                .edge_priority(|a, b| a.y + b.y)
+                .compute(&points);
+
+            assert_simple(&hull);
+        }
+
+        #[test]
+        fn valid_hulls() {
+            let points = load_question_mark();
+
+            for concavity in [0., 40., f32::INFINITY] {
+                assert_eq!(
+                    validate_hull(&points, &concave_hull(&points, concavity)),
+                    Ok(())
+                );
+            }
+        }
+
+        #[test]
+        fn nn_relative_scale_invariant() {
+            let points = load_question_mark();
+            // Scaling by a power of two is exact, so the hulls should match exactly
+            let scaled: Vec<Point> = points.iter().map(|p| p * 8.).collect();
+
+            let hull = concave_hull_nn_relative(&points, 2.);
+            let scaled_hull = concave_hull_nn_relative(&scaled, 2.);
+
+            let ids: Vec<usize> = hull.iter().map(|(id, _)| *id).collect();
+            let scaled_ids: Vec<usize> = scaled_hull.iter().map(|(id, _)| *id).collect();
+            assert_eq!(ids, scaled_ids);
+            // Make sure the hull is actually concave
+            assert!(hull.len() > concave_hull(&points, f32::INFINITY).len());
+        }
+
+        #[test]
+        fn sorted_unsorted() {
+            let points = load_question_mark();
+
+            for concavity in [0., 40., f32::INFINITY] {
+                let edges = concave_hull_unsorted(&points, concavity);
+                assert_eq!(
+                    crate::concave::sort_hull(edges),
+                    concave_hull(&points, concavity)
+                );
+            }
+        }
+
+        #[test]
+        fn custom_point_type() {
+            struct Sample {
+                east: f32,
+                north: f32,
+            }
+
+            impl AsPoint2<f32> for Sample {
+                fn xy(&self) -> (f32, f32) {
+                    (self.east, self.north)
+                }
+            }
+
+            let points = load_question_mark();
+            let samples: Vec<Sample> = points
+                .iter()
+                .map(|p| Sample {
+                    east: p.x,
+                    north: p.y,
+                })
+                .collect();
+            let tuples: Vec<(f32, f32)> = points.iter().map(|p| (p.x, p.y)).collect();
+
+            let expected = concave_hull(&points, 40.);
+            assert_eq!(concave_hull_generic(&samples, 40.), expected);
+            assert_eq!(concave_hull_generic(&tuples, 40.), expected);
+        }
+
+        #[test]
+        #[cfg(feature = "geo-types")]
+        fn polygon_round_trip() {
+            let points = load_question_mark();
+            let hull = concave_hull(&points, 40.);
+
+            let polygon = hull_to_polygon(&hull);
+            assert_eq!(polygon.exterior().0.len(), hull.len() + 1);
+
+            let hull_points: Vec<Point> = hull.iter().map(|(_, p)| *p).collect();
+            assert_eq!(polygon_to_points(&polygon), hull_points);
+        }
+
+        #[test]
+        #[cfg(feature = "csv")]
+        fn csv_reader() {
+            let f = File::open("./test_data/question_mark.csv").unwrap();
+            let points: Vec<Point> = read_points_csv(f, &CsvOptions::new())
+                .collect::<Result<_, _>>()
+                .unwrap();
+            assert_eq!(points, load_question_mark());
+
+            let data = "label;y;x\na; 2 ;1\nb;4\nc;x;3\nd;6;5\n";
+            let options = CsvOptions::new()
+                .columns(2, 1)
+                .delimiter(b';')
+                .has_headers(true);
+            let records: Vec<_> = read_points_csv(data.as_bytes(), &options).collect();
+            assert_eq!(records.len(), 4);
+            assert_eq!(records[0].as_ref().unwrap(), &Point::new(1., 2.));
+            assert!(matches!(
+                records[1],
+                Err(CsvPointError::MissingColumn { line: 3, column: 2 })
+            ));
+            assert!(matches!(
+                records[2],
+                Err(CsvPointError::InvalidNumber { line: 4, column: 1 })
+            ));
+            assert_eq!(records[3].as_ref().unwrap(), &Point::new(5., 6.));
+        }
+
+        #[test]
+        fn weighted() {
+            let points = load_question_mark();
+            let mut weights = vec![1.; points.len()];
+            assert_eq!(
+                concave_hull_weighted(&points, &weights, 40.),
+                concave_hull(&points, 40.)
+            );
+
+            // This point is just inside of the hull, so a little extra weight is enough to pull it on
+            let on_hull = |hull: &[(usize, Point)]| hull.iter().any(|(id, _)| *id == 44);
+            assert!(!on_hull(&concave_hull(&points, 40.)));
+            weights[44] = 2.;
+            assert!(on_hull(&concave_hull_weighted(&points, &weights, 40.)));
+        }
+
+        #[test]
+        fn cheap_convex_checks() {
+            let points = load_question_mark();
+            for concavity in [0., 10., 40., 100., f32::INFINITY] {
+                let builder = ConcaveHullBuilder::new(concavity);
+                assert_eq!(
+                    builder.compute(&points),
+                    builder.clone().cheap_convex_checks(false).compute(&points)
+                );
+            }
+        }
+
+        #[test]
+        fn angle_surrogate() {
+            let points = load_question_mark();
+            for concavity in [0., 10., 20., 40., 60., f32::INFINITY] {
+                let builder = ConcaveHullBuilder::new(concavity);
+                assert_eq!(
+                    builder.compute(&points),
+                    builder.clone().angle_surrogate(false).compute(&points)
+                );
+            }
+        }
+
+        #[test]
+        #[cfg(feature = "delaunay")]
+        fn chi_shape_valid() {
+            let points = load_question_mark();
+
+            let convex = chi_shape(&points, f32::INFINITY);
+            let convex_area = area(&concave_hull(&points, f32::INFINITY));
+            assert!((area(&convex) - convex_area).abs() < convex_area * 1e-4);
+
+            let mut previous_area = area(&convex);
+            for threshold in [100., 40., 20., 0.] {
+                let hull = chi_shape(&points, threshold);
+                assert_eq!(validate_hull(&points, &hull), Ok(()));
+
+                assert!(area(&hull) <= previous_area);
+                previous_area = area(&hull);
+            }
+        }
+
+        #[test]
+        fn flat_layout() {
+            let points = load_question_mark();
+            let (indices, coordinates) = concave_hull_flat(&points, 40.);
+            assert_eq!(coordinates.len(), 2 * indices.len());
+
+            let rebuilt: Vec<(usize, Point)> = indices
+                .iter()
+                .zip(coordinates.chunks_exact(2))
+                .map(|(id, xy)| (*id, Point::new(xy[0], xy[1])))
+                .collect();
+            assert_eq!(rebuilt, concave_hull(&points, 40.));
+        }
+
+        #[test]
+        fn capacity_hint() {
+            let points = load_question_mark();
+
+            for hint in [0, 10, 45, 1000] {
+                let hull = ConcaveHullBuilder::new(40.)
+                    .capacity_hint(hint)
+                    .compute(&points);
+                assert_eq!(hull, concave_hull(&points, 40.));
+            }
+        }
+
+        #[test]
+        fn snapping() {
+            let points = load_question_mark();
+            // Nudge each point by a different amount, all less than half of the grid size
+            let noisy: Vec<Point> = points
+                .iter()
+                .enumerate()
+                .map(|(i, p)| {
+                    let dx = (i % 5) as f32 * 0.1 - 0.2;
+                    let dy = (i % 3) as f32 * 0.15 - 0.15;
+                    Point::new(p.x + dx, p.y + dy)
+                })
+                .collect();
+
+            let builder = ConcaveHullBuilder::new(40.).snap_to_grid(1.);
+            let hull = builder.compute(&points);
+            let noisy_hull = builder.compute(&noisy);
+
+            let ids: Vec<usize> = hull.iter().map(|(id, _)| *id).collect();
+            let noisy_ids: Vec<usize> = noisy_hull.iter().map(|(id, _)| *id).collect();
+            assert_eq!(ids, noisy_ids);
+            // The original coordinates are returned
+            assert!(noisy_hull.iter().all(|(id, p)| noisy[*id] == *p));
+        }
+
+        #[test]
+        fn anytime() {
+            let points = load_question_mark();
+            let hulls: Vec<_> = concave_hull_anytime(&points, 40.).collect();
+
+            assert_eq!(
+                hulls.first().unwrap().len(),
+                concave_hull(&points, f32::INFINITY).len()
+            );
+            assert_eq!(hulls.last().unwrap(), &concave_hull(&points, 40.));
+            for hull in &hulls {
+                assert_eq!(validate_hull(&points, hull), Ok(()));
+            }
+        }
+
+        #[test]
+        fn relative_to_longest_monotonic() {
+            let points = load_question_mark();
+
+            let hulls: Vec<Vec<(usize, Point)>> = [1., 0.5, 0.25, 0.1, 0.05, 0.]
+                .into_iter()
+                .map(|fraction| concave_hull_relative_to_longest(&points, fraction))
+                .collect();
+
+            // Smaller fractions dig further in, so the hulls only ever gain points and lose area
+            for pair in hulls.windows(2) {
+                assert!(pair[0].len() < pair[1].len());
+                assert!(area(&pair[0]) > area(&pair[1]));
+            }
+            assert_eq!(hulls.last().unwrap(), &concave_hull(&points, 0.));
+        }
+
+        #[test]
+        fn length_checks() {
+            let points = load_question_mark();
+
+            // A flat check matches the JS implementation, which is what concave_hull follows
+            let flat = ConcaveHullBuilder::new(40.)
+                .length_check(LengthCheck::Flat)
+                .compute(&points);
+            assert_eq!(flat, concave_hull(&points, 40.));
+
+            // Scaling by density (with k = 1) matches the original paper, which is what adaptive concavity turns on
+            let paper = ConcaveHullBuilder::new(40.)
+                .length_check(LengthCheck::DensityScaled(1.))
+                .compute(&points);
+            let adaptive = ConcaveHullBuilder::new(40.)
+                .adaptive_concavity(true)
+                .compute(&points);
+            assert_eq!(paper, adaptive);
+
+            // Scaling by density to the power of zero doesn't scale at all, so it finds the same hull as a flat check
+            let unscaled = ConcaveHullBuilder::new(40.)
+                .length_check(LengthCheck::DensityScaled(0.))
+                .compute(&points);
+            assert_eq!(canonical(&unscaled), canonical(&flat));
+        }
+
+        #[test]
+        fn channeled_edges() {
+            let points = load_question_mark();
+            let (sender, receiver) = std::sync::mpsc::channel();
+
+            concave_hull_channeled(&points, 40., sender);
+            let edges: Vec<_> = receiver.iter().collect();
 
-use nalgebra::{RealField, Scalar};
-use num_traits::float::TotalOrder;
+            // Reassemble the hull, starting from the last edge sent
+            let start = edges.last().unwrap().i;
+            let mut by_start: std::collections::HashMap<usize, _> =
+                edges.into_iter().map(|edge| (edge.i, edge)).collect();
+            let mut hull = Vec::new();
+            let mut curr = start;
+            while let Some(edge) = by_start.remove(&curr) {
+                hull.push((edge.i, edge.point_i));
+                curr = edge.j;
+            }
 
-mod concave;
-mod edge;
-mod segment_intersect;
+            assert!(by_start.is_empty());
+            assert_eq!(hull, concave_hull(&points, 40.));
+        }
 
-#[cfg(feature = "benches")]
-pub use edge::Edge;
-#[cfg(feature = "benches")]
-pub use segment_intersect::edges_intersect;
+        #[test]
+        fn provenance() {
+            let points = load_question_mark();
 
-/// Trait bound for scalars we can work with
-///
-/// In practice, I think this is just the float types
-#[cfg(not(feature = "benches"))]
-pub(crate) trait HullScalar = Scalar + RealField + Copy + TotalOrder;
+            let convex = concave_hull_provenance(&points, f32::INFINITY);
+            assert!(convex.iter().all(|&(_, _, original)| original));
 
-/// Trait bound for scalars we can work with
-///
-/// In practice, I think this is just the float types
-#[cfg(feature = "benches")]
-pub trait HullScalar = Scalar + RealField + Copy + TotalOrder;
+            // Digging in keeps every convex hull vertex, and inserts the rest
+            let hull = concave_hull_provenance(&points, 40.);
+            let original = hull.iter().filter(|&&(_, _, original)| original).count();
+            assert_eq!(original, convex.len());
+            let plain: Vec<(usize, Point)> = hull.iter().map(|&(id, p, _)| (id, p)).collect();
+            assert_eq!(plain, concave_hull(&points, 40.));
+        }
 
-/// Spatial points and concave hull generation for [`prim@f32`] precision
-#[cfg(feature = "f32")]
-pub mod f32 {
-    /// [`parry2d`]'s point type, which [`concave_hull`] uses internally for all its math
-    ///
-    /// This is also the point type used in function signatures and returns
-    pub type Point = parry2d::math::Point<f32>;
-    pub use parry2d;
+        #[test]
+        fn replaced_edges() {
+            let points = load_question_mark();
 
-    use crate::concave::concave_hull_inner;
+            let mut replaced = replaced_convex_edges(&points, 40.);
+            replaced.sort();
+            assert_eq!(replaced, [(15, 42), (42, 79), (71, 72), (75, 10)]);
 
-    /// Computes the concave hull of the provided point cloud, using the provided concavity parameter
-    ///
-    /// Inputs:
-    /// - `points`: A list of points, making up the point cloud to generate the concave hull for.
-    /// It is assumed that this list contains no repeat points.
-    /// - `concavity`: A parameter determining how concave the hull should be.
-    /// See the crate-level docs for guidance on picking the concavity parameter.
-    ///
-    /// The returned [`Vec`] contains a tuple of:
-    /// - The index of the hull point in the original slice
-    /// - The value of the point in the original slice
-    ///
-    /// The points are returned in counter-clockwise order.
-    pub fn concave_hull(points: &[Point], concavity: f32) -> Vec<(usize, Point)> {
-        if points.len() <= 1 {
-            // Degenerate case with too few points to make a convex hull
-            // Just return the original point (or nothing)
-            return points.iter().enumerate().map(|(id, p)| (id, *p)).collect();
+            // With no concavity, the convex hull is left as it is
+            assert!(replaced_convex_edges(&points, f32::INFINITY).is_empty());
         }
 
-        // Get the convex hull from parry
-        let convex = parry2d::transformation::convex_hull_idx(points);
+        /// Rotates a hull to start from its lexicographically smallest point, and drops the indices
+        fn canonical(hull: &[(usize, Point)]) -> Vec<Point> {
+            let start = (0..hull.len())
+                .min_by(|&a, &b| {
+                    let (a, b) = (hull[a].1, hull[b].1);
+                    a.x.total_cmp(&b.x).then(a.y.total_cmp(&b.y))
+                })
+                .unwrap_or(0);
 
-        concave_hull_inner(points, concavity, convex)
-    }
-}
+            hull[start..]
+                .iter()
+                .chain(&hull[..start])
+                .map(|(_, p)| *p)
+                .collect()
+        }
 
-/// Spatial points and concave hull generation for [`prim@f64`] precision
-#[cfg(feature = "f64")]
-pub mod f64 {
-    /// [`parry2d`]'s point type, which [`concave_hull`] uses internally for all its math
-    ///
-    /// This is also the point type used in function signatures and returns
-    pub type Point = parry2d::math::Point<f64>;
-    pub use parry2d_f64 as parry2d;
+        #[test]
+        fn shuffle_invariance() {
+            let points = load_question_mark();
+            let n = points.len();
+            let permutations: [Vec<usize>; 3] = [
+                (0..n).rev().collect(),
+                (0..n).map(|k| (k + 17) % n).collect(),
+                // 7 is coprime with the number of points, so this visits every point
+                (0..n).map(|k| (k * 7) % n).collect(),
+            ];
 
-    use crate::concave::concave_hull_inner;
+            for concavity in [0., 20., 40., f32::INFINITY] {
+                let expected = canonical(&concave_hull(&points, concavity));
 
-    /// Computes the concave hull of the provided point cloud, using the provided concavity parameter
-    ///
-    /// Inputs:
-    /// - `points`: A list of points, making up the point cloud to generate the concave hull for.
-    /// It is assumed that this list contains no repeat points.
-    /// - `concavity`: A parameter determining how concave the hull should be.
-    /// See the crate-level docs for guidance on picking the concavity parameter.
-    ///
-    /// The returned [`Vec`] contains a tuple of:
-    /// - The index of the hull point in the original slice
-    /// - The value of the point in the original slice
-    ///
-    /// The points are returned in counter-clockwise order.
-    pub fn concave_hull(points: &[Point], concavity: f64) -> Vec<(usize, Point)> {
-        if points.len() <= 1 {
-            // Degenerate case with too few points to make a convex hull
-            // Just return the original point (or nothing)
-            return points.iter().enumerate().map(|(id, p)| (id, *p)).collect();
+                for permutation in &permutations {
+                    let shuffled: Vec<Point> = permutation.iter().map(|&k| points[k]).collect();
+                    assert_eq!(canonical(&concave_hull(&shuffled, concavity)), expected);
+                }
+            }
         }
 
-        // Get the convex hull from parry
-        let convex = parry2d::transformation::convex_hull_idx(points);
+        #[test]
+        fn indexed_queries() {
+            let points = load_question_mark();
+            let indexed = concave_hull_indexed(&points, 40.);
+            let hull = indexed.hull();
+            assert_eq!(hull, concave_hull(&points, 40.));
 
-        concave_hull_inner(points, concavity, convex)
-    }
-}
+            // Compare the index against checking every edge, on a spread of query points in and around the hull
+            for x in (0..300).step_by(23) {
+                for y in (0..500).step_by(37) {
+                    let query = Point::new(x as f32, y as f32);
+                    let (nearest, distance) = indexed.nearest_edge(&query).unwrap();
 
-#[cfg(test)]
-mod tests {
-    use super::f32::*;
+                    let brute_force = (0..hull.len())
+                        .map(|k| {
+                            let (a, b) = (hull[k].1, hull[(k + 1) % hull.len()].1);
+                            let t =
+                                ((query - a).dot(&(b - a)) / (b - a).norm_squared()).clamp(0., 1.);
+                            (query - (a + (b - a) * t)).norm()
+                        })
+                        .fold(f32::INFINITY, f32::min);
+                    assert_eq!(distance, brute_force);
+                    assert!(nearest < hull.len());
+                }
+            }
 
-    mod small_clouds {
-        use super::*;
+            // The dot and the hook are inside, but the gap between them isn't
+            assert!(indexed.contains(&Point::new(160., 40.)));
+            assert!(indexed.contains(&Point::new(200., 400.)));
+            assert!(!indexed.contains(&Point::new(128., 100.)));
+            assert!(!indexed.contains(&Point::new(-10., 100.)));
+        }
 
-        /// An array of points in a numpad grid, in numpad order
-        ///
-        /// 7 8 9
-        /// 4 5 6
-        /// 1 2 3
-        /// 0
-        const POINTS: [Point; 10] = [
-            Point::new(0., 0.),
-            Point::new(0., 1.),
-            Point::new(1., 1.),
-            Point::new(2., 1.),
-            Point::new(0., 2.),
-            Point::new(1., 2.),
-            Point::new(2., 2.),
-            Point::new(0., 3.),
-            Point::new(1., 3.),
-            Point::new(2., 3.),
-        ];
+        #[test]
+        fn hull_aabb_matches_parry() {
+            use parry2d::bounding_volume::details::local_point_cloud_aabb;
+
+            let points = load_question_mark();
+            let hull = concave_hull(&points, 40.);
+            let hull_points: Vec<Point> = hull.iter().map(|(_, p)| *p).collect();
+
+            let aabb = hull_aabb(&hull);
+            assert_eq!(aabb, local_point_cloud_aabb(&hull_points));
+            // The hull reaches the edges of the point cloud, so the boxes are the same
+            assert_eq!(aabb, local_point_cloud_aabb(&points));
+
+            let empty = hull_aabb(&[]);
+            assert!(empty.mins.x > empty.maxs.x && empty.mins.y > empty.maxs.y);
+        }
 
         #[test]
-        fn zero_points() {
-            let hull = concave_hull(&POINTS[0..0], 10.);
-            assert_eq!(hull, Vec::new());
+        #[cfg(feature = "shapefile")]
+        fn shapefile_round_trip() {
+            let points = load_question_mark();
+            let hull = concave_hull(&points, 40.);
+
+            let dir = std::env::temp_dir().join(format!("concave_hull_shp_{}", std::process::id()));
+            std::fs::create_dir_all(&dir).unwrap();
+            write_hull_shapefile(dir.join("hull.shp"), &hull).unwrap();
+
+            // Read the ring back out of the only record, just after the 100 byte file header and 8 byte record header
+            let shp = std::fs::read(dir.join("hull.shp")).unwrap();
+            let int = |at: usize| i32::from_le_bytes(shp[at..at + 4].try_into().unwrap());
+            let float = |at: usize| f64::from_le_bytes(shp[at..at + 8].try_into().unwrap());
+            assert_eq!(i32::from_be_bytes(shp[0..4].try_into().unwrap()), 9994);
+            assert_eq!(
+                i32::from_be_bytes(shp[24..28].try_into().unwrap()) as usize * 2,
+                shp.len()
+            );
+            assert_eq!(int(108), 5);
+            assert_eq!(int(144), 1);
+            let count = int(148) as usize;
+            let ring: Vec<(f64, f64)> = (0..count)
+                .map(|k| (float(156 + 16 * k), float(164 + 16 * k)))
+                .collect();
+
+            // The ring is closed, and wound clockwise, which is backwards from the hull
+            assert_eq!(count, hull.len() + 1);
+            assert_eq!(ring[0], ring[count - 1]);
+            let mut expected: Vec<(f64, f64)> =
+                hull.iter().map(|(_, p)| (p.x as f64, p.y as f64)).collect();
+            expected.reverse();
+            assert_eq!(ring[..hull.len()], expected);
+
+            assert_eq!(std::fs::read(dir.join("hull.shx")).unwrap().len(), 108);
+            let dbf = std::fs::read(dir.join("hull.dbf")).unwrap();
+            assert_eq!(dbf.last(), Some(&0x1A));
+
+            assert!(write_hull_shapefile(dir.join("line"), &hull[..2]).is_err());
+            std::fs::remove_dir_all(dir).unwrap();
         }
 
         #[test]
-        fn one_point() {
-            let hull = concave_hull(&POINTS[0..1], 10.);
-            assert_eq!(hull, Vec::from([(0, POINTS[0])]));
+        fn merged_partitions() {
+            let points = load_question_mark();
+            let full = concave_hull(&points, 40.);
+            let part_hull = |part: &[usize]| -> Vec<usize> {
+                concave_hull_subset(&points, part, 40.)
+                    .into_iter()
+                    .map(|(id, _)| id)
+                    .collect()
+            };
+
+            // Overlapping parts cover every point of the full hull, so nothing is lost
+            let first: Vec<usize> = (0..50).collect();
+            let second: Vec<usize> = (30..points.len()).collect();
+            let merged = merge_hulls(&points, &[part_hull(&first), part_hull(&second)], 40.);
+            assert_eq!(canonical(&merged), canonical(&full));
+
+            // Splitting down the middle hides some points along the seam, but the merged hull is still close
+            let (left, right): (Vec<usize>, Vec<usize>) =
+                (0..points.len()).partition(|&id| points[id].x < 140.);
+            let merged = merge_hulls(&points, &[part_hull(&left), part_hull(&right)], 40.);
+            assert!(hausdorff_distance(&merged, &full) < 10.);
         }
 
         #[test]
-        fn two_points() {
-            let hull = concave_hull(&POINTS[0..2], 10.);
-            assert_eq!(hull, Vec::from([(0, POINTS[0]), (1, POINTS[1])]));
+        fn sampled_preview() {
+            let points = load_question_mark();
+            let full = concave_hull(&points, 40.);
+
+            let sampled = concave_hull_sampled(&points, 40., 0.5, 42);
+            assert_eq!(sampled, concave_hull_sampled(&points, 40., 0.5, 42));
+            assert!(sampled.len() < full.len());
+            assert!(sampled.iter().all(|(id, p)| points[*id] == *p));
+
+            // The preview is missing some details, but never strays further than the concavity from the full hull
+            assert!(hausdorff_distance(&full, &sampled) < 40.);
+
+            assert_eq!(concave_hull_sampled(&points, 40., 1., 42), full);
         }
 
         #[test]
-        fn three_points() {
-            let hull = concave_hull(&POINTS[0..3], 10.);
-            assert_eq!(
-                hull,
-                Vec::from([(0, POINTS[0]), (2, POINTS[2]), (1, POINTS[1]),])
-            );
+        fn scooped_out_area() {
+            let points = load_question_mark();
+
+            let convex = area(&concave_hull(&points, f32::INFINITY));
+            let concave = area(&concave_hull(&points, 40.));
+
+            // The convex hull is summed from a different starting point, so allow for rounding
+            assert!(concavity_area(&points, f32::INFINITY) < convex * 1e-5);
+
+            let scooped = concavity_area(&points, 40.);
+            assert!(scooped > 0.);
+            assert!((scooped - (convex - concave)).abs() < convex * 1e-5);
         }
 
         #[test]
-        fn square() {
-            let hull = concave_hull(&[POINTS[1], POINTS[2], POINTS[4], POINTS[5]], 10.);
+        #[cfg(feature = "delaunay")]
+        fn delaunay_huller_sweep() {
+            let points = load_question_mark();
+            let huller = DelaunayHuller::new(&points);
+
+            for threshold in [0., 20., 40., 80., f32::INFINITY] {
+                assert_eq!(huller.hull(threshold), chi_shape(&points, threshold));
+            }
+
+            // Degenerate clouds fall back to the convex hull
+            let line = [Point::new(0., 0.), Point::new(1., 1.), Point::new(2., 2.)];
             assert_eq!(
-                hull,
-                Vec::from([
-                    (2, POINTS[4]),
-                    (0, POINTS[1]),
-                    (1, POINTS[2]),
-                    (3, POINTS[5]),
-                ])
+                DelaunayHuller::new(&line).hull(1.),
+                concave_hull(&line, f32::INFINITY)
             );
         }
-    }
 
-    mod question_mark {
-        use std::fs::File;
+        #[test]
+        fn oriented_around_hint() {
+            let points = load_question_mark();
+            let hint = Point::new(200., 400.);
+            let hull = concave_hull_oriented(&points, 40., hint).unwrap();
+            assert_eq!(validate_hull(&points, &hull), Ok(()));
 
-        use csv::ReaderBuilder;
+            // The hint is on the left of the edge closest to it
+            let (k, _) = IndexedHull::new(hull.clone()).nearest_edge(&hint).unwrap();
+            let (a, b) = (hull[k].1, hull[(k + 1) % hull.len()].1);
+            assert!((b - a).perp(&(hint - a)) > 0.);
 
-        use super::*;
+            // Hints in the gap between the dot and the hook, or on a vertex, are rejected
+            assert_eq!(
+                concave_hull_oriented(&points, 40., Point::new(128., 100.)),
+                Err(ConcaveHullError::HintOutside)
+            );
+            assert_eq!(
+                concave_hull_oriented(&points, 40., hull[0].1),
+                Err(ConcaveHullError::HintOutside)
+            );
+        }
 
-        fn load_question_mark() -> Vec<Point> {
-            let f = File::open("./test_data/question_mark.csv").unwrap();
+        #[test]
+        fn subset_indices() {
+            let points = load_question_mark();
+            let subset: Vec<usize> = (0..points.len()).filter(|id| id % 3 != 0).collect();
+            let gathered: Vec<Point> = subset.iter().map(|&id| points[id]).collect();
 
-            let mut reader = ReaderBuilder::new().has_headers(false).from_reader(f);
+            let hull = concave_hull_subset(&points, &subset, 40.);
+            assert!(hull.len() >= 3);
+            for (id, p) in &hull {
+                assert!(subset.contains(id));
+                assert_eq!(points[*id], *p);
+            }
 
-            reader
-                .records()
-                .map(|r| {
-                    let r = r.unwrap();
-                    let x = r[0].parse().unwrap();
-                    let y = r[1].parse().unwrap();
+            // Same shape as hulling a copy of the subset
+            let copied: Vec<Point> = concave_hull(&gathered, 40.)
+                .into_iter()
+                .map(|(_, p)| p)
+                .collect();
+            let shape: Vec<Point> = hull.into_iter().map(|(_, p)| p).collect();
+            assert_eq!(shape, copied);
+        }
 
-                    Point::new(x, y)
-                })
-                .collect()
+        #[test]
+        fn vertex_indices() {
+            let points = load_question_mark();
+            let hull = concave_hull(&points, 40.);
+            let vertices = concave_hull_vertices(&points, 40.);
+
+            assert_eq!(vertices.len(), hull.len());
+            for (k, (vertex, (id, p))) in vertices.iter().zip(&hull).enumerate() {
+                assert_eq!(vertex.order, k);
+                assert_eq!(vertex.original, *id);
+                assert_eq!(vertex.point, *p);
+            }
         }
 
         #[test]
@@ -403,5 +5979,213 @@ mod tests {
 
             assert_eq!(hull, expected);
         }
+
+        #[test]
+        fn sweep_parity() {
+            let points = load_question_mark();
+            let concavities = [0., 20., 40., f32::INFINITY];
+            let sweep = concave_hull_sweep(&points, &concavities);
+
+            assert_eq!(sweep.len(), concavities.len());
+            for (hull, concavity) in sweep.into_iter().zip(concavities) {
+                assert_eq!(hull, concave_hull(&points, concavity));
+            }
+        }
+
+        #[test]
+        fn deepest_protrusion() {
+            let points = load_question_mark();
+            let depths = protrusion_depths(&points, 40.);
+
+            // Every hull point is reported, in hull order
+            let hull = concave_hull(&points, 40.);
+            assert!(
+                depths
+                    .iter()
+                    .map(|(id, _)| id)
+                    .eq(hull.iter().map(|(id, _)| id))
+            );
+
+            // The inside of the hook
+            let (deepest, depth) = depths
+                .into_iter()
+                .max_by(|a, b| a.1.total_cmp(&b.1))
+                .unwrap();
+            assert_eq!(deepest, 43);
+            assert!((depth - 132.07).abs() < 0.01);
+        }
+
+        #[test]
+        fn convex_stats() {
+            let points = load_question_mark();
+
+            assert!(concave_hull_with_stats(&points, f32::INFINITY).is_convex);
+            assert!(!concave_hull_with_stats(&points, 40.).is_convex);
+        }
+
+        #[test]
+        fn eager_finalize_parity() {
+            let points = load_question_mark();
+
+            // Eager finalization only applies with stable ordering
+            for concavity in [0., 10., 20., 40., f32::INFINITY] {
+                let builder = ConcaveHullBuilder::new(concavity).stable_ordering(true);
+                assert_eq!(
+                    builder.compute(&points),
+                    builder.clone().eager_finalize(false).compute(&points)
+                );
+            }
+
+            // Edges which are already short enough never go through the queue
+            let builder = ConcaveHullBuilder::new(40.).stable_ordering(true);
+            assert_eq!(builder.count_queue_operations(&points), 63);
+            assert_eq!(
+                builder
+                    .clone()
+                    .eager_finalize(false)
+                    .count_queue_operations(&points),
+                147
+            );
+        }
+
+        #[test]
+        fn warm_start() {
+            let mut points = load_question_mark();
+            let previous: Vec<usize> = concave_hull(&points, 40.)
+                .iter()
+                .map(|(id, _)| *id)
+                .collect();
+
+            // Nudge one of the hull points, as if it moved a little between frames
+            points[previous[10]] += Vector::new(0.5, 0.5);
+
+            let warm = concave_hull_warm(&previous, &points, 40.);
+            let full = concave_hull(&points, 40.);
+            assert_eq!(validate_hull(&points, &warm), Ok(()));
+            assert!(hausdorff_distance(&warm, &full) < 1.);
+
+            // A previous hull which no longer makes sense falls back to a full recompute
+            assert_eq!(concave_hull_warm(&[0, 1, 2], &points, 40.), full);
+            assert_eq!(concave_hull_warm(&[0, 1, points.len()], &points, 40.), full);
+        }
+
+        #[test]
+        fn grid_search() {
+            let points = load_question_mark();
+            let (factors, knns) = ([1., 2., 4.], [1, 3]);
+            let (factor, k) =
+                parameter_grid_search(&points, &factors, &knns, |result| -area(&result.hull))
+                    .unwrap();
+
+            // The smallest area is the tightest hull, which is the smallest factor of the smaller spacing
+            assert_eq!((factor, k), (1., 1));
+            let best = area(&concave_hull_nn_relative(&points, 1.));
+            for &factor in &factors {
+                assert!(best <= area(&concave_hull_nn_relative(&points, factor)));
+            }
+
+            // Ties go to the first combination
+            assert_eq!(
+                parameter_grid_search(&points, &factors, &knns, |_| 0.),
+                Some((1., 1))
+            );
+
+            // With nothing to search, or no scores, there's no best combination
+            assert_eq!(parameter_grid_search(&points, &[], &knns, |_| 0.), None);
+            assert_eq!(
+                parameter_grid_search(&points, &factors, &knns, |_| f32::NAN),
+                None
+            );
+        }
+
+        #[test]
+        fn fix_every_convex_edge() {
+            let points = load_question_mark();
+            let convex = concave_hull(&points, f32::INFINITY);
+
+            for concavity in [0., 20., 40.] {
+                let hull = ConcaveHullBuilder::new(concavity)
+                    .fix_longest_convex(usize::MAX)
+                    .compute(&points);
+                assert_eq!(hull, convex);
+            }
+        }
+
+        #[test]
+        fn morph_ends() {
+            let points = load_question_mark();
+            let (loose, tight) = (
+                concave_hull(&points, f32::INFINITY),
+                concave_hull(&points, 20.),
+            );
+
+            for (t, hull) in [(0., &loose), (1., &tight)] {
+                let morphed = morph_hulls(&points, f32::INFINITY, 20., t);
+                assert!(hull.iter().all(|(_, p)| morphed.contains(p)));
+
+                let morphed: Vec<(usize, Point)> = morphed.into_iter().enumerate().collect();
+                assert!((area(&morphed) - area(hull)).abs() < area(hull) * 1e-4);
+            }
+
+            // Every step has the same number of points, so frames can be drawn with the same mesh
+            let half = morph_hulls(&points, f32::INFINITY, 20., 0.5);
+            assert_eq!(
+                half.len(),
+                morph_hulls(&points, f32::INFINITY, 20., 0.).len()
+            );
+        }
+
+        #[test]
+        fn mesh() {
+            let points = load_question_mark();
+            let mesh = hull_mesh(&points, 40.);
+            let hull = concave_hull(&points, 40.);
+
+            let outline: Vec<(usize, Point)> = mesh
+                .outline
+                .iter()
+                .copied()
+                .zip(mesh.vertices.iter().copied())
+                .collect();
+            assert_eq!(outline, hull);
+            assert_eq!(mesh.triangles.len(), hull.len() - 2);
+
+            let triangles: f32 = mesh
+                .triangles
+                .iter()
+                .map(|triangle| area(&triangle.map(|k| (k, mesh.vertices[k]))))
+                .sum();
+            assert!((triangles - area(&hull)).abs() < area(&hull) * 1e-4);
+        }
+
+        #[test]
+        #[cfg(feature = "f64")]
+        fn refs_parity() {
+            use crate::f64::{Point, concave_hull, concave_hull_refs};
+
+            let points: Vec<Point> = load_question_mark()
+                .iter()
+                .map(|point| Point::new(point.x.into(), point.y.into()))
+                .collect();
+            let owned = concave_hull(&points, 40.);
+            let refs = concave_hull_refs(&points, 40.);
+
+            assert_eq!(refs.len(), owned.len());
+            for ((ref_id, ref_point), (id, point)) in refs.iter().zip(&owned) {
+                assert_eq!(ref_id, id);
+                assert_eq!(*ref_point, point);
+                assert!(std::ptr::eq(*ref_point, &points[*id]));
+            }
+        }
+
+        #[test]
+        fn boundary_complexity_ordering() {
+            let points = load_question_mark();
+            let crinkly = boundary_complexity(&concave_hull(&points, 0.));
+            let convex = boundary_complexity(&concave_hull(&points, f32::INFINITY));
+
+            assert!((convex - 1.).abs() < 1e-6);
+            assert!(crinkly > convex);
+        }
     }
 }
@@ -0,0 +1,324 @@
+use nalgebra::{Point2 as Point, Vector2 as Vector};
+
+use crate::{HullScalar, rng::SplitMix64};
+
+/// The seed for the shuffle in [`bounding_circle`], so that the same points always give the same circle
+const BOUNDING_CIRCLE_SEED: u64 = 0x5EED_C1C1E;
+
+/// Computes the perimeter of a closed ring of points
+///
+/// The ring is implicitly closed, so the segment from the last point back to the first is included.
+/// Empty and single-point rings have a perimeter of zero.
+pub(crate) fn perimeter<T: HullScalar>(ring: impl IntoIterator<Item = Point<T>>) -> T {
+    let mut ring = ring.into_iter();
+    let Some(first) = ring.next() else {
+        return T::zero();
+    };
+
+    let mut total = T::zero();
+    let mut prev = first;
+    for p in ring {
+        total += (p - prev).norm();
+        prev = p;
+    }
+
+    total + (first - prev).norm()
+}
+
+/// Computes the length of the longest edge of a closed ring of points
+///
+/// The ring is implicitly closed, so the segment from the last point back to the first is included.
+/// Empty and single-point rings have no edges, so their longest edge has a length of zero.
+pub(crate) fn longest_edge<T: HullScalar>(ring: &[Point<T>]) -> T {
+    (0..ring.len())
+        .map(|k| (ring[(k + 1) % ring.len()] - ring[k]).norm())
+        .fold(T::zero(), |longest, length| longest.max(length))
+}
+
+/// Ratio of the hull's perimeter to the perimeter of its convex hull
+///
+/// `convex` must be the convex hull of the points in `hull`.
+/// Since the convex hull is the shortest ring enclosing the points, the result is always at least one.
+pub(crate) fn boundary_complexity<T: HullScalar>(
+    hull: &[(usize, Point<T>)],
+    convex: &[Point<T>],
+) -> T {
+    let convex_perimeter = perimeter(convex.iter().copied());
+    if convex_perimeter <= T::zero() {
+        // Degenerate hull with no extent, which is trivially as simple as it gets
+        return T::one();
+    }
+
+    perimeter(hull.iter().map(|(_, p)| *p)) / convex_perimeter
+}
+
+/// A box at any angle, as its center, its two (unit length, perpendicular) axes, and its half-extent along each axis
+type OrientedBox<T> = (Point<T>, [Vector<T>; 2], [T; 2]);
+
+/// Finds the minimum-area oriented bounding box of a convex ring of points
+///
+/// The minimum-area box always has a side flush with one of the edges of the convex hull,
+/// so every edge direction is tried, and the box with the smallest area is kept.
+/// This is O(h²) in the number of convex hull points, which is usually small.
+///
+/// Returns the center of the box, its two (unit length, perpendicular) axes, and its half-extent along each axis.
+/// The first axis is parallel to the edge the box is flush with.
+/// Rings which are a single point (or have no edges with any length) get a box aligned with the coordinate axes.
+/// Empty rings get a box with zero extent at the origin.
+pub(crate) fn oriented_bounding_box<T: HullScalar>(convex: &[Point<T>]) -> OrientedBox<T> {
+    let Some(first) = convex.first() else {
+        return (Point::origin(), [Vector::x(), Vector::y()], [T::zero(); 2]);
+    };
+
+    let mut axes: Vec<Vector<T>> = (0..convex.len())
+        .filter_map(|k| (convex[(k + 1) % convex.len()] - convex[k]).try_normalize(T::zero()))
+        .collect();
+    if axes.is_empty() {
+        axes.push(Vector::x());
+    }
+
+    let mut best: Option<(T, OrientedBox<T>)> = None;
+    for u in axes {
+        let v = Vector::new(-u.y, u.x);
+
+        // Project every point onto both axes, relative to the first point
+        let (mut min_u, mut max_u, mut min_v, mut max_v) =
+            (T::zero(), T::zero(), T::zero(), T::zero());
+        for p in convex {
+            let offset = p - first;
+            let (along_u, along_v) = (offset.dot(&u), offset.dot(&v));
+            min_u = min_u.min(along_u);
+            max_u = max_u.max(along_u);
+            min_v = min_v.min(along_v);
+            max_v = max_v.max(along_v);
+        }
+
+        let area = (max_u - min_u) * (max_v - min_v);
+        if best
+            .as_ref()
+            .is_none_or(|(best_area, ..)| area < *best_area)
+        {
+            let two: T = nalgebra::convert(2.0);
+            let center = first + u * ((min_u + max_u) / two) + v * ((min_v + max_v) / two);
+            let half_extents = [(max_u - min_u) / two, (max_v - min_v) / two];
+            best = Some((area, (center, [u, v], half_extents)));
+        }
+    }
+
+    let (_, oriented_box) = best.expect("There is always at least one axis");
+    oriented_box
+}
+
+/// Finds the smallest circle enclosing a set of points, with Welzl's algorithm
+///
+/// This is the iterative form of Welzl's algorithm: points are added one at a time, and whenever one lands outside of the circle so far,
+/// the circle is rebuilt with that point on its boundary, from the points before it.
+/// Points are visited in a shuffled (but fixed) order, which makes this O(n) on average, whatever order they come in.
+/// Points count as inside if they're within a tiny relative tolerance of the circle, so rounding doesn't rebuild it needlessly.
+///
+/// Returns the center and radius of the circle.
+/// A single point gets a circle with zero radius, and an empty set of points gets that same circle at the origin.
+pub(crate) fn bounding_circle<T: HullScalar>(points: &[Point<T>]) -> (Point<T>, T) {
+    if points.is_empty() {
+        return (Point::origin(), T::zero());
+    }
+
+    let mut order = points.to_vec();
+    let mut rng = SplitMix64::new(BOUNDING_CIRCLE_SEED);
+    for k in (1..order.len()).rev() {
+        let swap = (rng.next_u64() % (k as u64 + 1)) as usize;
+        order.swap(k, swap);
+    }
+
+    let tolerance: T = nalgebra::convert(1e-5);
+    let inside = |(center, radius): (Point<T>, T), p: &Point<T>| {
+        (p - center).norm() <= radius * (T::one() + tolerance)
+    };
+    let two: T = nalgebra::convert(2.0);
+    let diameter = |a: Point<T>, b: Point<T>| {
+        let center = nalgebra::center(&a, &b);
+        (center, (a - b).norm() / two)
+    };
+
+    let mut circle = (order[0], T::zero());
+    for i in 0..order.len() {
+        if inside(circle, &order[i]) {
+            continue;
+        }
+
+        // The new point must be on the boundary of the circle around every point so far
+        circle = (order[i], T::zero());
+        for j in 0..i {
+            if inside(circle, &order[j]) {
+                continue;
+            }
+
+            // As must this one
+            circle = diameter(order[i], order[j]);
+            for &p in &order[..j] {
+                if inside(circle, &p) {
+                    continue;
+                }
+
+                // Three points on the boundary pin the circle down
+                let (a, b) = (order[i], order[j]);
+                let center = circumcentre(a, b, p);
+                circle = if center.x.is_finite() && center.y.is_finite() {
+                    (center, (a - center).norm())
+                } else {
+                    // Rounding can make the three points collinear, in which case the farthest pair spans the circle
+                    [diameter(a, b), diameter(b, p), diameter(p, a)]
+                        .into_iter()
+                        .max_by(|x, y| x.1.total_cmp(&y.1))
+                        .expect("There are three pairs")
+                };
+            }
+        }
+    }
+
+    circle
+}
+
+/// The centre of the circle through `a`, `b`, and `c`
+///
+/// Collinear points have no circumcircle, so this gives non-finite coordinates for them.
+pub(crate) fn circumcentre<T: HullScalar>(a: Point<T>, b: Point<T>, c: Point<T>) -> Point<T> {
+    let (ab, ac) = (b - a, c - a);
+    let d = cross(a, b, c) * nalgebra::convert::<f64, T>(2.0);
+
+    let x = (ac.y * ab.norm_squared() - ab.y * ac.norm_squared()) / d;
+    let y = (ab.x * ac.norm_squared() - ac.x * ab.norm_squared()) / d;
+    a + Vector::new(x, y)
+}
+
+/// Computes the signed area of a closed ring of points, using the shoelace formula
+///
+/// The area is positive for counter-clockwise rings, and negative for clockwise rings.
+pub(crate) fn signed_area<T: HullScalar>(ring: impl IntoIterator<Item = Point<T>>) -> T {
+    let mut ring = ring.into_iter();
+    let Some(first) = ring.next() else {
+        return T::zero();
+    };
+
+    let mut total = T::zero();
+    let mut prev = first;
+    for p in ring {
+        total += prev.x * p.y - p.x * prev.y;
+        prev = p;
+    }
+    total += prev.x * first.y - first.x * prev.y;
+
+    total / nalgebra::convert::<f64, T>(2.0)
+}
+
+/// Computes the symmetric Hausdorff distance between the boundaries of two closed rings of points
+///
+/// This is the furthest that any vertex of either ring is from the boundary of the other ring,
+/// so it is zero exactly when the rings trace the same shape (even if they start from different points),
+/// and grows with the largest local disagreement between them.
+/// Distances are only measured from vertices, which is exact unless the rings cross each other in the middle of an edge.
+///
+/// If either ring is empty, the distance is zero.
+pub(crate) fn hausdorff_distance<T: HullScalar>(a: &[Point<T>], b: &[Point<T>]) -> T {
+    if a.is_empty() || b.is_empty() {
+        return T::zero();
+    }
+
+    let directed = |from: &[Point<T>], to: &[Point<T>]| {
+        from.iter()
+            .map(|p| distance_to_ring(p, to))
+            .fold(T::zero(), |furthest, distance| furthest.max(distance))
+    };
+
+    directed(a, b).max(directed(b, a))
+}
+
+/// Computes the distance from `point` to the nearest point on the boundary of a non-empty closed ring
+fn distance_to_ring<T: HullScalar>(point: &Point<T>, ring: &[Point<T>]) -> T {
+    (0..ring.len())
+        .map(|k| distance_to_segment(point, ring[k], ring[(k + 1) % ring.len()]))
+        .reduce(|nearest, distance| nearest.min(distance))
+        .expect("The ring is not empty")
+}
+
+/// Computes the distance from `point` to the nearest point on the segment from `a` to `b`
+pub(crate) fn distance_to_segment<T: HullScalar>(point: &Point<T>, a: Point<T>, b: Point<T>) -> T {
+    let ab = b - a;
+
+    // Project onto the segment, clamping to its endpoints
+    let length_squared = ab.norm_squared();
+    let t = if length_squared > T::zero() {
+        ((point - a).dot(&ab) / length_squared).clamp(T::zero(), T::one())
+    } else {
+        T::zero()
+    };
+
+    (point - (a + ab * t)).norm()
+}
+
+/// Finds the reflex corners of a counter-clockwise hull: the corners with an interior angle greater than 180 degrees
+///
+/// Returns the original index of each reflex corner, in the same order as `hull`.
+/// Straight corners are not reflex.
+pub(crate) fn reflex_vertices<T: HullScalar>(hull: &[(usize, Point<T>)]) -> Vec<usize> {
+    if hull.len() < 3 {
+        return Vec::new();
+    }
+
+    (0..hull.len())
+        .filter(|&k| {
+            let prev = hull[(k + hull.len() - 1) % hull.len()].1;
+            let next = hull[(k + 1) % hull.len()].1;
+            // Walking counter-clockwise, reflex corners turn right
+            cross(prev, hull[k].1, next) < T::zero()
+        })
+        .map(|k| hull[k].0)
+        .collect()
+}
+
+/// Computes the signed turning angle at each vertex of a closed ring of points, in radians
+///
+/// This is the angle between the incoming and outgoing edges: zero where the ring runs straight,
+/// positive where it turns left, and negative where it turns right, up to π in either direction.
+/// For a counter-clockwise hull, the angles sum to 2π, and reflex corners have negative angles.
+///
+/// Vertices with a zero-length edge on either side, and rings with fewer than three points, have a turning angle of zero.
+pub(crate) fn turning_angles<T: HullScalar>(ring: &[Point<T>]) -> Vec<T> {
+    if ring.len() < 3 {
+        return vec![T::zero(); ring.len()];
+    }
+
+    (0..ring.len())
+        .map(|k| {
+            let prev = ring[(k + ring.len() - 1) % ring.len()];
+            let next = ring[(k + 1) % ring.len()];
+            let (u, v) = (ring[k] - prev, next - ring[k]);
+            if u == Vector::zeros() || v == Vector::zeros() {
+                return T::zero();
+            }
+
+            cross(prev, ring[k], next).atan2(u.dot(&v))
+        })
+        .collect()
+}
+
+/// Checks if the path from `a` to `c` through `b` is straight, to within `epsilon`
+///
+/// This compares the sine of the angle that the path turns by at `b` against `epsilon`,
+/// so paths that double straight back are collinear too, as are paths with a zero length edge.
+pub(crate) fn is_collinear<T: HullScalar>(
+    a: Point<T>,
+    b: Point<T>,
+    c: Point<T>,
+    epsilon: T,
+) -> bool {
+    cross(a, b, c).abs() <= epsilon * (b - a).norm() * (c - b).norm()
+}
+
+/// The z component of the cross product of `b - a` and `c - b`
+///
+/// Positive for left turns, negative for right turns, and zero for straight lines.
+pub(crate) fn cross<T: HullScalar>(a: Point<T>, b: Point<T>, c: Point<T>) -> T {
+    let (u, v) = (b - a, c - b);
+    u.x * v.y - u.y * v.x
+}
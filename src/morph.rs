@@ -0,0 +1,74 @@
+use nalgebra::Point2 as Point;
+
+use crate::HullScalar;
+
+/// Interpolates between two rings, with `t` going from `0` (at `a`) to `1` (at `b`)
+///
+/// Points on the two rings are matched up by how far around their ring they are, as a fraction of its perimeter.
+/// `b` is first rotated to start from its nearest point to the start of `a`, so that the two fractions are measured from the same place.
+/// Both rings are then resampled at the fractions of every vertex of either ring, and each pair of samples is interpolated linearly.
+/// Since every vertex is kept, the result has exactly the shape of `a` at `t = 0`, and of `b` at `t = 1`, with extra points along their edges.
+///
+/// Returns nothing if either ring is empty.
+pub(crate) fn morph_rings<T: HullScalar>(a: &[Point<T>], b: &[Point<T>], t: T) -> Vec<Point<T>> {
+    if a.is_empty() || b.is_empty() {
+        return Vec::new();
+    }
+
+    let start = (0..b.len())
+        .min_by(|&i, &j| {
+            let (di, dj) = ((b[i] - a[0]).norm_squared(), (b[j] - a[0]).norm_squared());
+            di.total_cmp(&dj)
+        })
+        .expect("Ring isn't empty");
+    let b: Vec<Point<T>> = b[start..].iter().chain(&b[..start]).copied().collect();
+
+    let (fractions_a, fractions_b) = (fractions(a), fractions(&b));
+    let mut samples: Vec<T> = fractions_a.iter().chain(&fractions_b).copied().collect();
+    samples.sort_by(|x, y| x.total_cmp(y));
+    samples.dedup();
+
+    samples
+        .into_iter()
+        .map(|s| {
+            let p = point_at(a, &fractions_a, s);
+            let q = point_at(&b, &fractions_b, s);
+            // Weighting both ends (rather than adding on a fraction of the difference) makes the ends exact
+            Point::from(p.coords * (T::one() - t) + q.coords * t)
+        })
+        .collect()
+}
+
+/// How far around the ring each vertex is, as a fraction of its perimeter, starting from zero
+///
+/// A ring with no perimeter (where every point is the same) has every vertex at zero.
+fn fractions<T: HullScalar>(ring: &[Point<T>]) -> Vec<T> {
+    let mut fractions = Vec::with_capacity(ring.len());
+    let mut length = T::zero();
+    for (k, p) in ring.iter().enumerate() {
+        fractions.push(length);
+        length += (ring[(k + 1) % ring.len()] - p).norm();
+    }
+
+    if length > T::zero() {
+        for fraction in &mut fractions {
+            *fraction /= length;
+        }
+    }
+
+    fractions
+}
+
+/// The point which is `s` of the way around the ring, given the fractions of its vertices from [`fractions`]
+fn point_at<T: HullScalar>(ring: &[Point<T>], fractions: &[T], s: T) -> Point<T> {
+    let k = fractions.partition_point(|&f| f <= s).max(1) - 1;
+    let end = fractions.get(k + 1).copied().unwrap_or_else(T::one);
+    let (p, q) = (ring[k], ring[(k + 1) % ring.len()]);
+
+    if end > fractions[k] {
+        let along = (s - fractions[k]) / (end - fractions[k]);
+        p + (q - p) * along
+    } else {
+        p
+    }
+}
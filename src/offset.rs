@@ -0,0 +1,72 @@
+use nalgebra::{Point2 as Point, Vector2 as Vector};
+
+use crate::{HullScalar, metrics::signed_area};
+
+/// How far a mitred corner may stick out, as a multiple of the offset distance
+///
+/// Sharp corners have very long mitres, so they're cut off at this length.
+const MITER_LIMIT: f64 = 4.;
+
+/// Offsets each vertex of a closed ring by `distance`, outwards for positive distances and inwards for negative ones
+///
+/// Each vertex moves along the bisector of its corner, far enough that both of its edges move by `distance` (a mitred join).
+/// Corners which turn towards the offset side pull their edges in, and far enough in, neighbouring vertices would pass each other,
+/// folding the ring over itself.
+/// To stop this, the distance at these corners is clamped so that neither vertex slides more than half way along their shared edge.
+/// Narrow parts of the ring end up offset by less than `distance`, but the offset ring doesn't fold back on itself there.
+/// (Parts of the ring that are far apart along it can still meet, such as either side of a deep notch.)
+/// Corners which turn away from the offset side have their mitres cut off at [`MITER_LIMIT`] times `distance`.
+///
+/// The ring may wind either way. Zero length edges take the direction of the edge on the other side of their vertex.
+pub(crate) fn offset_ring<T: HullScalar>(ring: &[Point<T>], distance: T) -> Vec<Point<T>> {
+    let n = ring.len();
+    let two: T = nalgebra::convert(2.0);
+    let miter_limit: T = nalgebra::convert(MITER_LIMIT);
+
+    // Work in terms of the left side of each edge, which is inside of a counter-clockwise ring
+    let left = if signed_area(ring.iter().copied()) < T::zero() {
+        distance
+    } else {
+        -distance
+    };
+
+    (0..n)
+        .map(|k| {
+            let (prev, p, next) = (ring[(k + n - 1) % n], ring[k], ring[(k + 1) % n]);
+            let (incoming, outgoing) = (p - prev, next - p);
+            let (u1, u2) = match (
+                incoming.try_normalize(T::zero()),
+                outgoing.try_normalize(T::zero()),
+            ) {
+                (Some(u1), Some(u2)) => (u1, u2),
+                (Some(u), None) | (None, Some(u)) => (u, u),
+                (None, None) => return p,
+            };
+
+            // The sine and one plus the cosine of the angle the ring turns by at this corner
+            let turn = u1.x * u2.y - u1.y * u2.x;
+            let straightness = T::one() + u1.dot(&u2);
+            if straightness <= T::default_epsilon() {
+                // The ring doubles straight back, so there's no corner to mitre, only a spike to extend
+                return p + u1 * left.abs();
+            }
+
+            let mut offset = left;
+            if offset * turn > T::zero() {
+                // Each vertex slides along its edges by the distance times the tangent of half the turn
+                let shortest = incoming.norm().min(outgoing.norm());
+                let limit = shortest * straightness / (two * turn.abs());
+                offset = offset.clamp(-limit, limit);
+            }
+
+            let normals = Vector::new(-u1.y - u2.y, u1.x + u2.x);
+            let mut shift = normals * (offset / straightness);
+            let longest = miter_limit * offset.abs();
+            if shift.norm() > longest {
+                shift = shift.normalize() * longest;
+            }
+
+            p + shift
+        })
+        .collect()
+}
@@ -0,0 +1,32 @@
+use nalgebra::Point2 as Point;
+
+use crate::HullScalar;
+
+/// Finds the spikes in a hull: vertices where both edges are longer than `ratio` times the median edge length
+///
+/// A spike is what the hull looks like when it has to reach out to a lone point, far away from the rest of the cloud.
+/// Returns the original index of each spike, in the same order as `hull`.
+/// Hulls with fewer than three vertices have no spikes.
+pub(crate) fn spikes<T: HullScalar>(hull: &[(usize, Point<T>)], ratio: T) -> Vec<usize> {
+    if hull.len() < 3 {
+        return Vec::new();
+    }
+
+    // The length of the edge leaving each vertex
+    let lengths: Vec<T> = (0..hull.len())
+        .map(|k| (hull[(k + 1) % hull.len()].1 - hull[k].1).norm())
+        .collect();
+
+    let mut sorted = lengths.clone();
+    let middle = sorted.len() / 2;
+    let (_, median, _) = sorted.select_nth_unstable_by(middle, |a, b| a.total_cmp(b));
+    let limit = *median * ratio;
+
+    (0..hull.len())
+        .filter(|&k| {
+            let incoming = lengths[(k + hull.len() - 1) % hull.len()];
+            incoming > limit && lengths[k] > limit
+        })
+        .map(|k| hull[k].0)
+        .collect()
+}
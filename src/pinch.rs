@@ -0,0 +1,79 @@
+use nalgebra::Point2 as Point;
+
+use crate::{HullScalar, validate::on_segment};
+
+/// Finds the positions of every vertex of `hull` where the hull touches itself
+///
+/// A vertex is a pinch if another vertex is in exactly the same place (in which case both are returned),
+/// or if it lies on an edge which doesn't end at it.
+/// Positions are returned in increasing order.
+///
+/// This is O(h²) in the number of hull points.
+pub(crate) fn find_pinches<T: HullScalar>(hull: &[(usize, Point<T>)]) -> Vec<usize> {
+    (0..hull.len())
+        .filter(|&k| touch(hull, k).is_some())
+        .collect()
+}
+
+/// Splits `hull` into simple rings at every pinch, so that no ring touches itself
+///
+/// The rings are returned in an unspecified order, each in the same winding order as `hull`.
+/// The vertex at a pinch ends up on every ring which meets there, with the same index.
+/// Hulls without any pinches are returned as a single ring.
+pub(crate) fn split_pinches<T: HullScalar>(
+    hull: Vec<(usize, Point<T>)>,
+) -> Vec<Vec<(usize, Point<T>)>> {
+    let mut pending = vec![hull];
+    let mut rings = Vec::new();
+
+    while let Some(mut ring) = pending.pop() {
+        let Some((k, touching)) = (0..ring.len()).find_map(|k| Some((k, touch(&ring, k)?))) else {
+            rings.push(ring);
+            continue;
+        };
+
+        let (k, other) = match touching {
+            Touch::Vertex(other) => (k, other),
+            Touch::Edge(start) => {
+                // Put a copy of the vertex into the edge it's touching, so that the ring meets itself at a vertex
+                // This shifts the vertex along if it comes after the edge
+                ring.insert(start + 1, ring[k]);
+                (if k > start { k + 1 } else { k }, start + 1)
+            }
+        };
+
+        // Cut the ring into the loop between the two copies, and the rest
+        let (a, b) = (k.min(other), k.max(other));
+        let mut rest = ring.split_off(b);
+        let inner = ring.split_off(a);
+        rest.extend(ring);
+
+        pending.push(rest);
+        pending.push(inner);
+    }
+
+    rings
+}
+
+/// How a vertex touches the rest of its ring
+enum Touch {
+    /// Another vertex, at this position, is in the same place
+    Vertex(usize),
+    /// The vertex lies on the edge starting at this position
+    Edge(usize),
+}
+
+/// Checks whether the vertex at position `k` touches any other part of `ring`
+fn touch<T: HullScalar>(ring: &[(usize, Point<T>)], k: usize) -> Option<Touch> {
+    let n = ring.len();
+    let p = ring[k].1;
+
+    if let Some(other) = (0..n).find(|&m| m != k && ring[m].1 == p) {
+        return Some(Touch::Vertex(other));
+    }
+
+    (0..n)
+        .filter(|&m| m != k && (m + 1) % n != k)
+        .find(|&m| on_segment(&ring[m].1, &ring[(m + 1) % n].1, &p))
+        .map(Touch::Edge)
+}
@@ -0,0 +1,18 @@
+use image::GrayImage;
+use nalgebra::Point2 as Point;
+
+use crate::HullScalar;
+
+/// Collects the centers of the pixels in `mask` which are brighter than `threshold`, in row-major order
+///
+/// Pixel `(x, y)` covers the unit square from `(x, y)` to `(x + 1, y + 1)`, so its center is at `(x + 0.5, y + 0.5)`.
+/// Like the image itself, y counts down from the top row.
+pub(crate) fn foreground_points<T: HullScalar>(mask: &GrayImage, threshold: u8) -> Vec<Point<T>> {
+    let half: T = nalgebra::convert(0.5);
+    let coordinate = |c: u32| nalgebra::convert::<f64, T>(c as f64) + half;
+
+    mask.enumerate_pixels()
+        .filter(|(_, _, pixel)| pixel.0[0] > threshold)
+        .map(|(x, y, _)| Point::new(coordinate(x), coordinate(y)))
+        .collect()
+}
@@ -0,0 +1,232 @@
+use std::collections::{HashMap, HashSet};
+
+use nalgebra::Point2 as Point;
+
+use crate::{HullScalar, metrics::signed_area, result::EdgeKind, triangulate::ear_clip};
+
+/// Splits a hull into its pockets: the regions carved out of the convex hull by concavity
+///
+/// Each pocket is bounded by a chain of hull vertices which starts and ends on convex hull vertices,
+/// and by the straight convex hull edge between those two ends.
+/// Pockets are returned as lists of positions into `hull`, in winding order, including both convex ends.
+/// A pocket may wrap around the end of `hull`.
+///
+/// `convex_hull` contains the original indices of the convex hull points, all of which must be on `hull`.
+pub(crate) fn find_pockets<T: HullScalar>(
+    hull: &[(usize, Point<T>)],
+    convex_hull: &[usize],
+) -> Vec<Vec<usize>> {
+    let convex: HashSet<usize> = convex_hull.iter().copied().collect();
+    let Some(first) = hull.iter().position(|(id, _)| convex.contains(id)) else {
+        // No convex points to anchor pockets to
+        return Vec::new();
+    };
+
+    let mut pockets = Vec::new();
+    let mut chain = Vec::from([first]);
+    for offset in 1..=hull.len() {
+        let pos = (first + offset) % hull.len();
+        chain.push(pos);
+
+        if convex.contains(&hull[pos].0) {
+            if chain.len() > 2 {
+                // There is at least one non-convex point between the ends
+                pockets.push(chain);
+            }
+            chain = Vec::from([pos]);
+        }
+    }
+
+    pockets
+}
+
+/// Labels each vertex of `hull` with the kind of the edge leaving it (towards the next vertex)
+///
+/// Every convex hull point is on the hull, in the same order, so an edge between two convex hull points
+/// skips over no other convex hull point, and must be an unsplit edge of the convex hull.
+/// Every other edge has at least one end that was added by splitting.
+///
+/// `convex_hull` contains the original indices of the convex hull points, all of which must be on `hull`.
+pub(crate) fn classify_edges<T: HullScalar>(
+    hull: &[(usize, Point<T>)],
+    convex_hull: &[usize],
+) -> Vec<(usize, Point<T>, EdgeKind)> {
+    let convex: HashSet<usize> = convex_hull.iter().copied().collect();
+
+    (0..hull.len())
+        .map(|k| {
+            let (id, point) = hull[k];
+            let next = hull[(k + 1) % hull.len()].0;
+            let kind = if convex.contains(&id) && convex.contains(&next) {
+                EdgeKind::Convex
+            } else {
+                EdgeKind::Concave
+            };
+
+            (id, point, kind)
+        })
+        .collect()
+}
+
+/// Computes the (unsigned) area of a pocket, as returned by [`find_pockets`]
+pub(crate) fn pocket_area<T: HullScalar>(hull: &[(usize, Point<T>)], pocket: &[usize]) -> T {
+    // The hull winds counter-clockwise, so the pockets (which are outside it) wind clockwise
+    // The closing edge is the convex hull edge
+    signed_area(pocket.iter().map(|&pos| hull[pos].1)).abs()
+}
+
+/// Replaces every pocket with an area smaller than `min_area` with its straight convex hull edge
+pub(crate) fn fill_notches<T: HullScalar>(
+    hull: Vec<(usize, Point<T>)>,
+    convex_hull: &[usize],
+    min_area: T,
+) -> Vec<(usize, Point<T>)> {
+    let mut keep = vec![true; hull.len()];
+    for pocket in find_pockets(&hull, convex_hull) {
+        if pocket_area(&hull, &pocket) < min_area {
+            for &pos in &pocket[1..pocket.len() - 1] {
+                keep[pos] = false;
+            }
+        }
+    }
+
+    hull.into_iter()
+        .zip(keep)
+        .filter_map(|(vertex, keep)| keep.then_some(vertex))
+        .collect()
+}
+
+/// Computes how deep each hull point protrudes into the pocket it belongs to
+///
+/// The depth of a point is its perpendicular distance from the convex hull edge spanning its pocket.
+/// Points on the convex hull have a depth of zero.
+/// Returns the original index of each point in `hull`, paired with its depth, in the same order as `hull`.
+pub(crate) fn protrusion_depths<T: HullScalar>(
+    hull: &[(usize, Point<T>)],
+    convex_hull: &[usize],
+) -> Vec<(usize, T)> {
+    let mut depths: Vec<(usize, T)> = hull.iter().map(|(id, _)| (*id, T::zero())).collect();
+
+    for pocket in find_pockets(hull, convex_hull) {
+        let a = hull[pocket[0]].1;
+        let b = hull[pocket[pocket.len() - 1]].1;
+        let chord = b - a;
+        let chord_length = chord.norm();
+
+        for &pos in &pocket[1..pocket.len() - 1] {
+            let v = hull[pos].1 - a;
+            // The cross product is the area of the parallelogram, so dividing by the base gives the height
+            depths[pos].1 = (chord.x * v.y - chord.y * v.x).abs() / chord_length;
+        }
+    }
+
+    depths
+}
+
+/// Triangulates each of the pockets in a hull
+///
+/// Each pocket (see [`find_pockets`]) is closed off by its convex hull edge, and triangulated with ear clipping.
+/// Returns the triangles from every pocket, with their vertices as original indices and values, wound counter-clockwise.
+pub(crate) fn pocket_triangles<T: HullScalar>(
+    hull: &[(usize, Point<T>)],
+    convex_hull: &[usize],
+) -> Vec<[(usize, Point<T>); 3]> {
+    let mut triangles = Vec::new();
+    for pocket in find_pockets(hull, convex_hull) {
+        let ring: Vec<Point<T>> = pocket.iter().map(|&pos| hull[pos].1).collect();
+        triangles.extend(
+            ear_clip(&ring)
+                .into_iter()
+                .map(|triangle| triangle.map(|k| hull[pocket[k]])),
+        );
+    }
+
+    triangles
+}
+
+/// Computes the regions which are inside one of two hulls of the same point cloud, but not the other
+///
+/// Both hulls contain every convex hull point, in the same order, so they only differ inside of pockets.
+/// Within each pocket, the two chains of hull points are split further at every point they share,
+/// and each pair of sub-chains which differ encloses one region.
+/// Regions are returned as rings of points, made up of the sub-chain from `a` followed by the sub-chain from `b` in reverse.
+///
+/// If the two sub-chains cross each other between shared points, the ring will be self-intersecting,
+/// and filling it with the even-odd rule gives the right regions.
+pub(crate) fn hull_difference<T: HullScalar>(
+    a: &[(usize, Point<T>)],
+    b: &[(usize, Point<T>)],
+    convex_hull: &[usize],
+) -> Vec<Vec<Point<T>>> {
+    let chains_b: HashMap<usize, Vec<(usize, Point<T>)>> = convex_chains(b, convex_hull)
+        .into_iter()
+        .map(|chain| (chain[0].0, chain))
+        .collect();
+
+    let mut regions = Vec::new();
+    for chain_a in convex_chains(a, convex_hull) {
+        let Some(chain_b) = chains_b.get(&chain_a[0].0) else {
+            continue;
+        };
+        let positions_b: HashMap<usize, usize> = chain_b
+            .iter()
+            .enumerate()
+            .map(|(pos, (id, _))| (*id, pos))
+            .collect();
+
+        // Split both chains at the points they share, keeping the splits in order along both
+        let mut splits = Vec::from([(0, 0)]);
+        for (pos_a, (id, _)) in chain_a.iter().enumerate().skip(1) {
+            let last_b = splits.last().map_or(0, |(_, pos_b)| *pos_b);
+            if let Some(&pos_b) = positions_b.get(id).filter(|&&pos_b| pos_b > last_b) {
+                splits.push((pos_a, pos_b));
+            }
+        }
+
+        for window in splits.windows(2) {
+            let [(start_a, start_b), (end_a, end_b)] = [window[0], window[1]];
+            let sub_a = &chain_a[start_a..=end_a];
+            let sub_b = &chain_b[start_b..=end_b];
+            if sub_a.len() == 2 && sub_b.len() == 2 {
+                // Both hulls follow the same edge
+                continue;
+            }
+
+            regions.push(
+                sub_a
+                    .iter()
+                    .chain(sub_b[1..sub_b.len() - 1].iter().rev())
+                    .map(|(_, p)| *p)
+                    .collect(),
+            );
+        }
+    }
+
+    regions
+}
+
+/// Splits a hull into chains between consecutive convex hull points, including both ends
+///
+/// Unlike [`find_pockets`], this includes chains which are just a single convex hull edge.
+fn convex_chains<T: HullScalar>(
+    hull: &[(usize, Point<T>)],
+    convex_hull: &[usize],
+) -> Vec<Vec<(usize, Point<T>)>> {
+    let convex: HashSet<usize> = convex_hull.iter().copied().collect();
+    let Some(first) = hull.iter().position(|(id, _)| convex.contains(id)) else {
+        return Vec::new();
+    };
+
+    let mut chains = Vec::new();
+    let mut chain = Vec::from([hull[first]]);
+    for offset in 1..=hull.len() {
+        let vertex = hull[(first + offset) % hull.len()];
+        chain.push(vertex);
+
+        if convex.contains(&vertex.0) {
+            chains.push(std::mem::replace(&mut chain, Vec::from([vertex])));
+        }
+    }
+
+    chains
+}
@@ -0,0 +1,71 @@
+use nalgebra::{Point2 as Point, Scalar};
+
+/// Anything which can be read as a 2D point
+///
+/// Implement this for your own point type to pass it straight to `concave_hull_generic`,
+/// without converting it to a [`Point`] yourself first.
+///
+/// With the `half` feature enabled, this is implemented for pairs of `half::f16` coordinates, at both precisions.
+/// The hull math can't run at `f16` precision, so the coordinates are widened first (which is exact),
+/// and the hull is computed and returned at the wider precision.
+/// Keep in mind that `f16` only has 11 bits of precision, so points which are close together (relative to their distance
+/// from the origin) can round to the same value, which breaks the assumption that there are no repeat points.
+pub trait AsPoint2<T> {
+    /// The x and y coordinates of the point, in that order
+    fn xy(&self) -> (T, T);
+}
+
+impl<T: Scalar + Copy> AsPoint2<T> for Point<T> {
+    fn xy(&self) -> (T, T) {
+        (self.x, self.y)
+    }
+}
+
+impl<T: Copy> AsPoint2<T> for (T, T) {
+    fn xy(&self) -> (T, T) {
+        *self
+    }
+}
+
+impl<T: Copy> AsPoint2<T> for [T; 2] {
+    fn xy(&self) -> (T, T) {
+        (self[0], self[1])
+    }
+}
+
+impl<T, P: AsPoint2<T>> AsPoint2<T> for &P {
+    fn xy(&self) -> (T, T) {
+        (*self).xy()
+    }
+}
+
+// Half precision floats can't be used for the math directly, since `half::f16` doesn't implement nalgebra's `RealField`
+// (and neither type is ours, so we can't implement it either)
+// Instead, they are widened on the way in, which is exact, so the hull is just computed at the higher precision
+#[cfg(feature = "half")]
+impl AsPoint2<f32> for (half::f16, half::f16) {
+    fn xy(&self) -> (f32, f32) {
+        (self.0.to_f32(), self.1.to_f32())
+    }
+}
+
+#[cfg(feature = "half")]
+impl AsPoint2<f32> for [half::f16; 2] {
+    fn xy(&self) -> (f32, f32) {
+        (self[0].to_f32(), self[1].to_f32())
+    }
+}
+
+#[cfg(feature = "half")]
+impl AsPoint2<f64> for (half::f16, half::f16) {
+    fn xy(&self) -> (f64, f64) {
+        (self.0.to_f64(), self.1.to_f64())
+    }
+}
+
+#[cfg(feature = "half")]
+impl AsPoint2<f64> for [half::f16; 2] {
+    fn xy(&self) -> (f64, f64) {
+        (self[0].to_f64(), self[1].to_f64())
+    }
+}
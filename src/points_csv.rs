@@ -0,0 +1,145 @@
+use std::{error::Error, fmt, io::Read, str::FromStr};
+
+use csv::{Position, ReaderBuilder};
+use nalgebra::Point2 as Point;
+
+use crate::HullScalar;
+
+/// Options for reading points from a CSV file
+///
+/// By default, the file is comma separated with no header row, and the x and y coordinates are the first two columns.
+/// Options are set with chained method calls.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CsvOptions {
+    pub(crate) x_column: usize,
+    pub(crate) y_column: usize,
+    pub(crate) delimiter: u8,
+    pub(crate) has_headers: bool,
+}
+
+impl Default for CsvOptions {
+    fn default() -> Self {
+        Self {
+            x_column: 0,
+            y_column: 1,
+            delimiter: b',',
+            has_headers: false,
+        }
+    }
+}
+
+impl CsvOptions {
+    /// Creates the default options
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets which columns hold the x and y coordinates, counting from zero
+    ///
+    /// Any other columns are ignored.
+    pub fn columns(mut self, x_column: usize, y_column: usize) -> Self {
+        self.x_column = x_column;
+        self.y_column = y_column;
+        self
+    }
+
+    /// Sets the byte which separates fields
+    pub fn delimiter(mut self, delimiter: u8) -> Self {
+        self.delimiter = delimiter;
+        self
+    }
+
+    /// Sets whether the first row is a header row, which is skipped
+    pub fn has_headers(mut self, has_headers: bool) -> Self {
+        self.has_headers = has_headers;
+        self
+    }
+}
+
+/// The reasons a record can fail to be read as a point
+///
+/// Lines are counted from one, and include the header row (if any).
+#[derive(Debug)]
+pub enum CsvPointError {
+    /// The underlying CSV reader failed, either due to IO or malformed CSV
+    Csv(csv::Error),
+    /// The record on `line` has no field in `column`
+    MissingColumn {
+        /// Line of the record in the file
+        line: u64,
+        /// The missing column
+        column: usize,
+    },
+    /// The field in `column` on `line` is not a number
+    InvalidNumber {
+        /// Line of the record in the file
+        line: u64,
+        /// The column of the field
+        column: usize,
+    },
+}
+
+impl fmt::Display for CsvPointError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Csv(error) => write!(f, "failed to read CSV: {error}"),
+            Self::MissingColumn { line, column } => {
+                write!(f, "line {line} has no column {column}")
+            }
+            Self::InvalidNumber { line, column } => {
+                write!(f, "column {column} on line {line} is not a number")
+            }
+        }
+    }
+}
+
+impl Error for CsvPointError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Csv(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<csv::Error> for CsvPointError {
+    fn from(error: csv::Error) -> Self {
+        Self::Csv(error)
+    }
+}
+
+/// Lazily reads points from CSV data, one record at a time
+///
+/// Fields have surrounding whitespace trimmed before being parsed.
+/// Reading continues after a bad record, so callers can choose whether to stop at the first error or skip bad records.
+pub(crate) fn read_points_csv<T: HullScalar + FromStr, R: Read>(
+    reader: R,
+    options: &CsvOptions,
+) -> impl Iterator<Item = Result<Point<T>, CsvPointError>> + use<T, R> {
+    let options = *options;
+
+    ReaderBuilder::new()
+        .has_headers(options.has_headers)
+        .delimiter(options.delimiter)
+        // Records only need the coordinate columns, which is checked for each record below
+        .flexible(true)
+        .from_reader(reader)
+        .into_records()
+        .map(move |record| {
+            let record = record?;
+            let line = record.position().map_or(0, Position::line);
+            let field = |column: usize| -> Result<T, CsvPointError> {
+                record
+                    .get(column)
+                    .ok_or(CsvPointError::MissingColumn { line, column })?
+                    .trim()
+                    .parse()
+                    .map_err(|_| CsvPointError::InvalidNumber { line, column })
+            };
+
+            Ok(Point::new(
+                field(options.x_column)?,
+                field(options.y_column)?,
+            ))
+        })
+}
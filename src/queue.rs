@@ -0,0 +1,210 @@
+#[cfg(feature = "benches")]
+use std::cell::Cell;
+use std::{
+    cmp::Ordering,
+    collections::{BinaryHeap, HashSet},
+};
+
+use crate::{
+    HullScalar,
+    edge::{Edge, cmp_edge_ties},
+};
+
+/// A max-priority queue of the edges which might still be split
+///
+/// The gift opening process is generic over this, so that other queue implementations can be swapped in and compared.
+/// With stable ordering, [`QueuedEdge`] has a total order, so every correct implementation pops edges in exactly the same order,
+/// and produces exactly the same hull.
+pub(crate) trait EdgeQueue<T: HullScalar> {
+    /// The set used alongside this queue to keep track of which points are on the hull
+    type Boundary: BoundarySet;
+
+    /// Creates an empty queue, with room for at least `capacity` edges
+    fn with_capacity(capacity: usize) -> Self;
+
+    /// Adds an edge to the queue
+    fn push(&mut self, edge: QueuedEdge<T>);
+
+    /// Removes the edge with the highest priority, or returns `None` if the queue is empty
+    fn pop(&mut self) -> Option<QueuedEdge<T>>;
+
+    /// Iterates over every edge in the queue, in an arbitrary order
+    fn iter(&self) -> impl Iterator<Item = &QueuedEdge<T>> + Send;
+
+    /// The number of edges in the queue
+    fn len(&self) -> usize;
+}
+
+/// A set of point indices, for keeping track of which points are on the hull
+///
+/// Like [`EdgeQueue`], this lets small clouds swap the hashing machinery for a plain [`Vec`].
+pub(crate) trait BoundarySet: FromIterator<usize> {
+    /// Checks if the point is in the set
+    fn contains(&self, i: usize) -> bool;
+
+    /// Adds a point to the set
+    fn insert(&mut self, i: usize);
+
+    /// Makes room for at least `additional` more points
+    fn reserve(&mut self, additional: usize);
+}
+
+impl BoundarySet for HashSet<usize> {
+    fn contains(&self, i: usize) -> bool {
+        HashSet::contains(self, &i)
+    }
+
+    fn insert(&mut self, i: usize) {
+        HashSet::insert(self, i);
+    }
+
+    fn reserve(&mut self, additional: usize) {
+        HashSet::reserve(self, additional);
+    }
+}
+
+/// With only a handful of points on the hull, a scan over a plain [`Vec`] is faster than hashing
+impl BoundarySet for Vec<usize> {
+    fn contains(&self, i: usize) -> bool {
+        self.as_slice().contains(&i)
+    }
+
+    fn insert(&mut self, i: usize) {
+        if !BoundarySet::contains(self, i) {
+            self.push(i);
+        }
+    }
+
+    fn reserve(&mut self, additional: usize) {
+        Vec::reserve(self, additional);
+    }
+}
+
+/// The queue used unless another is asked for
+pub(crate) type DefaultQueue<T> = BinaryHeap<QueuedEdge<T>>;
+
+impl<T: HullScalar> EdgeQueue<T> for BinaryHeap<QueuedEdge<T>> {
+    type Boundary = HashSet<usize>;
+
+    fn with_capacity(capacity: usize) -> Self {
+        BinaryHeap::with_capacity(capacity)
+    }
+
+    fn push(&mut self, edge: QueuedEdge<T>) {
+        BinaryHeap::push(self, edge);
+    }
+
+    fn pop(&mut self) -> Option<QueuedEdge<T>> {
+        BinaryHeap::pop(self)
+    }
+
+    fn iter(&self) -> impl Iterator<Item = &QueuedEdge<T>> + Send {
+        BinaryHeap::iter(self)
+    }
+
+    fn len(&self) -> usize {
+        BinaryHeap::len(self)
+    }
+}
+
+/// A queue which scans for the highest priority edge on every pop
+///
+/// Pops are O(n), but with only a handful of edges, a scan over a plain [`Vec`] is faster than keeping a heap in order.
+/// The points on the hull are kept in a plain [`Vec`] too.
+pub(crate) struct LinearQueue<T: HullScalar>(Vec<QueuedEdge<T>>);
+
+impl<T: HullScalar> EdgeQueue<T> for LinearQueue<T> {
+    type Boundary = Vec<usize>;
+
+    fn with_capacity(capacity: usize) -> Self {
+        Self(Vec::with_capacity(capacity))
+    }
+
+    fn push(&mut self, edge: QueuedEdge<T>) {
+        self.0.push(edge);
+    }
+
+    fn pop(&mut self) -> Option<QueuedEdge<T>> {
+        let (max, _) = self.0.iter().enumerate().max_by(|a, b| a.1.cmp(b.1))?;
+        Some(self.0.swap_remove(max))
+    }
+
+    fn iter(&self) -> impl Iterator<Item = &QueuedEdge<T>> + Send {
+        self.0.iter()
+    }
+
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+}
+
+#[cfg(feature = "benches")]
+thread_local! {
+    /// The number of pushes and pops made by every [`CountingQueue`] on this thread
+    pub(crate) static QUEUE_OPERATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+/// A [`DefaultQueue`] which counts its pushes and pops in [`QUEUE_OPERATIONS`], to measure how much work the queue does
+#[cfg(feature = "benches")]
+pub(crate) struct CountingQueue<T: HullScalar>(DefaultQueue<T>);
+
+#[cfg(feature = "benches")]
+impl<T: HullScalar> EdgeQueue<T> for CountingQueue<T> {
+    type Boundary = HashSet<usize>;
+
+    fn with_capacity(capacity: usize) -> Self {
+        Self(DefaultQueue::with_capacity(capacity))
+    }
+
+    fn push(&mut self, edge: QueuedEdge<T>) {
+        QUEUE_OPERATIONS.set(QUEUE_OPERATIONS.get() + 1);
+        self.0.push(edge);
+    }
+
+    fn pop(&mut self) -> Option<QueuedEdge<T>> {
+        QUEUE_OPERATIONS.set(QUEUE_OPERATIONS.get() + 1);
+        self.0.pop()
+    }
+
+    fn iter(&self) -> impl Iterator<Item = &QueuedEdge<T>> + Send {
+        self.0.iter()
+    }
+
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+}
+
+/// An edge waiting to be split, ordered by its priority
+pub(crate) struct QueuedEdge<T: HullScalar> {
+    pub(crate) priority: T,
+    pub(crate) edge: Edge<T>,
+    /// Whether ties in priority are broken by the edge's coordinates (see [`cmp_edge_ties`]), rather than left equal
+    pub(crate) stable: bool,
+}
+
+impl<T: HullScalar> PartialEq for QueuedEdge<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<T: HullScalar> Eq for QueuedEdge<T> {}
+
+impl<T: HullScalar> Ord for QueuedEdge<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let order = self.priority.total_cmp(&other.priority);
+        if self.stable {
+            // Break ties by coordinates, so that equal priorities never fall back to heap order
+            order.then_with(|| cmp_edge_ties(&self.edge, &other.edge))
+        } else {
+            order
+        }
+    }
+}
+
+impl<T: HullScalar> PartialOrd for QueuedEdge<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
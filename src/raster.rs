@@ -0,0 +1,56 @@
+use nalgebra::Point2 as Point;
+
+use crate::HullScalar;
+
+/// Scan-fills a closed ring of points into a row-major `width` by `height` mask
+///
+/// The ring is given in mask coordinates, where the cell in column `x` and row `y` covers the unit square
+/// from `(x, y)` to `(x + 1, y + 1)`.
+/// A cell is filled if its center is inside of the ring, using the even-odd rule.
+/// Centers exactly on a left or bottom edge count as inside, and centers on a right or top edge count as outside,
+/// so two rings which share an edge never both fill the same cell.
+///
+/// Each row is filled by finding where the ring crosses the horizontal line through its cell centers,
+/// so this is O(height × ring length), plus the number of filled cells.
+/// Rings with fewer than three points fill nothing.
+pub(crate) fn scan_fill<T: HullScalar>(
+    ring: &[Point<T>],
+    width: usize,
+    height: usize,
+) -> Vec<bool> {
+    let mut mask = vec![false; width * height];
+    if ring.len() < 3 {
+        return mask;
+    }
+
+    let half: T = nalgebra::convert(0.5);
+    let mut crossings: Vec<T> = Vec::new();
+    for row in 0..height {
+        let y = nalgebra::convert::<f64, T>(row as f64) + half;
+
+        // Find every x coordinate where the ring crosses this row, counting each vertex only once
+        crossings.clear();
+        for k in 0..ring.len() {
+            let (a, b) = (ring[k], ring[(k + 1) % ring.len()]);
+            if (a.y <= y) != (b.y <= y) {
+                crossings.push(a.x + (y - a.y) * (b.x - a.x) / (b.y - a.y));
+            }
+        }
+        crossings.sort_unstable_by(|a, b| a.total_cmp(b));
+
+        // Between each pair of crossings is inside of the ring
+        for span in crossings.chunks_exact(2) {
+            let first = column_at(span[0] - half, width);
+            let last = column_at(span[1] - half, width);
+            mask[row * width + first..row * width + last].fill(true);
+        }
+    }
+
+    mask
+}
+
+/// The first column whose center is at or to the right of `x + 0.5`, clamped to the mask
+fn column_at<T: HullScalar>(x: T, width: usize) -> usize {
+    let column = nalgebra::try_convert::<T, f64>(x.ceil()).unwrap_or(0.);
+    column.clamp(0., width as f64) as usize
+}
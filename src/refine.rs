@@ -0,0 +1,95 @@
+use std::collections::HashSet;
+
+use nalgebra::Point2 as Point;
+
+use crate::{HullScalar, metrics::turning_angles};
+
+/// Adds any point which lies on an edge of the hull (within `tolerance`) to the hull, as a vertex
+///
+/// A point lies on an edge if it is within `tolerance` of the edge,
+/// and its projection onto the edge is strictly between the edge's endpoints.
+/// If several points lie on the same edge, they are added in order along the edge.
+/// Points which are already vertices of the hull are never added again.
+///
+/// This is O(n * h), where h is the number of hull points.
+pub(crate) fn include_edge_points<T: HullScalar>(
+    points: &[Point<T>],
+    hull: Vec<(usize, Point<T>)>,
+    tolerance: T,
+) -> Vec<(usize, Point<T>)> {
+    let vertices: HashSet<usize> = hull.iter().map(|(id, _)| *id).collect();
+    let tolerance_squared = tolerance * tolerance;
+
+    let mut refined = Vec::with_capacity(hull.len());
+    for k in 0..hull.len() {
+        let (a, b) = (hull[k].1, hull[(k + 1) % hull.len()].1);
+        let edge = b - a;
+        let length_squared = edge.norm_squared();
+
+        // Find all the points on this edge, along with how far along the edge they are
+        let mut on_edge: Vec<(T, usize)> = Vec::new();
+        if length_squared > T::zero() {
+            for (id, p) in points.iter().enumerate() {
+                if vertices.contains(&id) {
+                    continue;
+                }
+
+                let t = (p - a).dot(&edge) / length_squared;
+                if t <= T::zero() || t >= T::one() {
+                    continue;
+                }
+
+                let projection = a + edge * t;
+                if (p - projection).norm_squared() <= tolerance_squared {
+                    on_edge.push((t, id));
+                }
+            }
+        }
+        on_edge.sort_by(|(t1, _), (t2, _)| t1.total_cmp(t2));
+
+        refined.push(hull[k]);
+        refined.extend(on_edge.into_iter().map(|(_, id)| (id, points[id])));
+    }
+
+    refined
+}
+
+/// Drops reflex corners whose turn is smaller than `max_angle` (in radians), reconnecting their neighbours directly
+///
+/// Removing a reflex corner only ever adds area to the hull, so every point stays inside of it.
+/// Since removing a corner changes the turns at its neighbours, this repeats until no tiny reflex corners are left.
+/// Neighbouring corners are never removed in the same pass, so each removal sees up to date turns on both sides.
+/// Hulls with three or fewer points are returned as-is.
+pub(crate) fn remove_micro_reflex<T: HullScalar>(
+    mut hull: Vec<(usize, Point<T>)>,
+    max_angle: T,
+) -> Vec<(usize, Point<T>)> {
+    while hull.len() > 3 {
+        let ring: Vec<Point<T>> = hull.iter().map(|(_, p)| *p).collect();
+        let angles = turning_angles(&ring);
+
+        let mut removed = vec![false; hull.len()];
+        let mut count = 0;
+        for k in 0..hull.len() {
+            let prev = (k + hull.len() - 1) % hull.len();
+            let next = (k + 1) % hull.len();
+            let tiny = angles[k] < T::zero() && -angles[k] < max_angle;
+            // Always leave at least a triangle
+            if tiny && !removed[prev] && !removed[next] && hull.len() - count > 3 {
+                removed[k] = true;
+                count += 1;
+            }
+        }
+        if count == 0 {
+            break;
+        }
+
+        let mut k = 0;
+        hull.retain(|_| {
+            k += 1;
+            !removed[k - 1]
+        });
+    }
+
+    hull
+}
@@ -0,0 +1,50 @@
+use nalgebra::{Point2 as Point, Scalar};
+
+/// A concave hull, along with some extra information about how it was computed
+#[derive(Debug, Clone, PartialEq)]
+pub struct HullResult<T: Scalar> {
+    /// The hull points, in the same format as returned by `concave_hull`
+    pub hull: Vec<(usize, Point<T>)>,
+    /// Whether the hull is identical to the convex hull of the point cloud
+    ///
+    /// This is checked by comparing vertex counts: it is true when the hull has as many vertices as the convex hull,
+    /// after any notches are filled (but before any edge points are included).
+    /// Every split adds a vertex, so this means that either no edge of the convex hull was split,
+    /// or that every split was undone by filling notches.
+    /// Either way, it usually means that the concavity parameter was too high for any concave features to emerge.
+    pub is_convex: bool,
+}
+
+/// A single vertex of a concave hull, with both of its indices
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HullVertex<T: Scalar> {
+    /// The index of the point in the original point cloud
+    pub original: usize,
+    /// The position of the vertex in the hull, counting from zero in winding order
+    pub order: usize,
+    /// The value of the point
+    pub point: Point<T>,
+}
+
+/// Where an edge of a concave hull came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EdgeKind {
+    /// An edge of the convex hull, which was never split
+    Convex,
+    /// An edge which was made by splitting, and bends in towards the point cloud
+    Concave,
+}
+
+/// A concave hull, triangulated and ready to render
+#[derive(Debug, Clone, PartialEq)]
+pub struct HullMesh<T: Scalar> {
+    /// The hull points, in winding order
+    pub vertices: Vec<Point<T>>,
+    /// Triangles covering the hull, as indices into `vertices`, each wound counter-clockwise
+    pub triangles: Vec<[usize; 3]>,
+    /// The index of each vertex in the original point cloud
+    ///
+    /// `vertices` is already in winding order, so it can be drawn as a line loop as-is.
+    /// This maps each vertex back to its point, so that `vertices[k]` is `points[outline[k]]`.
+    pub outline: Vec<usize>,
+}
@@ -0,0 +1,41 @@
+/// A small, fast, seedable pseudo-random number generator (SplitMix64)
+///
+/// This is nowhere near cryptographically secure, but it's plenty random for jittering points,
+/// and saves us a dependency.
+/// The same seed always produces the same sequence, on every platform.
+#[derive(Debug, Clone)]
+pub(crate) struct SplitMix64 {
+    state: u64,
+}
+
+impl SplitMix64 {
+    /// Creates a new generator from a seed
+    pub(crate) fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    /// Generates the next 64 random bits
+    pub(crate) fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E3779B97F4A7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+        z ^ (z >> 31)
+    }
+
+    /// Generates a number uniformly distributed in `[0, 1)`
+    pub(crate) fn next_f64(&mut self) -> f64 {
+        // Use the top 53 bits, which exactly fill an f64's mantissa
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+/// Picks a random subset of the indices `0..len`, keeping each one with probability `fraction`
+///
+/// The indices are returned in increasing order.
+/// The same seed always picks the same subset, and a `fraction` of one (or more) keeps every index.
+pub(crate) fn sample_indices(len: usize, fraction: f64, seed: u64) -> Vec<usize> {
+    let mut rng = SplitMix64::new(seed);
+
+    (0..len).filter(|_| rng.next_f64() < fraction).collect()
+}
@@ -0,0 +1,184 @@
+use std::{
+    fs, io,
+    path::Path,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use nalgebra::Point2 as Point;
+
+use crate::{HullScalar, metrics::signed_area, validate::is_empty_hull};
+
+/// The shape type code for polygons, which is the only shape type we write
+const POLYGON: i32 = 5;
+
+/// The shape type code for records without any geometry, which polygon files may contain
+const NULL_SHAPE: i32 = 0;
+
+/// The width of the single numeric `ID` field in the attribute table
+const ID_WIDTH: u8 = 10;
+
+/// Writes a hull to a polygon shapefile, as the `.shp`, `.shx`, and `.dbf` files next to `path`
+///
+/// The extension of `path` is replaced for each file, so `hull.shp` and `hull` both write `hull.shp`, `hull.shx`, and `hull.dbf`.
+/// The hull is written as a single polygon record, with a single ring.
+/// Shapefiles expect exterior rings to wind clockwise and to be closed,
+/// so the ring is reversed if it winds counter-clockwise, and the first point is repeated at the end.
+/// Coordinates are always stored as `f64`.
+///
+/// The attribute table has a single numeric `ID` field, which is zero for the hull.
+/// An empty hull is written as a single null shape record, with an all-zero bounding box.
+/// Hulls with one or two points aren't polygons, so they are rejected with [`io::ErrorKind::InvalidInput`].
+pub(crate) fn write_hull_shapefile<T: HullScalar>(
+    path: &Path,
+    hull: &[(usize, Point<T>)],
+) -> io::Result<()> {
+    if is_empty_hull(hull) {
+        return write_files(path, &[0.; 4], &NULL_SHAPE.to_le_bytes());
+    }
+    if hull.len() < 3 {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "hull has fewer than three points, so it isn't a polygon",
+        ));
+    }
+
+    let to_f64 = |x: T| nalgebra::try_convert::<T, f64>(x).unwrap_or(f64::NAN);
+    let mut ring: Vec<Point<f64>> = hull
+        .iter()
+        .map(|(_, p)| Point::new(to_f64(p.x), to_f64(p.y)))
+        .collect();
+    if signed_area(ring.iter().copied()) > 0. {
+        // Counter-clockwise, which shapefiles use for holes
+        ring.reverse();
+    }
+    ring.push(ring[0]);
+
+    let mut bbox = [ring[0].x, ring[0].y, ring[0].x, ring[0].y];
+    for p in &ring {
+        bbox = [
+            bbox[0].min(p.x),
+            bbox[1].min(p.y),
+            bbox[2].max(p.x),
+            bbox[3].max(p.y),
+        ];
+    }
+
+    // Shape type, bounding box, part count, point count, the start of the only part, then the points
+    let mut content = Vec::with_capacity(4 + 32 + 4 + 4 + 4 + 16 * ring.len());
+    content.extend(POLYGON.to_le_bytes());
+    bbox.iter().for_each(|x| content.extend(x.to_le_bytes()));
+    content.extend(1i32.to_le_bytes());
+    content.extend((ring.len() as i32).to_le_bytes());
+    content.extend(0i32.to_le_bytes());
+    for p in &ring {
+        content.extend(p.x.to_le_bytes());
+        content.extend(p.y.to_le_bytes());
+    }
+
+    write_files(path, &bbox, &content)
+}
+
+/// Writes the `.shp`, `.shx`, and `.dbf` files for a single record, given the content of its shape
+///
+/// `bbox` is `[min_x, min_y, max_x, max_y]`, for the file headers.
+fn write_files(path: &Path, bbox: &[f64; 4], content: &[u8]) -> io::Result<()> {
+    let mut shp = file_header(100 + 8 + content.len(), bbox);
+    shp.extend(1i32.to_be_bytes());
+    shp.extend(words(content.len()).to_be_bytes());
+    shp.extend(content);
+
+    // The index has the offset of each record (just after the file header) and its length
+    let mut shx = file_header(100 + 8, bbox);
+    shx.extend(words(100).to_be_bytes());
+    shx.extend(words(content.len()).to_be_bytes());
+
+    fs::write(path.with_extension("shp"), shp)?;
+    fs::write(path.with_extension("shx"), shx)?;
+    fs::write(path.with_extension("dbf"), attribute_table())?;
+
+    Ok(())
+}
+
+/// Converts a length in bytes to the 16-bit words that shapefiles measure lengths in
+fn words(bytes: usize) -> i32 {
+    (bytes / 2) as i32
+}
+
+/// Builds the 100 byte header shared by the `.shp` and `.shx` files
+///
+/// `len` is the total length of the file in bytes, and `bbox` is `[min_x, min_y, max_x, max_y]`.
+fn file_header(len: usize, bbox: &[f64; 4]) -> Vec<u8> {
+    let mut header = Vec::with_capacity(len);
+
+    // File code, then five unused integers
+    header.extend(9994i32.to_be_bytes());
+    header.extend([0; 20]);
+    header.extend(words(len).to_be_bytes());
+    // Version, then shape type
+    header.extend(1000i32.to_le_bytes());
+    header.extend(POLYGON.to_le_bytes());
+    bbox.iter().for_each(|x| header.extend(x.to_le_bytes()));
+    // The z and m ranges, which polygons don't have
+    header.extend([0; 32]);
+
+    header
+}
+
+/// Builds a dBase III attribute table with a single numeric `ID` field, and a single record with an ID of zero
+fn attribute_table() -> Vec<u8> {
+    let header_len: u16 = 32 + 32 + 1;
+    let record_len: u16 = 1 + ID_WIDTH as u16;
+    let (year, month, day) = today();
+
+    let mut table = Vec::new();
+    table.push(0x03);
+    table.extend([(year - 1900) as u8, month, day]);
+    table.extend(1u32.to_le_bytes());
+    table.extend(header_len.to_le_bytes());
+    table.extend(record_len.to_le_bytes());
+    table.extend([0; 20]);
+
+    // The field descriptor: a null-padded name, the type, a reserved address, the width, and the decimal count
+    let mut name = [0; 11];
+    name[..2].copy_from_slice(b"ID");
+    table.extend(name);
+    table.push(b'N');
+    table.extend([0; 4]);
+    table.extend([ID_WIDTH, 0]);
+    table.extend([0; 14]);
+    table.push(0x0D);
+
+    // Records start with a space, which marks them as not deleted, and numbers are right-aligned
+    table.push(b' ');
+    table.extend(format!("{:>width$}", 0, width = ID_WIDTH as usize).bytes());
+    table.push(0x1A);
+
+    table
+}
+
+/// Today's date (in UTC) as a year, month, and day, for the last update date of the attribute table
+fn today() -> (i64, u8, u8) {
+    let days = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs() / 86_400)
+        .unwrap_or(0) as i64;
+
+    // Converts days since 1970-01-01 to a date in the proleptic Gregorian calendar
+    // See http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    (year, month as u8, day as u8)
+}
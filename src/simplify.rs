@@ -0,0 +1,32 @@
+use nalgebra::Point2 as Point;
+
+use crate::{HullScalar, metrics::cross};
+
+/// Simplifies a ring down to at most `count` vertices, by repeatedly dropping the least important vertex
+///
+/// This is the Visvalingam–Whyatt algorithm: a vertex's importance is the area of the triangle it makes with its neighbours,
+/// which is exactly how much the area of the ring changes when it's dropped.
+/// Importances are recomputed after every drop, and ties go to the earliest vertex.
+/// The remaining vertices keep their order and indices.
+///
+/// This is O(h²) in the number of ring points.
+pub(crate) fn simplify_to_count<T: HullScalar>(
+    ring: &[(usize, Point<T>)],
+    count: usize,
+) -> Vec<(usize, Point<T>)> {
+    let mut ring = ring.to_vec();
+
+    while ring.len() > count {
+        let n = ring.len();
+        // Twice the area of the triangle, which ranks the vertices just as well
+        let importance =
+            |k: usize| cross(ring[(k + n - 1) % n].1, ring[k].1, ring[(k + 1) % n].1).abs();
+        let least = (0..n)
+            .min_by(|&a, &b| importance(a).total_cmp(&importance(b)))
+            .expect("Ring has at least one point");
+
+        ring.remove(least);
+    }
+
+    ring
+}
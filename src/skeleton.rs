@@ -0,0 +1,108 @@
+use std::collections::HashMap;
+
+use nalgebra::Point2 as Point;
+
+use crate::{
+    HullScalar, delaunay::triangulate, indexed::IndexedHull, is_nan, metrics::circumcentre,
+};
+
+/// Chords of the triangulation which span at most this many steps around the resampled boundary are pruned
+///
+/// These only appear where the boundary turns a corner, and their skeleton segments make the tip of the branch into that corner.
+const SPUR_STEPS: usize = 2;
+
+/// Approximates the medial axis of a ring, as a set of line segments
+///
+/// The boundary is resampled so that no two consecutive samples are more than `sampling` apart,
+/// and the samples are triangulated with [`triangulate`].
+/// Dense samples make the (unconstrained) Delaunay triangulation conform to the boundary,
+/// so the triangles whose centroids are inside the ring cover it.
+/// The skeleton then joins the circumcentres of every pair of these triangles which share an edge.
+/// This is the part of the Voronoi diagram of the samples inside the ring, which converges to the medial axis as `sampling` shrinks.
+///
+/// Two kinds of segments are pruned:
+/// those with an endpoint outside of the ring (from thin triangles along the boundary),
+/// and those across chords which only cut off a corner of the boundary (see [`SPUR_STEPS`]).
+/// Segments of zero length, which come from cocircular samples, are dropped too.
+///
+/// The triangulation is O(m²) in the number of samples, so `sampling` shouldn't be much smaller than it needs to be.
+pub(crate) fn hull_skeleton<T: HullScalar>(
+    hull: &[(usize, Point<T>)],
+    sampling: T,
+) -> Vec<(Point<T>, Point<T>)> {
+    if hull.len() < 3 || is_nan(sampling) || sampling <= T::zero() {
+        return Vec::new();
+    }
+
+    let samples = resample(hull, sampling);
+    let n = samples.len();
+    let indexed = IndexedHull::build(hull.to_vec());
+    let three: T = nalgebra::convert(3.0);
+
+    let triangles: Vec<[usize; 3]> = triangulate(&samples)
+        .into_iter()
+        .filter(|&[a, b, c]| {
+            let centroid =
+                Point::from((samples[a].coords + samples[b].coords + samples[c].coords) / three);
+            indexed.query_contains(&centroid)
+        })
+        .collect();
+
+    // Every directed edge belongs to exactly one triangle, so shared edges are the ones whose reverse is also present
+    let owner: HashMap<(usize, usize), usize> = triangles
+        .iter()
+        .enumerate()
+        .flat_map(|(k, &[a, b, c])| [((a, b), k), ((b, c), k), ((c, a), k)])
+        .collect();
+    let centres: Vec<Point<T>> = triangles
+        .iter()
+        .map(|&[a, b, c]| circumcentre(samples[a], samples[b], samples[c]))
+        .collect();
+
+    let mut segments = Vec::new();
+    for (&(i, j), &k) in &owner {
+        let Some(&other) = owner.get(&(j, i)) else {
+            continue;
+        };
+        let steps = i.abs_diff(j).min(n - i.abs_diff(j));
+        if i > j || steps <= SPUR_STEPS {
+            continue;
+        }
+
+        let (a, b) = (centres[k], centres[other]);
+        if a != b && indexed.query_contains(&a) && indexed.query_contains(&b) {
+            segments.push((a, b));
+        }
+    }
+
+    // Hash map order isn't deterministic, so sort the segments to give the same output every time
+    segments.sort_by(|(a, b), (c, d)| {
+        a.x.total_cmp(&c.x)
+            .then(a.y.total_cmp(&c.y))
+            .then(b.x.total_cmp(&d.x))
+            .then(b.y.total_cmp(&d.y))
+    });
+    segments
+}
+
+/// Adds evenly spaced points along each edge of the ring, so that none are more than `sampling` apart
+///
+/// The original vertices are kept, and each edge is split into the fewest equal pieces which are short enough.
+fn resample<T: HullScalar>(hull: &[(usize, Point<T>)], sampling: T) -> Vec<Point<T>> {
+    let mut samples = Vec::new();
+
+    for (k, &(_, a)) in hull.iter().enumerate() {
+        let b = hull[(k + 1) % hull.len()].1;
+        let pieces = nalgebra::try_convert::<T, f64>(((b - a).norm() / sampling).ceil())
+            .unwrap_or(1.)
+            .max(1.) as usize;
+        let pieces_t: T = nalgebra::convert(pieces as f64);
+
+        samples.extend((0..pieces).map(|step| {
+            let t = nalgebra::convert::<f64, T>(step as f64) / pieces_t;
+            a + (b - a) * t
+        }));
+    }
+
+    samples
+}
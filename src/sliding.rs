@@ -0,0 +1,73 @@
+use std::collections::VecDeque;
+
+use nalgebra::{Point2 as Point, Scalar};
+
+/// A concave hull over a sliding window of the most recent points from a stream
+///
+/// Points are pushed in as they arrive, and once the window is full, each new point evicts the oldest one.
+/// The hull is recomputed lazily, the first time it is requested after the window changes.
+///
+/// Complexity:
+/// - Pushing a point is O(1)
+/// - Requesting the hull when the window hasn't changed is O(1)
+/// - Requesting the hull after the window has changed costs a full concave hull computation over the window
+#[derive(Debug, Clone)]
+pub struct SlidingHull<T: Scalar> {
+    pub(crate) concavity: T,
+    pub(crate) capacity: usize,
+    pub(crate) window: VecDeque<Point<T>>,
+    /// Cached hull of the current window, if it's up to date
+    pub(crate) hull: Option<Vec<(usize, Point<T>)>>,
+}
+
+impl<T: Scalar> SlidingHull<T> {
+    /// Creates a new, empty window which holds at most `capacity` points
+    ///
+    /// Panics if `capacity` is zero.
+    pub fn new(capacity: usize, concavity: T) -> Self {
+        assert!(capacity > 0, "Sliding window must hold at least one point");
+
+        Self {
+            concavity,
+            capacity,
+            window: VecDeque::with_capacity(capacity),
+            hull: None,
+        }
+    }
+
+    /// Adds a point to the window, returning the oldest point if it was evicted to make room
+    pub fn push(&mut self, point: Point<T>) -> Option<Point<T>> {
+        let evicted = if self.window.len() == self.capacity {
+            self.window.pop_front()
+        } else {
+            None
+        };
+
+        self.window.push_back(point);
+        self.hull = None;
+
+        evicted
+    }
+
+    /// The maximum number of points held in the window
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// The number of points currently in the window
+    pub fn len(&self) -> usize {
+        self.window.len()
+    }
+
+    /// Whether the window is empty
+    pub fn is_empty(&self) -> bool {
+        self.window.is_empty()
+    }
+
+    /// Iterates over the points in the window, from oldest to newest
+    ///
+    /// The indices returned by `hull` are positions in this iteration order.
+    pub fn points(&self) -> impl Iterator<Item = &Point<T>> {
+        self.window.iter()
+    }
+}
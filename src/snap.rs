@@ -0,0 +1,34 @@
+use std::collections::HashSet;
+
+use nalgebra::Point2 as Point;
+
+use crate::HullScalar;
+
+/// Rounds each point to the nearest multiple of `grid`, merging points which land in the same place
+///
+/// Returns the snapped points, along with the original index of each snapped point.
+/// When several points snap to the same place, the one which comes first in `points` is kept.
+pub(crate) fn snap_points<T: HullScalar>(
+    points: &[Point<T>],
+    grid: T,
+) -> (Vec<Point<T>>, Vec<usize>) {
+    let mut seen = HashSet::with_capacity(points.len());
+    let mut snapped = Vec::with_capacity(points.len());
+    let mut originals = Vec::with_capacity(points.len());
+
+    for (id, p) in points.iter().enumerate() {
+        let (x, y) = ((p.x / grid).round(), (p.y / grid).round());
+        let key = (grid_coordinate(x), grid_coordinate(y));
+        if seen.insert(key) {
+            snapped.push(Point::new(x * grid, y * grid));
+            originals.push(id);
+        }
+    }
+
+    (snapped, originals)
+}
+
+/// Converts a (whole) number of grid steps to an integer, so that it can be hashed
+fn grid_coordinate<T: HullScalar>(steps: T) -> i64 {
+    nalgebra::try_convert::<T, f64>(steps).unwrap_or(0.) as i64
+}
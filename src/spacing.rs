@@ -0,0 +1,108 @@
+use std::collections::HashMap;
+
+use nalgebra::Point2 as Point;
+
+use crate::{HullScalar, density::cell_coordinate};
+
+/// Computes the mean distance from each point to its nearest neighbour
+///
+/// Points are bucketed into a grid of square cells, with roughly one point per cell,
+/// and each nearest neighbour search expands outwards ring by ring from the point's own cell.
+/// For reasonably even point clouds, this is O(n).
+///
+/// Point clouds with fewer than two points have a mean nearest neighbour distance of zero.
+pub(crate) fn mean_nearest_neighbor_distance<T: HullScalar>(points: &[Point<T>]) -> T {
+    mean_kth_neighbor_distance(points, 1)
+}
+
+/// Computes the mean distance from each point to its `k`th nearest neighbour
+///
+/// This works in the same way as [`mean_nearest_neighbor_distance`], except that each search keeps the `k` nearest points,
+/// and only stops once the `k`th nearest is closer than anything left to search.
+/// This is O(nk) for reasonably even point clouds.
+///
+/// `k` is clamped to be at least one, and at most the number of other points.
+/// Point clouds with fewer than two points have a mean distance of zero.
+pub(crate) fn mean_kth_neighbor_distance<T: HullScalar>(points: &[Point<T>], k: usize) -> T {
+    if points.len() < 2 {
+        return T::zero();
+    }
+    let k = k.clamp(1, points.len() - 1);
+
+    let (mut mins, mut maxs) = (points[0], points[0]);
+    for p in points {
+        mins = mins.inf(p);
+        maxs = maxs.sup(p);
+    }
+    let extents = maxs - mins;
+
+    // Aim for about one point per cell
+    let n: T = nalgebra::convert(points.len() as f64);
+    let mut cell_size = (extents.x * extents.y / n).sqrt();
+    if cell_size <= T::zero() {
+        // The cloud is flat, so fall back to dividing up the longest side
+        cell_size = extents.x.max(extents.y) / n;
+    }
+    if cell_size <= T::zero() {
+        // Every point is in the same place
+        return T::zero();
+    }
+
+    let cell_of = |p: &Point<T>| {
+        let offset = (p - mins) / cell_size;
+        (cell_coordinate(offset.x), cell_coordinate(offset.y))
+    };
+    let mut grid: HashMap<(usize, usize), Vec<usize>> = HashMap::new();
+    for (i, p) in points.iter().enumerate() {
+        grid.entry(cell_of(p)).or_default().push(i);
+    }
+    let (columns, rows) = cell_of(&maxs);
+    let max_ring = columns.max(rows);
+
+    let mut total = T::zero();
+    for (i, p) in points.iter().enumerate() {
+        let (column, row) = cell_of(p);
+        // The distances to the nearest points found so far, in ascending order
+        let mut nearest: Vec<T> = Vec::with_capacity(k + 1);
+
+        for ring in 0..=max_ring {
+            // Every point outside of the rings searched so far is at least this far away
+            let reach = cell_size * nalgebra::convert::<f64, T>(ring.saturating_sub(1) as f64);
+            if nearest.len() == k && nearest[k - 1] <= reach {
+                break;
+            }
+
+            for cell in ring_cells(column, row, ring) {
+                for &j in grid.get(&cell).into_iter().flatten() {
+                    if i == j {
+                        continue;
+                    }
+
+                    let distance = (points[j] - p).norm();
+                    let position = nearest.partition_point(|&d| d <= distance);
+                    if position < k {
+                        nearest.insert(position, distance);
+                        nearest.truncate(k);
+                    }
+                }
+            }
+        }
+
+        total += nearest[k - 1];
+    }
+
+    total / n
+}
+
+/// Iterates over the cells which are exactly `ring` cells away from `(column, row)`, along either axis
+///
+/// Cells which would have negative coordinates are skipped.
+fn ring_cells(column: usize, row: usize, ring: usize) -> impl Iterator<Item = (usize, usize)> {
+    let ring = ring as isize;
+    (-ring..=ring)
+        .flat_map(move |dx| (-ring..=ring).map(move |dy| (dx, dy)))
+        .filter(move |(dx, dy)| dx.abs() == ring || dy.abs() == ring)
+        .filter_map(move |(dx, dy)| {
+            Some((column.checked_add_signed(dx)?, row.checked_add_signed(dy)?))
+        })
+}
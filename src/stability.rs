@@ -0,0 +1,43 @@
+use nalgebra::{Point2 as Point, Vector2 as Vector};
+
+use crate::{HullScalar, metrics::hausdorff_distance, rng::SplitMix64};
+
+/// Measures how much a hull changes when the point cloud is jittered
+///
+/// Each trial offsets every point by a random amount (uniformly distributed in `[-jitter, jitter]` along each axis),
+/// computes the hull of the jittered points with `hull`, and measures its Hausdorff distance from the hull of the original points.
+/// Both hulls are measured using the original positions of their points, so that the jitter itself doesn't count,
+/// only changes to which points are on the hull.
+///
+/// Returns the mean distance across all trials, or zero if there are no trials.
+pub(crate) fn hull_stability<T: HullScalar>(
+    points: &[Point<T>],
+    jitter: T,
+    trials: usize,
+    seed: u64,
+    hull: impl Fn(&[Point<T>]) -> Vec<(usize, Point<T>)>,
+) -> T {
+    if trials == 0 {
+        return T::zero();
+    }
+
+    let original_ring = |hull: Vec<(usize, Point<T>)>| -> Vec<Point<T>> {
+        hull.into_iter().map(|(id, _)| points[id]).collect()
+    };
+    let reference = original_ring(hull(points));
+
+    let mut rng = SplitMix64::new(seed);
+    let mut offset = || -> T { nalgebra::convert::<f64, T>(rng.next_f64() * 2. - 1.) * jitter };
+
+    let mut total = T::zero();
+    for _ in 0..trials {
+        let jittered: Vec<Point<T>> = points
+            .iter()
+            .map(|p| p + Vector::new(offset(), offset()))
+            .collect();
+
+        total += hausdorff_distance(&reference, &original_ring(hull(&jittered)));
+    }
+
+    total / nalgebra::convert::<f64, T>(trials as f64)
+}
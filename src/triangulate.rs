@@ -0,0 +1,76 @@
+use nalgebra::Point2 as Point;
+
+use crate::{
+    HullScalar,
+    metrics::{cross, signed_area},
+};
+
+/// Triangulates a simple polygon using ear clipping
+///
+/// `ring` is the polygon's vertices in order, and may wind in either direction.
+/// The ring is implicitly closed, so the last point connects back to the first.
+/// Returns triangles as positions into `ring`, each wound counter-clockwise.
+/// A polygon with `n` vertices produces `n - 2` triangles, and rings with fewer than three points produce none.
+///
+/// This is O(n³) in the worst case, but rings are generally small.
+pub(crate) fn ear_clip<T: HullScalar>(ring: &[Point<T>]) -> Vec<[usize; 3]> {
+    if ring.len() < 3 {
+        return Vec::new();
+    }
+
+    // Work in counter-clockwise order, so that ears are the corners which turn left
+    let mut remaining: Vec<usize> = (0..ring.len()).collect();
+    if signed_area(ring.iter().copied()) < T::zero() {
+        remaining.reverse();
+    }
+
+    let mut triangles = Vec::with_capacity(ring.len() - 2);
+    while remaining.len() > 3 {
+        let n = remaining.len();
+        let corner = |k: usize| {
+            [
+                remaining[(k + n - 1) % n],
+                remaining[k],
+                remaining[(k + 1) % n],
+            ]
+        };
+
+        let ear = (0..n)
+            .find(|&k| is_ear(ring, &remaining, corner(k)))
+            // Straight corners make empty triangles, so they can always be clipped
+            .or_else(|| {
+                (0..n).find(|&k| {
+                    let [a, b, c] = corner(k);
+                    cross(ring[a], ring[b], ring[c]) == T::zero()
+                })
+            })
+            // Only reachable if the ring isn't simple, but make sure we always terminate
+            .unwrap_or(0);
+
+        triangles.push(corner(ear));
+        remaining.remove(ear);
+    }
+    triangles.push([remaining[0], remaining[1], remaining[2]]);
+
+    triangles
+}
+
+/// Checks if the corner `[a, b, c]` of a counter-clockwise ring can be clipped off
+///
+/// This is true if the corner turns left, and no other vertex is inside (or on the edge of) the triangle.
+fn is_ear<T: HullScalar>(ring: &[Point<T>], remaining: &[usize], [a, b, c]: [usize; 3]) -> bool {
+    let (pa, pb, pc) = (ring[a], ring[b], ring[c]);
+    if cross(pa, pb, pc) <= T::zero() {
+        return false;
+    }
+
+    remaining
+        .iter()
+        .filter(|&&k| k != a && k != b && k != c)
+        .all(|&k| {
+            let p = ring[k];
+            cross(pa, pb, p) < T::zero()
+                || cross(pb, pc, p) < T::zero()
+                || cross(pc, pa, p) < T::zero()
+        })
+}
@@ -0,0 +1,178 @@
+use std::{collections::HashSet, error::Error, fmt};
+
+use nalgebra::Point2 as Point;
+
+use crate::{
+    HullScalar,
+    edge::Edge,
+    is_nan,
+    metrics::{cross, signed_area},
+    segment_intersect::edges_intersect,
+};
+
+/// The reasons a hull can fail validation against a point cloud
+///
+/// Hull positions are positions in the hull slice, and point indices are indices into the point cloud.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HullValidationError {
+    /// The hull vertex at `position` is not the point at its index in the point cloud
+    UnknownVertex {
+        /// Position of the vertex in the hull
+        position: usize,
+    },
+    /// The point at `index` appears in the hull more than once
+    RepeatedVertex {
+        /// Index of the point in the point cloud
+        index: usize,
+    },
+    /// Two edges of the hull intersect
+    ///
+    /// Each edge is identified by the hull position of its first vertex.
+    SelfIntersecting {
+        /// Position of the first edge in the hull
+        first: usize,
+        /// Position of the second edge in the hull
+        second: usize,
+    },
+    /// The hull winds clockwise (or encloses no area)
+    NotCounterClockwise,
+    /// The point at `index` is outside of the hull
+    PointOutside {
+        /// Index of the point in the point cloud
+        index: usize,
+    },
+}
+
+impl fmt::Display for HullValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownVertex { position } => {
+                write!(f, "hull vertex {position} does not match the point cloud")
+            }
+            Self::RepeatedVertex { index } => {
+                write!(f, "point {index} appears in the hull more than once")
+            }
+            Self::SelfIntersecting { first, second } => {
+                write!(f, "hull edges {first} and {second} intersect")
+            }
+            Self::NotCounterClockwise => write!(f, "hull does not wind counter-clockwise"),
+            Self::PointOutside { index } => write!(f, "point {index} is outside of the hull"),
+        }
+    }
+}
+
+impl Error for HullValidationError {}
+
+/// Checks that `hull` is a valid concave hull of `points`
+///
+/// Checks are run in the order of the variants of [`HullValidationError`], and the first failure is returned.
+/// Hulls with fewer than three vertices have no edges to check, so they only need to cover every point,
+/// either as a vertex or by lying on them (the single point, or the segment between the two points).
+pub(crate) fn validate_hull<T: HullScalar>(
+    points: &[Point<T>],
+    hull: &[(usize, Point<T>)],
+) -> Result<(), HullValidationError> {
+    // Every vertex must be a distinct input point
+    let mut seen = HashSet::with_capacity(hull.len());
+    for (position, (index, point)) in hull.iter().enumerate() {
+        if points.get(*index) != Some(point) {
+            return Err(HullValidationError::UnknownVertex { position });
+        }
+        if !seen.insert(*index) {
+            return Err(HullValidationError::RepeatedVertex { index: *index });
+        }
+    }
+
+    if hull.len() < 3 {
+        let covered = |p: &Point<T>| match hull {
+            [(_, a)] => p == a,
+            [(_, a), (_, b)] => on_segment(a, b, p),
+            _ => false,
+        };
+
+        return match (0..points.len())
+            .find(|&index| !seen.contains(&index) && !covered(&points[index]))
+        {
+            Some(index) => Err(HullValidationError::PointOutside { index }),
+            None => Ok(()),
+        };
+    }
+
+    // Vertices are distinct, so neighbouring edges only touch at their shared endpoint
+    let edges: Vec<Edge<T>> = (0..hull.len())
+        .map(|k| Edge::new(hull[k].0, hull[(k + 1) % hull.len()].0, points))
+        .collect();
+    for (first, e1) in edges.iter().enumerate() {
+        for (second, e2) in edges.iter().enumerate().skip(first + 1) {
+            if edges_intersect(e1, e2) {
+                return Err(HullValidationError::SelfIntersecting { first, second });
+            }
+        }
+    }
+
+    if signed_area(hull.iter().map(|(_, p)| *p)) <= T::zero() {
+        return Err(HullValidationError::NotCounterClockwise);
+    }
+
+    match points.iter().position(|p| !contains(hull, p)) {
+        Some(index) => Err(HullValidationError::PointOutside { index }),
+        None => Ok(()),
+    }
+}
+
+/// Checks if `point` is inside of (or on the boundary of) a simple hull
+pub(crate) fn contains<T: HullScalar>(hull: &[(usize, Point<T>)], point: &Point<T>) -> bool {
+    // Cast a ray in the +x direction, and count how many edges it crosses
+    let mut inside = false;
+    for k in 0..hull.len() {
+        let a = hull[k].1;
+        let b = hull[(k + 1) % hull.len()].1;
+
+        let on_line = cross(a, b, *point) == T::zero();
+        let in_bounds = point.x >= a.x.min(b.x)
+            && point.x <= a.x.max(b.x)
+            && point.y >= a.y.min(b.y)
+            && point.y <= a.y.max(b.y);
+        if on_line && in_bounds {
+            return true;
+        }
+
+        if (a.y > point.y) != (b.y > point.y) {
+            let crossing = a.x + (point.y - a.y) * (b.x - a.x) / (b.y - a.y);
+            if point.x < crossing {
+                inside = !inside;
+            }
+        }
+    }
+
+    inside
+}
+
+/// Checks if `point` lies exactly on one of the edges of a closed hull
+pub(crate) fn on_boundary<T: HullScalar>(hull: &[(usize, Point<T>)], point: &Point<T>) -> bool {
+    (0..hull.len()).any(|k| on_segment(&hull[k].1, &hull[(k + 1) % hull.len()].1, point))
+}
+
+/// Checks if `p` lies on the segment from `a` to `b`, including its endpoints
+pub(crate) fn on_segment<T: HullScalar>(a: &Point<T>, b: &Point<T>, p: &Point<T>) -> bool {
+    cross(*a, *b, *p) == T::zero()
+        && p.x >= a.x.min(b.x)
+        && p.x <= a.x.max(b.x)
+        && p.y >= a.y.min(b.y)
+        && p.y <= a.y.max(b.y)
+}
+
+/// Checks if a hull passed to one of the public helpers is empty, so that the helper can return its empty result straight away
+///
+/// Every helper which takes a hull accepts an empty one: measurements are zero (or the origin),
+/// lists of vertices and rings are empty, containment is always false, and serializers write their empty form.
+/// Hulls of finite points never have NaN coordinates, so non-empty hulls are checked for them in debug builds,
+/// since a single NaN silently poisons every measurement.
+pub(crate) fn is_empty_hull<T: HullScalar>(hull: &[(usize, Point<T>)]) -> bool {
+    debug_assert!(
+        hull.iter().all(|(_, p)| !is_nan(p.x) && !is_nan(p.y)),
+        "Hull has a NaN coordinate"
+    );
+
+    hull.is_empty()
+}
@@ -0,0 +1,82 @@
+use nalgebra::{Point2 as Point, Scalar};
+
+use crate::{HullScalar, is_nan};
+
+/// Periodic coordinates, for point clouds which wrap around, such as longitudes on a map
+///
+/// An axis with a period wraps around every `period` units, so that (for example) with an x period of 360,
+/// `x = 179` and `x = -179` are only 2 units apart, across the seam.
+/// Axes without a period are left as-is.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Wrap<T: Scalar> {
+    /// The period of the x axis, if it wraps around
+    pub x_period: Option<T>,
+    /// The period of the y axis, if it wraps around
+    pub y_period: Option<T>,
+}
+
+/// Shifts points by whole periods along each wrapped axis, so that the point cloud is contiguous
+///
+/// Along each axis, the widest empty gap around the circle is found, and the circle is cut there,
+/// so that the cloud doesn't straddle the seam anymore.
+/// The first point always keeps its original coordinates, and every other point is moved by a whole number of periods.
+/// Periods which aren't positive are ignored.
+pub(crate) fn unwrap_points<T: HullScalar>(points: &[Point<T>], wrap: &Wrap<T>) -> Vec<Point<T>> {
+    let mut unwrapped = points.to_vec();
+    if let Some(period) = wrap.x_period {
+        unwrap_axis(&mut unwrapped, period, |p| &mut p.x);
+    }
+    if let Some(period) = wrap.y_period {
+        unwrap_axis(&mut unwrapped, period, |p| &mut p.y);
+    }
+
+    unwrapped
+}
+
+/// Unwraps a single axis, as described in [`unwrap_points`]
+fn unwrap_axis<T: HullScalar>(
+    points: &mut [Point<T>],
+    period: T,
+    axis: impl Fn(&mut Point<T>) -> &mut T,
+) {
+    if points.is_empty() || is_nan(period) || period <= T::zero() {
+        return;
+    }
+
+    let coordinates: Vec<T> = points.iter_mut().map(|p| *axis(p)).collect();
+
+    // The number of whole periods to add to each coordinate to bring it into [0, period)
+    let mut turns: Vec<T> = coordinates.iter().map(|&c| -(c / period).floor()).collect();
+    let reduced: Vec<T> = coordinates
+        .iter()
+        .zip(&turns)
+        .map(|(&c, &turn)| c + turn * period)
+        .collect();
+    let mut order: Vec<usize> = (0..points.len()).collect();
+    order.sort_unstable_by(|&a, &b| reduced[a].total_cmp(&reduced[b]));
+
+    // Start with the gap across the seam, from the largest coordinate around to the smallest
+    let mut widest = reduced[order[0]] + period - reduced[order[order.len() - 1]];
+    let mut cut = None;
+    for k in 1..order.len() {
+        let gap = reduced[order[k]] - reduced[order[k - 1]];
+        if gap > widest {
+            widest = gap;
+            cut = Some(k);
+        }
+    }
+
+    // Everything before the cut goes around once more, to come after everything past the cut
+    if let Some(cut) = cut {
+        for &k in &order[..cut] {
+            turns[k] += T::one();
+        }
+    }
+
+    let anchor = turns[0];
+    for (p, turn) in points.iter_mut().zip(turns) {
+        if turn != anchor {
+            *axis(p) += (turn - anchor) * period;
+        }
+    }
+}